@@ -0,0 +1,32 @@
+// 地面网格着色器：按世界坐标在平面上程序化绘制次/主网格线，线宽恒为约 1 像素，随距离淡出
+
+#import bevy_pbr::{
+    forward_io::VertexOutput,
+    mesh_view_bindings::view,
+}
+
+// 颜色（线性空间），a 为主网格线不透明度
+@group(#{MATERIAL_BIND_GROUP}) @binding(0) var<uniform> color: vec4<f32>;
+// x: 次网格间距，y: 主网格为次网格的倍数，z: 次网格线不透明度（0~1），w: 淡出半径
+@group(#{MATERIAL_BIND_GROUP}) @binding(1) var<uniform> params: vec4<f32>;
+
+// 到最近网格线的距离（以像素计）换算为覆盖率
+fn grid_line(coord: vec2<f32>, spacing: f32) -> f32 {
+    let cell = coord / spacing;
+    let width = max(fwidth(cell), vec2<f32>(1e-6));
+    let distance_px = abs(fract(cell - 0.5) - 0.5) / width;
+    return 1.0 - min(min(distance_px.x, distance_px.y), 1.0);
+}
+
+@fragment
+fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+    let p = in.world_position.xz;
+    let minor = grid_line(p, params.x) * params.z * 0.5;
+    let major = grid_line(p, params.x * params.y);
+    let fade = 1.0 - smoothstep(params.w * 0.4, params.w, distance(p, view.world_position.xz));
+    let alpha = color.a * max(minor, major) * fade;
+    if (alpha < 0.005) {
+        discard;
+    }
+    return vec4<f32>(color.rgb, alpha);
+}
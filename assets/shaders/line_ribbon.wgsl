@@ -0,0 +1,94 @@
+// 带状线着色器：每个端点两个顶点，沿线段在屏幕上的法向展开；虚线按沿线距离裁剪
+// 网格带顶点颜色时（VERTEX_COLORS）与材质颜色相乘
+
+#import bevy_pbr::{
+    mesh_functions::{get_world_from_local, mesh_position_local_to_world},
+    view_transformations::position_world_to_clip,
+    mesh_view_bindings::view,
+}
+
+// 颜色（线性空间）
+@group(#{MATERIAL_BIND_GROUP}) @binding(0) var<uniform> color: vec4<f32>;
+// x: 线宽，y: 1 为屏幕像素，z: 虚线周期（0 为实线）
+@group(#{MATERIAL_BIND_GROUP}) @binding(1) var<uniform> params: vec4<f32>;
+// 实线段 / 间隔 / 实线段 / 间隔
+@group(#{MATERIAL_BIND_GROUP}) @binding(2) var<uniform> dash: vec4<f32>;
+
+struct Vertex {
+    @builtin(instance_index) instance_index: u32,
+    @location(0) position: vec3<f32>,
+    @location(1) other: vec3<f32>,
+    // x: 0 起点 / 1 终点，y: 展开方向 ±1
+    @location(2) uv: vec2<f32>,
+#ifdef VERTEX_COLORS
+    @location(3) vertex_color: vec4<f32>,
+#endif
+};
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    // 沿线世界距离（透视校正插值）
+    @location(0) along_world: f32,
+    // 沿线屏幕像素距离（屏幕空间线性插值）
+    @location(1) @interpolate(linear) along_px: f32,
+#ifdef VERTEX_COLORS
+    @location(2) vertex_color: vec4<f32>,
+#endif
+};
+
+@vertex
+fn vertex(vertex: Vertex) -> VertexOutput {
+    let world_from_local = get_world_from_local(vertex.instance_index);
+    let p = mesh_position_local_to_world(world_from_local, vec4<f32>(vertex.position, 1.0)).xyz;
+    let q = mesh_position_local_to_world(world_from_local, vec4<f32>(vertex.other, 1.0)).xyz;
+    let clip_p = position_world_to_clip(p);
+    let clip_q = position_world_to_clip(q);
+
+    let half_viewport = view.viewport.zw * 0.5;
+    let screen_p = clip_p.xy / clip_p.w * half_viewport;
+    let screen_q = clip_q.xy / clip_q.w * half_viewport;
+    var dir = screen_q - screen_p;
+    let screen_length = length(dir);
+    dir = select(vec2<f32>(1.0, 0.0), dir / screen_length, screen_length > 1e-5);
+    // 终点的另一端点是起点，方向取反以保持两侧一致
+    if (vertex.uv.x > 0.5) {
+        dir = -dir;
+    }
+    let normal = vec2<f32>(-dir.y, dir.x);
+
+    var width_px = params.x;
+    if (params.y < 0.5) {
+        // 世界宽度换算为像素：投影缩放 * 视口高度的一半 / 深度
+        width_px = params.x * view.clip_from_view[1][1] * half_viewport.y / max(clip_p.w, 1e-4);
+    }
+    // 不足 1 像素时保持 1 像素，避免远处线段闪烁消失
+    let offset = normal * vertex.uv.y * max(width_px, 1.0) * 0.5 / half_viewport;
+
+    var out: VertexOutput;
+    out.clip_position = vec4<f32>(clip_p.xy + offset * clip_p.w, clip_p.zw);
+    out.along_world = vertex.uv.x * distance(p, q);
+    out.along_px = vertex.uv.x * screen_length;
+#ifdef VERTEX_COLORS
+    out.vertex_color = vertex.vertex_color;
+#endif
+    return out;
+}
+
+@fragment
+fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+    let period = params.z;
+    if (period > 0.0) {
+        let along = select(in.along_world, in.along_px, params.y > 0.5);
+        let phase = along - floor(along / period) * period;
+        let in_gap = (phase >= dash.x && phase < dash.x + dash.y)
+            || phase >= dash.x + dash.y + dash.z;
+        if (in_gap) {
+            discard;
+        }
+    }
+#ifdef VERTEX_COLORS
+    return color * in.vertex_color;
+#else
+    return color;
+#endif
+}
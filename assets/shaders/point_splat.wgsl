@@ -0,0 +1,57 @@
+// 点 splat 着色器：每个点的 4 个顶点位置相同，按 UV 角点在裁剪空间展开为面向相机的方块/圆点
+
+#import bevy_pbr::{
+    mesh_functions::{get_world_from_local, mesh_position_local_to_world},
+    view_transformations::position_world_to_clip,
+    mesh_view_bindings::view,
+}
+
+// 颜色（线性空间）
+@group(#{MATERIAL_BIND_GROUP}) @binding(0) var<uniform> color: vec4<f32>;
+// x: 大小，y: 1 为世界尺寸，z: 衰减距离（0 不衰减），w: 1 为圆点
+@group(#{MATERIAL_BIND_GROUP}) @binding(1) var<uniform> params: vec4<f32>;
+
+struct Vertex {
+    @builtin(instance_index) instance_index: u32,
+    @location(0) position: vec3<f32>,
+    @location(2) uv: vec2<f32>,
+};
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) corner: vec2<f32>,
+};
+
+@vertex
+fn vertex(vertex: Vertex) -> VertexOutput {
+    let world_from_local = get_world_from_local(vertex.instance_index);
+    let world_position = mesh_position_local_to_world(world_from_local, vec4<f32>(vertex.position, 1.0));
+    var clip = position_world_to_clip(world_position.xyz);
+
+    // 透视投影下 clip.w 即视空间深度；正交投影恒为 1
+    let depth = max(clip.w, 1e-4);
+    var size_px = params.x;
+    if (params.y > 0.5) {
+        // 世界尺寸换算为像素：投影缩放 * 视口高度的一半 / 深度
+        size_px = params.x * view.clip_from_view[1][1] * view.viewport.w * 0.5 / depth;
+    } else if (params.z > 0.0) {
+        size_px = params.x / max(1.0, depth / params.z);
+    }
+
+    let corner = vertex.uv * 2.0 - 1.0;
+    let offset_ndc = corner * max(size_px, 1.0) / view.viewport.zw;
+    clip = vec4<f32>(clip.xy + offset_ndc * clip.w, clip.zw);
+
+    var out: VertexOutput;
+    out.clip_position = clip;
+    out.corner = corner;
+    return out;
+}
+
+@fragment
+fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+    if (params.w > 0.5 && dot(in.corner, in.corner) > 1.0) {
+        discard;
+    }
+    return color;
+}
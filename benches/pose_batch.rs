@@ -0,0 +1,86 @@
+//! 批量位姿更新基准 — 对比逐个 `Update` Unit 与 `PoseBatch`
+//!
+//! 运行：`cargo bench --bench pose_batch`
+//!
+//! 统计每轮更新的编码字节数、Unit 数，以及「编码 → 解码 → KeyFrame 应用」的耗时。
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use expto::rdmp::{ExTransform, Unit};
+use expto::rdmp::decoding::decode;
+use expto::rdmp::encoding::encode;
+use redra::data::frame::KeyFrame;
+use redra_client::{EntityHandle, ShapeBuilder, pose_batch_units};
+
+const ENTITIES: u64 = 5000;
+const ROUNDS: u32 = 20;
+
+fn scene() -> KeyFrame {
+    let mut keyframe = KeyFrame::new(0);
+    for id in 0..ENTITIES {
+        for unit in ShapeBuilder::sphere(0.1).id(id).build().1 {
+            keyframe.update(&unit);
+        }
+    }
+    keyframe
+}
+
+fn poses(round: u32) -> Vec<ExTransform> {
+    (0..ENTITIES)
+        .map(|i| ExTransform { x: i as f32, y: round as f32 * 0.1, ..ExTransform::identity() })
+        .collect()
+}
+
+/// 编码、解码并应用到关键帧，返回（编码字节数，耗时）
+fn run(keyframe: &mut KeyFrame, units: &[Unit]) -> (usize, Duration) {
+    let start = Instant::now();
+    let mut bytes = 0;
+    for unit in units {
+        let buf = encode(unit).expect("编码失败");
+        bytes += buf.len();
+        keyframe.update(&decode(&buf).expect("解码失败"));
+    }
+    (bytes, start.elapsed())
+}
+
+fn report(name: &str, units: usize, bytes: usize, total: Duration) {
+    println!(
+        "{:<10} {:>6} 个 Unit/轮  {:>9} 字节/轮  {:>10.3} ms/轮",
+        name,
+        units,
+        bytes,
+        total.as_secs_f64() * 1000.0 / ROUNDS as f64,
+    );
+}
+
+fn main() {
+    let ids: Vec<u64> = (0..ENTITIES).collect();
+
+    let mut keyframe = scene();
+    let (mut bytes, mut total, mut count) = (0, Duration::ZERO, 0);
+    for round in 0..ROUNDS {
+        let units: Vec<Unit> = ids.iter()
+            .zip(poses(round))
+            .flat_map(|(&id, pose)| EntityHandle::from_id(id).transform_units(pose))
+            .collect();
+        count = units.len();
+        let (b, t) = run(&mut keyframe, &units);
+        bytes = b;
+        total += t;
+    }
+    black_box(&keyframe);
+    report("逐个更新", count, bytes, total);
+
+    let mut keyframe = scene();
+    let (mut bytes, mut total, mut count) = (0, Duration::ZERO, 0);
+    for round in 0..ROUNDS {
+        let units = pose_batch_units(&ids, &poses(round), &[]).expect("构造批量更新失败");
+        count = units.len();
+        let (b, t) = run(&mut keyframe, &units);
+        bytes = b;
+        total += t;
+    }
+    black_box(&keyframe);
+    report("PoseBatch", count, bytes, total);
+}
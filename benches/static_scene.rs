@@ -0,0 +1,75 @@
+//! 静止场景渲染基准 — 对比变化驱动渲染与每帧全量重扫
+//!
+//! 运行：`cargo bench --bench static_scene`
+//!
+//! 构造含 20 万个点的单帧场景，在最小 bevy 世界中驱动 `FrameRendererPlugin`，
+//! 统计数据不变时每次 `Update` 的耗时；「全量重扫」在每次更新前标记帧数据已变化，等价于改动前的行为。
+
+use std::time::{Duration, Instant};
+
+use bevy::prelude::*;
+use expto::rdmp::{ExMesh, ExTransform, Point};
+use redra::assets::materials::{GenericMaterial, MaterialManager};
+use redra::data::frame::{FrameManager, KeyFrame};
+use redra::data::namespace::NamespaceFilter;
+use redra::data::tag::{TagFilter, TagRegistry};
+use redra::render::box_style::BoxStyleOverride;
+use redra::render::coord_system::CoordSystem;
+use redra::render::frame_renderer::FrameRendererPlugin;
+use redra::render::line_ribbon::LineMaterial;
+use redra::render::point_lod::PointBudget;
+use redra::render::point_splat::{PointSplatMaterial, PointStyle};
+
+const POINTS: u64 = 200_000;
+const TICKS: u32 = 50;
+
+fn scene_app() -> App {
+    let mut keyframe = KeyFrame::new(0);
+    for id in 0..POINTS {
+        let (x, y) = ((id % 500) as f32 * 0.1, (id / 500) as f32 * 0.1);
+        keyframe.insert_entity(id, ExMesh::from(Point { x, y, z: 0.0 }), ExTransform::identity());
+    }
+    let mut frame_manager = FrameManager::new();
+    frame_manager.add_keyframe(keyframe);
+
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, AssetPlugin::default(), FrameRendererPlugin))
+        .init_asset::<Mesh>()
+        .init_asset::<GenericMaterial>()
+        .init_asset::<PointSplatMaterial>()
+        .init_asset::<LineMaterial>()
+        .init_asset::<StandardMaterial>()
+        .init_resource::<PointStyle>()
+        .init_resource::<BoxStyleOverride>()
+        .init_resource::<PointBudget>()
+        .init_resource::<MaterialManager>()
+        .init_resource::<TagFilter>()
+        .init_resource::<TagRegistry>()
+        .init_resource::<NamespaceFilter>()
+        .init_resource::<CoordSystem>()
+        .insert_resource(frame_manager);
+    app
+}
+
+/// 首次渲染后再执行 `TICKS` 次更新，返回每次更新的平均耗时
+fn run(force_rescan: bool) -> Duration {
+    let mut app = scene_app();
+    app.update();
+
+    let start = Instant::now();
+    for _ in 0..TICKS {
+        if force_rescan {
+            app.world_mut().resource_mut::<FrameManager>().touch();
+        }
+        app.update();
+    }
+    start.elapsed() / TICKS
+}
+
+fn main() {
+    let rescan = run(true);
+    let incremental = run(false);
+    println!("{:<10} {:>10.3} ms/帧", "全量重扫", rescan.as_secs_f64() * 1000.0);
+    println!("{:<10} {:>10.3} ms/帧", "变化驱动", incremental.as_secs_f64() * 1000.0);
+    println!("CPU 耗时降低 {:.1}%", (1.0 - incremental.as_secs_f64() / rescan.as_secs_f64()) * 100.0);
+}
@@ -13,6 +13,9 @@ fn main() -> Result<()> {
         .out_dir("src/rdmp/proto/")
         .extern_path(".google.protobuf.Any", "::prost::alloc::boxed::Box<::prost_types::Any>")
         .type_attribute(".", "#[derive(serde::Serialize, serde::Deserialize)]")
+        // 四元数参与序列化，SQLite 中的 Tag 偏移不丢失旋转；自描述格式缺少该字段时为 None。
+        // bincode 不自描述，此前写入的 Tag 没有该字段，由 FrameStorage 按旧布局兼容解码
+        .field_attribute(".transform.ExTransform.rotation", "#[serde(default)]")
         .compile_protos(
             &[
                 "command.proto",
@@ -139,6 +139,7 @@ pub fn config_to_unit(config: &EntityConfig, entity_id: u64) -> crate::rdmp::Uni
         sx: config.scale[0],
         sy: config.scale[1],
         sz: config.scale[2],
+        rotation: None,
     };
     
     // 添加对象到 Unit（按照 react_spawn 的期望顺序：Id + Mesh + Transform + MaterialId）
@@ -3,6 +3,7 @@ pub mod ip;
 pub mod api;
 pub mod prelude;
 pub mod config;
+pub mod schema;
 
 /// 初始化日志系统
 pub fn init_log() {
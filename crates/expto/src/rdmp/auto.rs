@@ -2,5 +2,6 @@ pub mod stamper;
 pub mod unit;
 pub mod mesh;
 pub mod object;
+pub mod transform;
 
 pub use stamper::*;
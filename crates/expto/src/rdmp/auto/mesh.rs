@@ -1,4 +1,4 @@
-use crate::rdmp::{Cone, Cube, Cylinder, ExMesh, Line, Point, Sphere, ex_mesh };
+use crate::rdmp::{Color, Cone, Cube, Cylinder, ExMesh, Line, OrientedBox, Point, Polyline, Sphere, TriangleMesh, ex_mesh };
 
 impl ExMesh {
     pub fn set_point<T: Into<Point>>(&mut self, point: T) -> Result<(), String> {
@@ -30,6 +30,21 @@ impl ExMesh {
         self.u_mesh = Some(ex_mesh::UMesh::Cube(cube));
         Ok(())
     }
+
+    pub fn set_triangle_mesh(&mut self, triangle_mesh: TriangleMesh) -> Result<(), String> {
+        self.u_mesh = Some(ex_mesh::UMesh::TriangleMesh(triangle_mesh));
+        Ok(())
+    }
+
+    pub fn set_oriented_box(&mut self, oriented_box: OrientedBox) -> Result<(), String> {
+        self.u_mesh = Some(ex_mesh::UMesh::OrientedBox(oriented_box));
+        Ok(())
+    }
+
+    pub fn set_polyline<T: Into<Polyline>>(&mut self, polyline: T) -> Result<(), String> {
+        self.u_mesh = Some(ex_mesh::UMesh::Polyline(polyline.into()));
+        Ok(())
+    }
 }
 
 impl From<Point> for ExMesh {
@@ -80,6 +95,30 @@ impl From<Cube> for ExMesh {
     }
 }
 
+impl From<OrientedBox> for ExMesh {
+    fn from(oriented_box: OrientedBox) -> Self {
+        ExMesh {
+            u_mesh: Some(ex_mesh::UMesh::OrientedBox(oriented_box)),
+        }
+    }
+}
+
+impl From<Polyline> for ExMesh {
+    fn from(polyline: Polyline) -> Self {
+        ExMesh {
+            u_mesh: Some(ex_mesh::UMesh::Polyline(polyline)),
+        }
+    }
+}
+
+impl From<TriangleMesh> for ExMesh {
+    fn from(triangle_mesh: TriangleMesh) -> Self {
+        ExMesh {
+            u_mesh: Some(ex_mesh::UMesh::TriangleMesh(triangle_mesh)),
+        }
+    }
+}
+
 // 实现 Into<Point> trait 以便支持更多类型的输入
 impl From<(f32, f32, f32)> for Point {
     fn from((x, y, z): (f32, f32, f32)) -> Self {
@@ -103,6 +142,7 @@ impl From<(Point, Point)> for Line {
         Line {
             start: Some(start),
             end: Some(end),
+            ..Default::default()
         }
     }
 }
@@ -124,13 +164,61 @@ impl From<(f32, f32)> for Cone {
 // 实现 Cube 构造
 impl From<Vec<Point>> for Cube {
     fn from(vertices: Vec<Point>) -> Self {
-        Cube { vertices }
+        Cube { vertices, ..Default::default() }
     }
 }
 
 impl<const N: usize> From<[(f32, f32, f32); N]> for Cube {
     fn from(arr: [(f32, f32, f32); N]) -> Self {
         let vertices = arr.iter().map(|&(x, y, z)| Point { x, y, z }).collect();
-        Cube { vertices }
+        Cube { vertices, ..Default::default() }
+    }
+}
+
+impl From<[f32; 3]> for Point {
+    fn from([x, y, z]: [f32; 3]) -> Self {
+        Point { x, y, z }
+    }
+}
+
+impl From<[f32; 4]> for Color {
+    fn from([r, g, b, a]: [f32; 4]) -> Self {
+        Color { r, g, b, a }
+    }
+}
+
+// 实现 TriangleMesh 构造（顶点 + 索引，法线与颜色留空）
+impl From<(Vec<[f32; 3]>, Vec<u32>)> for TriangleMesh {
+    fn from((vertices, indices): (Vec<[f32; 3]>, Vec<u32>)) -> Self {
+        TriangleMesh {
+            vertices: vertices.into_iter().map(Point::from).collect(),
+            normals: Vec::new(),
+            colors: Vec::new(),
+            indices,
+        }
+    }
+}
+
+// 实现 Polyline 构造（开放路径，无颜色，默认线宽）
+impl From<Vec<[f32; 3]>> for Polyline {
+    fn from(points: Vec<[f32; 3]>) -> Self {
+        Polyline {
+            points: points.into_iter().map(Point::from).collect(),
+            colors: Vec::new(),
+            closed: false,
+            width: 0.0,
+        }
+    }
+}
+
+// 实现 OrientedBox 构造（中心, 半边长, 四元数 [x, y, z, w]），默认实体绘制
+impl From<([f32; 3], [f32; 3], [f32; 4])> for OrientedBox {
+    fn from((center, half_extents, rotation): ([f32; 3], [f32; 3], [f32; 4])) -> Self {
+        OrientedBox {
+            center: Some(center.into()),
+            half_extents: Some(half_extents.into()),
+            rotation: Some(rotation.into()),
+            ..Default::default()
+        }
     }
 }
@@ -1,4 +1,4 @@
-use crate::rdmp::{ExMesh, ExObject, ExTransform, ex_object, Tag, TagCollectionDef, TagOption, TagStyle};
+use crate::rdmp::{ExMesh, ExObject, ExTransform, FrameTransform, Lifetime, PoseBatch, ex_object, Tag, TagCollectionDef, TagOption, TagStyle};
 
 
 impl ExObject {
@@ -74,6 +74,118 @@ impl ExObject {
         self.u_object = Some(ex_object::UObject::TagCollectionDef(def.into()));
         Ok(())
     }
+
+    pub fn set_parent_id<T: Into<u64>>(&mut self, parent_id: T) -> Result<(), String> {
+        self.u_object = Some(ex_object::UObject::ParentId(parent_id.into()));
+        Ok(())
+    }
+
+    /// 父实体引用（`u64` 的 `From` 已用于实体 ID，故单独提供构造函数）
+    pub fn parent(parent_id: u64) -> Self {
+        ExObject {
+            u_object: Some(ex_object::UObject::ParentId(parent_id)),
+        }
+    }
+
+    pub fn set_frame_id<T: Into<String>>(&mut self, frame_id: T) -> Result<(), String> {
+        self.u_object = Some(ex_object::UObject::FrameId(frame_id.into()));
+        Ok(())
+    }
+
+    /// 坐标系引用 — 同组 transform 在该命名坐标系中表达
+    pub fn frame_id(frame_id: impl Into<String>) -> Self {
+        ExObject {
+            u_object: Some(ex_object::UObject::FrameId(frame_id.into())),
+        }
+    }
+
+    pub fn set_namespace<T: Into<String>>(&mut self, namespace: T) -> Result<(), String> {
+        self.u_object = Some(ex_object::UObject::Namespace(namespace.into()));
+        Ok(())
+    }
+
+    /// 命名空间 — Spawn/Update 中标记实体所属图层，Clear 中指定清除范围
+    pub fn namespace(namespace: impl Into<String>) -> Self {
+        ExObject {
+            u_object: Some(ex_object::UObject::Namespace(namespace.into())),
+        }
+    }
+}
+
+impl From<Lifetime> for ExObject {
+    fn from(lifetime: Lifetime) -> Self {
+        ExObject {
+            u_object: Some(ex_object::UObject::Lifetime(lifetime)),
+        }
+    }
+}
+
+impl Lifetime {
+    /// 按持续时间（毫秒）到期
+    pub fn duration_ms(duration_ms: u64) -> Self {
+        Self { duration_ms, frames: 0 }
+    }
+
+    /// 按帧数到期（含生成所在帧）
+    pub fn frames(frames: u32) -> Self {
+        Self { duration_ms: 0, frames }
+    }
+
+    /// 两项均为 0 时永不到期
+    pub fn is_permanent(&self) -> bool {
+        self.duration_ms == 0 && self.frames == 0
+    }
+
+    /// 生成于 `spawned_at`、已存在 `age_frames` 帧后，在时间 `now` 的帧中是否已到期
+    ///
+    /// `age_frames` 为生成所在帧之后经过的帧数（生成所在帧为 0）。
+    pub fn is_expired(&self, spawned_at: u64, now: u64, age_frames: u32) -> bool {
+        (self.duration_ms > 0 && now >= spawned_at.saturating_add(self.duration_ms))
+            || (self.frames > 0 && age_frames >= self.frames)
+    }
+}
+
+impl From<PoseBatch> for ExObject {
+    fn from(batch: PoseBatch) -> Self {
+        ExObject {
+            u_object: Some(ex_object::UObject::PoseBatch(batch)),
+        }
+    }
+}
+
+impl PoseBatch {
+    /// 由等长的 ID 与变换数组构造；长度不一致时返回错误
+    pub fn new(ids: Vec<u64>, transforms: Vec<ExTransform>) -> Result<Self, String> {
+        if ids.len() != transforms.len() {
+            return Err(format!("批量位姿更新的 ID 数量（{}）与变换数量（{}）不一致", ids.len(), transforms.len()));
+        }
+        Ok(Self { ids, transforms, materials: Vec::new() })
+    }
+
+    /// 附带逐实体材质（与 ID 等长，空字符串表示不修改）
+    pub fn with_materials(mut self, materials: Vec<String>) -> Result<Self, String> {
+        if !materials.is_empty() && materials.len() != self.ids.len() {
+            return Err(format!("批量位姿更新的材质数量（{}）与 ID 数量（{}）不一致", materials.len(), self.ids.len()));
+        }
+        self.materials = materials;
+        Ok(self)
+    }
+
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+}
+
+impl From<FrameTransform> for ExObject {
+    fn from(frame_transform: FrameTransform) -> Self {
+        ExObject {
+            u_object: Some(ex_object::UObject::FrameTransform(frame_transform)),
+        }
+    }
 }
 
 // Tag 辅助构造函数
@@ -1,9 +1,11 @@
+use std::sync::OnceLock;
 use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
 
 use crate::rdmp::{ExStamp};
 
 static TIMESTAMP: AtomicU64 = AtomicU64::new(0);
 static SEQUENCE_NUMBER: AtomicU32 = AtomicU32::new(0);
+static SESSION_ID: OnceLock<String> = OnceLock::new();
 
 pub struct Stamper;
 
@@ -11,26 +13,57 @@ pub fn generate_stamp() -> ExStamp {
     Stamper::generate_stamp()
 }
 
+pub fn generate_stamp_at(timestamp: u64) -> ExStamp {
+    Stamper::generate_stamp_at(timestamp)
+}
+
 impl Stamper {
-    /// 获取当前时间戳（毫秒）
-    fn get_current_timestamp() -> u64 {
-        std::time::Instant::now().elapsed().as_millis() as u64
+    /// 获取当前时间戳（Unix 毫秒）
+    pub fn get_current_timestamp() -> u64 {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or_default()
     }
 
     /// 生成一个新的stamp，自动填充时间戳和序列号
     pub fn generate_stamp() -> ExStamp {
-        let ts = Self::get_current_timestamp();
+        Self::generate_stamp_at(Self::get_current_timestamp())
+    }
+
+    /// 生成带指定时间戳（Unix 毫秒，如传感器采集时间）的 stamp
+    pub fn generate_stamp_at(ts: u64) -> ExStamp {
         TIMESTAMP.store(ts, Ordering::Relaxed);
         
         let seq = SEQUENCE_NUMBER.fetch_add(1, Ordering::SeqCst);
         
         ExStamp {
             timestamp: ts,
-            session_id: String::default(), // 使用默认值
+            session_id: Self::session_id().to_string(),
             sequence_number: seq,
         }
     }
 
+    /// 当前进程的会话 ID
+    ///
+    /// 未通过 [`set_session_id`](Self::set_session_id) 指定时，首次访问自动生成
+    /// `session_{pid}_{毫秒时间戳}`，保证同时连接的多个客户端互不相同。
+    pub fn session_id() -> &'static str {
+        SESSION_ID.get_or_init(|| {
+            let now = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_millis())
+                .unwrap_or_default();
+            format!("session_{}_{}", std::process::id(), now)
+        })
+    }
+
+    /// 指定会话 ID（如传感器名称）；必须在生成第一个 stamp 之前调用
+    pub fn set_session_id(session_id: impl Into<String>) -> Result<(), String> {
+        SESSION_ID.set(session_id.into())
+            .map_err(|_| format!("会话 ID 已确定为 {}，无法修改", Self::session_id()))
+    }
+
     /// 获取当前序列号
     pub fn get_sequence_number(&self) -> u32 {
         SEQUENCE_NUMBER.load(Ordering::Relaxed)
@@ -52,6 +85,15 @@ mod tests {
         let stamp2 = Stamper::generate_stamp();
 
         assert_eq!(stamp1.sequence_number + 1, stamp2.sequence_number);
-        assert_eq!(stamp1.session_id, String::default()); // 验证使用了默认值
+        assert!(!stamp1.session_id.is_empty());
+        assert_eq!(stamp1.session_id, stamp2.session_id);
+        assert!(Stamper::set_session_id("late").is_err());
+
+        // 默认时间戳为 Unix 毫秒（2020 年之后）
+        assert!(stamp1.timestamp > 1_577_836_800_000);
+        assert!(stamp2.timestamp >= stamp1.timestamp);
+
+        let sensor = Stamper::generate_stamp_at(42);
+        assert_eq!(sensor.timestamp, 42);
     }
 }
\ No newline at end of file
@@ -0,0 +1,132 @@
+//! ExTransform 旋转约定
+//!
+//! 欧拉角 `rx/ry/rz` 按 bevy `EulerRot::XYZ` 解释：`R = Rx(rx) · Ry(ry) · Rz(rz)`。
+//! 可选的 `rotation` 四元数存在时优先；所有转换路径都应通过 [`ExTransform::quaternion`]
+//! 取得旋转，而不是各自解释欧拉角。
+
+use crate::rdmp::{ExTransform, FrameTransform, Quaternion};
+
+impl Quaternion {
+    pub const IDENTITY: Quaternion = Quaternion { x: 0.0, y: 0.0, z: 0.0, w: 1.0 };
+
+    /// 分量数组 `[x, y, z, w]`
+    pub fn to_array(self) -> [f32; 4] {
+        [self.x, self.y, self.z, self.w]
+    }
+
+    /// 由 XYZ 欧拉角（弧度）构造
+    pub fn from_euler_xyz(rx: f32, ry: f32, rz: f32) -> Self {
+        let (sx, cx) = (rx * 0.5).sin_cos();
+        let (sy, cy) = (ry * 0.5).sin_cos();
+        let (sz, cz) = (rz * 0.5).sin_cos();
+        Quaternion {
+            x: sx * cy * cz + cx * sy * sz,
+            y: cx * sy * cz - sx * cy * sz,
+            z: cx * cy * sz + sx * sy * cz,
+            w: cx * cy * cz - sx * sy * sz,
+        }
+    }
+
+    /// 转为 XYZ 欧拉角（弧度）；`ry = ±π/2` 万向节锁时 `rz` 取 0
+    pub fn to_euler_xyz(self) -> (f32, f32, f32) {
+        let Quaternion { x, y, z, w } = self;
+        let m02 = 2.0 * (x * z + w * y);
+        let ry = m02.clamp(-1.0, 1.0).asin();
+        if m02.abs() < 0.999_999 {
+            let m12 = 2.0 * (y * z - w * x);
+            let m22 = 1.0 - 2.0 * (x * x + y * y);
+            let m01 = 2.0 * (x * y - w * z);
+            let m00 = 1.0 - 2.0 * (y * y + z * z);
+            ((-m12).atan2(m22), ry, (-m01).atan2(m00))
+        } else {
+            let m21 = 2.0 * (y * z + w * x);
+            let m11 = 1.0 - 2.0 * (x * x + z * z);
+            (m21.atan2(m11), ry, 0.0)
+        }
+    }
+
+    /// 归一化；零四元数返回 `None`
+    pub fn normalized(self) -> Option<Self> {
+        let len = (self.x * self.x + self.y * self.y + self.z * self.z + self.w * self.w).sqrt();
+        if len < 1e-6 || !len.is_finite() {
+            return None;
+        }
+        Some(Quaternion { x: self.x / len, y: self.y / len, z: self.z / len, w: self.w / len })
+    }
+}
+
+impl From<[f32; 4]> for Quaternion {
+    fn from([x, y, z, w]: [f32; 4]) -> Self {
+        Quaternion { x, y, z, w }
+    }
+}
+
+impl ExTransform {
+    /// 单位变换
+    pub fn identity() -> Self {
+        ExTransform { sx: 1.0, sy: 1.0, sz: 1.0, ..Default::default() }
+    }
+
+    /// 生效的旋转（单位四元数）— `rotation` 存在且非零时使用它，否则由欧拉角换算
+    pub fn quaternion(&self) -> Quaternion {
+        self.rotation
+            .and_then(Quaternion::normalized)
+            .unwrap_or_else(|| Quaternion::from_euler_xyz(self.rx, self.ry, self.rz))
+    }
+
+    /// 以四元数设置旋转，同时回填等价的欧拉角供只认欧拉角的读取方使用
+    pub fn with_quaternion(mut self, rotation: impl Into<Quaternion>) -> Self {
+        let rotation = rotation.into().normalized().unwrap_or(Quaternion::IDENTITY);
+        (self.rx, self.ry, self.rz) = rotation.to_euler_xyz();
+        self.rotation = Some(rotation);
+        self
+    }
+}
+
+impl FrameTransform {
+    /// `frame_id` 坐标系在 `parent_frame_id` 坐标系中的位姿
+    pub fn new(frame_id: impl Into<String>, parent_frame_id: impl Into<String>, transform: ExTransform) -> Self {
+        FrameTransform {
+            frame_id: frame_id.into(),
+            parent_frame_id: parent_frame_id.into(),
+            transform: Some(transform),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn same_rotation(a: Quaternion, b: Quaternion) -> bool {
+        let dot = a.x * b.x + a.y * b.y + a.z * b.z + a.w * b.w;
+        dot.abs() > 1.0 - 1e-5
+    }
+
+    #[test]
+    fn test_euler_round_trip() {
+        for &(rx, ry, rz) in &[(0.3, -0.7, 1.2), (0.0, 0.0, 0.0), (-2.5, 0.4, 3.0), (0.2, std::f32::consts::FRAC_PI_2, 0.4)] {
+            let q = Quaternion::from_euler_xyz(rx, ry, rz);
+            let (ex, ey, ez) = q.to_euler_xyz();
+            assert!(same_rotation(q, Quaternion::from_euler_xyz(ex, ey, ez)), "({}, {}, {})", rx, ry, rz);
+        }
+    }
+
+    #[test]
+    fn test_quaternion_takes_precedence() {
+        let q = Quaternion::from_euler_xyz(0.0, 0.0, 1.0);
+        let t = ExTransform { rx: 2.0, rotation: Some(q), ..ExTransform::identity() };
+        assert!(same_rotation(t.quaternion(), q));
+
+        // 零四元数视为缺省，回退到欧拉角
+        let t = ExTransform { rx: 2.0, rotation: Some(Quaternion::default()), ..ExTransform::identity() };
+        assert!(same_rotation(t.quaternion(), Quaternion::from_euler_xyz(2.0, 0.0, 0.0)));
+    }
+
+    #[test]
+    fn test_with_quaternion_fills_euler() {
+        let q = Quaternion::from_euler_xyz(0.4, -0.3, 0.9);
+        let t = ExTransform::identity().with_quaternion(q);
+        assert!(same_rotation(Quaternion::from_euler_xyz(t.rx, t.ry, t.rz), q));
+    }
+}
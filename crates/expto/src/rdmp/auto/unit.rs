@@ -1,4 +1,4 @@
-use crate::rdmp::{ExCommand, CommandType, ExObject, Unit, auto::generate_stamp};
+use crate::rdmp::{ExCommand, CommandType, ExObject, Unit, auto::{generate_stamp, generate_stamp_at}};
 
 pub fn generate_unit() -> Unit { 
     Unit { 
@@ -8,6 +8,15 @@ pub fn generate_unit() -> Unit {
     }
 }
 
+/// 生成带指定时间戳（Unix 毫秒，如传感器采集时间）的 Unit
+pub fn generate_unit_at(timestamp: u64) -> Unit {
+    Unit {
+        stamp: Some(generate_stamp_at(timestamp)),
+        command: None,
+        objects: vec![],
+    }
+}
+
 impl Unit {
     // pub fn encoding(&self) -> Result<Vec<u8>, String> {
     //     let encoded_data = Unit::encode_to_vec(self);
@@ -46,6 +55,11 @@ impl Unit {
         Ok(())
     }
 
+    pub fn set_clear(&mut self) -> Result<(), String> {
+        self.command = Some(ExCommand { u_command: CommandType::Clear as i32 });
+        Ok(())
+    }
+
     pub fn set_object<T: Into<ExObject>>(&mut self, object: T) -> Result<(), String> {
         self.objects = vec![object.into()];
         Ok(())
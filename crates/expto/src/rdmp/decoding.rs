@@ -361,6 +361,7 @@ mod tests {
                         x: 1.0, y: 2.0, z: 3.0,
                         rx: 0.0, ry: 0.0, rz: 0.0,
                         sx: 1.0, sy: 1.0, sz: 1.0,
+                        rotation: None,
                     }))
                 },
                 ExObject { u_object: Some(UObject::Id(200)) },
@@ -269,6 +269,7 @@ mod tests {
                 sx: 1.0,
                 sy: 1.0,
                 sz: 1.0,
+                rotation: None,
             })),
         });
         
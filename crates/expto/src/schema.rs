@@ -0,0 +1,4 @@
+//! 录制数据库（SQLite）的表结构约定 — 主程序 `FrameStorage` 与客户端 `SqlWriter` 共用
+
+/// 后续版本新增的 entities 列（列名, 类型），旧版数据库打开时补齐
+pub const ADDED_ENTITY_COLUMNS: [(&str, &str); 3] = [("parent_id", "INTEGER"), ("frame_name", "TEXT"), ("namespace", "TEXT")];
@@ -63,6 +63,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         x: 0.0, y: 2.0, z: 0.0,
         rx: 0.0, ry: 0.0, rz: 0.0,
         sx: 1.0, sy: 1.0, sz: 1.0,
+        rotation: None,
     };
     spawn_sphere([3.0, 0.0, 0.0], 0.8, "blue")
         .id(3).tag(Tag::new("向上偏移").with_offset(offset)).send().await?;
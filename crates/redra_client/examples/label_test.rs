@@ -30,6 +30,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         x: 0.0, y: 2.0, z: 0.0,
         rx: 0.0, ry: 0.0, rz: 0.0,
         sx: 1.0, sy: 1.0, sz: 1.0,
+        rotation: None,
     };
     spawn_sphere([0.0, 0.0, 0.0], 1.0, "red")
         .id(1)
@@ -98,6 +99,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         x: 1.5, y: 1.5, z: 0.0,
         rx: 0.0, ry: 0.0, rz: 0.0,
         sx: 1.0, sy: 1.0, sz: 1.0,
+        rotation: None,
     };
     let complex_style = TagStyle::default_style()
         .with_font_size(22.0)
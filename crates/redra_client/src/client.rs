@@ -3,4 +3,6 @@ pub mod id;
 pub mod recv;
 pub mod send;
 pub mod builder;
+pub mod batch;
+pub mod handle;
 pub mod sql_writer;
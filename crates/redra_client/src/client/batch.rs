@@ -0,0 +1,283 @@
+//! 帧批量发送 — 将多个实体合并为少量 Unit，一次写入连接
+//!
+//! 逐个 `ShapeBuilder::send()` 时，每个实体都是一个独立 Unit、一次写入和一次 flush；
+//! 一帧数千个实体就是数千次小写入。[`FrameBatch`] 在本地累积实体，
+//! 按 Id 边界打包成不超过 `max_unit_bytes` 的 Unit，提交时连同 `Frameend`
+//! 一起编码到同一个缓冲区，只写一次。
+//!
+//! ```no_run
+//! use redra_client::{FrameBatch, spawn_sphere, spawn_point};
+//!
+//! # async fn run() -> Result<(), String> {
+//! let mut batch = FrameBatch::new();
+//! batch.add(spawn_sphere([0.0, 0.0, 0.0], 0.5, "red").id(1));
+//! for i in 0..2000 {
+//!     batch.add(spawn_point([i as f32 * 0.01, 0.0, 0.0], "green"));
+//! }
+//! batch.commit().await?;   // N 个 Unit + Frameend，一次写入
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! 必须显式 `commit().await`：未提交就被丢弃时不会发送任何内容，只记录一条警告。
+
+use expto::prelude::*;
+use expto::rdmp::auto::unit::{generate_unit, generate_unit_at};
+use prost::Message;
+
+use super::builder::ShapeBuilder;
+use super::link::get_link;
+
+/// 单个 Unit 的默认最大编码字节数（不含 Header）
+pub const DEFAULT_MAX_UNIT_BYTES: usize = 64 * 1024;
+
+/// 帧批量发送器
+///
+/// 通过 [`add`](Self::add) 累积实体，[`commit`](Self::commit) 时一次性发送并结束当前帧；
+/// 未提交就被丢弃的实体不会发送。
+pub struct FrameBatch {
+    max_unit_bytes: usize,
+    entities: Vec<Vec<ExObject>>,
+    timestamp: Option<u64>,
+    committed: bool,
+}
+
+impl FrameBatch {
+    /// 使用默认 Unit 大小上限（[`DEFAULT_MAX_UNIT_BYTES`]）
+    pub fn new() -> Self {
+        Self::with_max_unit_bytes(DEFAULT_MAX_UNIT_BYTES)
+    }
+
+    /// 指定单个 Unit 的最大编码字节数；超过上限时拆分为多个 Unit
+    ///
+    /// 单个实体本身超过上限时独占一个 Unit，不会被拆开。
+    pub fn with_max_unit_bytes(max_unit_bytes: usize) -> Self {
+        Self { max_unit_bytes, entities: Vec::new(), timestamp: None, committed: false }
+    }
+
+    /// 添加一个实体（或一个分组点云）到当前帧
+    ///
+    /// 未设置 ID 的实体会从全局 ID 生成器分配 ID，保证服务端按 Id 正确分组。
+    pub fn add(&mut self, builder: ShapeBuilder) -> &mut Self {
+        self.entities.extend(builder.into_entities());
+        self
+    }
+
+    /// 设置本帧的传感器时间（Unix 毫秒），写入所有 Unit 与 `Frameend`
+    ///
+    /// 未设置时使用各 Unit 生成时刻的系统时间。
+    pub fn timestamp(&mut self, timestamp_ms: u64) -> &mut Self {
+        self.timestamp = Some(timestamp_ms);
+        self
+    }
+
+    /// 已累积的实体数量
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+
+    /// 是否尚未添加任何实体
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+
+    /// 发送所有实体与 `Frameend`，一次写入连接
+    pub async fn commit(mut self) -> Result<(), String> {
+        let units = self.take_units();
+        send_units(&units).await
+    }
+
+    /// 取出打包好的 Unit（末尾为 `Frameend`），并标记为已提交
+    pub(crate) fn take_units(&mut self) -> Vec<Unit> {
+        self.committed = true;
+        let entities = std::mem::take(&mut self.entities);
+        let mut units = pack_entities(entities, self.max_unit_bytes, self.timestamp);
+        units.push(frame_end_unit(self.timestamp));
+        units
+    }
+}
+
+impl Default for FrameBatch {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for FrameBatch {
+    fn drop(&mut self) {
+        if self.committed {
+            return;
+        }
+        log::warn!("FrameBatch 未调用 commit() 就被丢弃，{} 个实体与 Frameend 未发送", self.entities.len());
+    }
+}
+
+/// 生成新 Unit；指定 `timestamp` 时使用传感器时间，否则使用系统时间
+fn new_unit(timestamp: Option<u64>) -> Unit {
+    match timestamp {
+        Some(ts) => generate_unit_at(ts),
+        None => generate_unit(),
+    }
+}
+
+/// 按 Id 边界将实体对象组打包为 Unit，每个 Unit 的编码长度不超过 `max_unit_bytes`
+///
+/// 没有实体时不生成 Unit：每个 Unit 都占用一个序列号，生成后不发送会被服务端计为丢包。
+pub(crate) fn pack_entities(
+    entities: Vec<Vec<ExObject>>,
+    max_unit_bytes: usize,
+    timestamp: Option<u64>,
+) -> Vec<Unit> {
+    if entities.is_empty() {
+        return Vec::new();
+    }
+    let mut units = Vec::new();
+    let mut current = new_unit(timestamp);
+    let mut current_len = current.encoded_len();
+
+    for objects in entities {
+        let entity_len: usize = objects.iter()
+            .map(|obj| prost::encoding::message::encoded_len(3, obj))
+            .sum();
+        if !current.objects.is_empty() && current_len + entity_len > max_unit_bytes {
+            units.push(std::mem::replace(&mut current, new_unit(timestamp)));
+            current_len = current.encoded_len();
+        }
+        current.objects.extend(objects);
+        current_len += entity_len;
+    }
+
+    if !current.objects.is_empty() {
+        units.push(current);
+    }
+    units
+}
+
+/// 生成帧结束 Unit
+pub(crate) fn frame_end_unit(timestamp: Option<u64>) -> Unit {
+    let mut unit = new_unit(timestamp);
+    unit.command = Some(ExCommand { u_command: CommandType::Frameend as i32 });
+    unit
+}
+
+/// 将多个 Unit 编码到同一缓冲区，一次写入连接
+pub(crate) async fn send_units(units: &[Unit]) -> Result<(), String> {
+    if units.is_empty() {
+        return Ok(());
+    }
+    let mut buf = Vec::new();
+    for unit in units {
+        buf.extend(encode(unit)?);
+    }
+    let link = get_link().await;
+    link.send(&buf).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::builder::spawn_point;
+    use expto::rdmp::decoding::decode_and_next;
+    use expto::rdmp::ex_object::UObject;
+
+    fn is_id(obj: &ExObject) -> bool {
+        matches!(obj.u_object, Some(UObject::Id(_)))
+    }
+
+    #[test]
+    fn test_pack_splits_at_id_boundaries() {
+        let mut batch = FrameBatch::with_max_unit_bytes(1024);
+        for i in 0..2000 {
+            batch.add(spawn_point([i as f32, 0.0, 0.0], "red").id(i));
+        }
+        assert_eq!(batch.len(), 2000);
+
+        let units = batch.take_units();
+        let (frame_end, data_units) = units.split_last().unwrap();
+        assert_eq!(frame_end.command.unwrap().u_command, CommandType::Frameend as i32);
+        assert!(data_units.len() > 1 && data_units.len() < 2000);
+
+        let mut ids = Vec::new();
+        for unit in data_units {
+            assert!(unit.encoded_len() <= 1024);
+            assert!(is_id(&unit.objects[0]), "每个 Unit 必须以 Id 开头");
+            ids.extend(unit.objects.iter().filter_map(|obj| match obj.u_object {
+                Some(UObject::Id(id)) => Some(id),
+                _ => None,
+            }));
+        }
+        assert_eq!(ids, (0..2000).collect::<Vec<u64>>());
+    }
+
+    #[test]
+    fn test_empty_batch_packs_no_units() {
+        assert!(pack_entities(Vec::new(), DEFAULT_MAX_UNIT_BYTES, None).is_empty());
+        // 空批次提交时只有 Frameend
+        let units = FrameBatch::new().take_units();
+        assert_eq!(units.len(), 1);
+        assert_eq!(units[0].command.unwrap().u_command, CommandType::Frameend as i32);
+    }
+
+    #[test]
+    fn test_oversized_entity_gets_own_unit() {
+        let mut batch = FrameBatch::with_max_unit_bytes(16);
+        batch.add(spawn_point([0.0, 0.0, 0.0], "red").tag("很长的标签文本"));
+        batch.add(spawn_point([1.0, 0.0, 0.0], "red"));
+
+        let units = batch.take_units();
+        assert_eq!(units.len(), 3);
+    }
+
+    #[test]
+    fn test_auto_assigned_ids() {
+        let mut batch = FrameBatch::new();
+        batch.add(spawn_point([0.0, 0.0, 0.0], "red"));
+        batch.add(spawn_point([1.0, 0.0, 0.0], "red"));
+
+        let units = batch.take_units();
+        let ids: Vec<u64> = units[0].objects.iter().filter_map(|obj| match obj.u_object {
+            Some(UObject::Id(id)) => Some(id),
+            _ => None,
+        }).collect();
+        assert_eq!(ids.len(), 2);
+        assert_ne!(ids[0], ids[1]);
+    }
+
+    #[test]
+    fn test_encoded_stream_decodes_in_order() {
+        let mut batch = FrameBatch::with_max_unit_bytes(256);
+        for i in 0..50 {
+            batch.add(spawn_point([i as f32, 0.0, 0.0], "blue").id(i));
+        }
+        let units = batch.take_units();
+
+        let mut buf = Vec::new();
+        for unit in &units {
+            buf.extend(encode(unit).unwrap());
+        }
+
+        let mut rest = buf.as_slice();
+        let mut decoded = Vec::new();
+        while !rest.is_empty() {
+            let (unit, next) = decode_and_next(rest).unwrap();
+            decoded.push(unit);
+            rest = next;
+        }
+        assert_eq!(decoded, units);
+    }
+
+    #[test]
+    fn test_sensor_timestamp_on_all_units() {
+        let mut batch = FrameBatch::with_max_unit_bytes(128);
+        batch.timestamp(1_700_000_000_123);
+        for i in 0..20 {
+            batch.add(spawn_point([i as f32, 0.0, 0.0], "red").id(i));
+        }
+
+        let units = batch.take_units();
+        assert!(units.len() > 2);
+        for unit in &units {
+            assert_eq!(unit.stamp.as_ref().unwrap().timestamp, 1_700_000_000_123);
+        }
+    }
+}
@@ -8,13 +8,17 @@
 //! use redra_client::ShapeBuilder;
 //!
 //! // 单个实体
-//! ShapeBuilder::sphere(1.0)
+//! let ball = ShapeBuilder::sphere(1.0)
 //!     .id(42)
 //!     .at(1.0, 2.0, 3.0)
 //!     .material("red")
 //!     .tag("我的球体")
 //!     .send().await.unwrap();
 //!
+//! // 后续通过句柄更新 / 销毁
+//! ball.set_material("green").await.unwrap();
+//! ball.destroy().await.unwrap();
+//!
 //! // 分组点云
 //! ShapeBuilder::point_cloud_grouped()
 //!     .group(vec![[0.0, 0.0, 0.0], [1.0, 0.0, 0.0]], "red")
@@ -30,18 +34,23 @@
 //! | 圆柱 | `cylinder(radius, height)` | 半径, 高度 |
 //! | 圆锥 | `cone(radius, height)` | 半径, 高度 |
 //! | 点 | `point(x, y, z)` | 坐标 |
-//! | 线段 | `line(x1,y1,z1, x2,y2,z2)` | 起终点 |
-//! | Cube | `cube(vertices)` | 8 个角点 |
+//! | 线段 | `line(x1,y1,z1, x2,y2,z2)` | 起终点（可选 `.line_width()` / `.screen_line_width()` / `.dash()`） |
+//! | Cube | `cube(vertices)` | 8 个角点（可选 `.box_style()`） |
+//! | 有向包围盒 | `oriented_box(center, half_extents, rotation)` / `oriented_box_from_corners(vertices)` | 中心, 半边长, 四元数（可选 `.wireframe()` / `.box_style()`） |
+//! | 三角网格 | `triangle_mesh(vertices, indices)` | 顶点, 索引（可选 `.normals()` / `.vertex_colors()`） |
+//! | 折线 / 轨迹 | `polyline(points)` | 顶点（可选 `.closed()` / `.line_width()` / `.vertex_colors()`） |
 //! | 分组点云 | `point_cloud_grouped()` | `.group()` 链式添加 |
 
 use expto::prelude::*;
-use expto::rdmp::auto::unit::generate_unit;
+use expto::rdmp::ex_object::UObject;
+use expto::rdmp::mesh::ex_mesh::UMesh;
 use expto::rdmp::{
-    Cone, Cube, Cylinder, ExMesh, Line, Point, Sphere,
+    BoxStyle, Cone, Cube, Cylinder, ExMesh, Line, OrientedBox, Point, Polyline, Sphere, TriangleMesh,
 };
-use nalgebra::{UnitQuaternion, Vector3};
 
-use super::link::get_link;
+use super::batch::{frame_end_unit, pack_entities, send_units, DEFAULT_MAX_UNIT_BYTES};
+use super::handle::EntityHandle;
+use super::id::id_generator;
 
 // ─── 分组点云 ──────────────────────────────────────────────
 
@@ -79,10 +88,21 @@ pub struct ShapeBuilder {
     pub(crate) mesh: ExMesh,
     pub(crate) tx: f32, pub(crate) ty: f32, pub(crate) tz: f32,
     pub(crate) rx: f32, pub(crate) ry: f32, pub(crate) rz: f32,
+    /// 四元数旋转 `[x, y, z, w]`，设置后优先于欧拉角
+    pub(crate) rotation: Option<[f32; 4]>,
     pub(crate) sx: f32, pub(crate) sy: f32, pub(crate) sz: f32,
     pub(crate) material: Option<String>,
     pub(crate) tag_list: Vec<Tag>,
     pub(crate) groups: Option<Vec<PointGroup>>,
+    pub(crate) timestamp: Option<u64>,
+    /// 父实体 ID — 设置后变换相对父实体
+    pub(crate) parent: Option<u64>,
+    /// 变换所在的命名坐标系
+    pub(crate) frame_id: Option<String>,
+    /// 命名空间（图层）
+    pub(crate) namespace: Option<String>,
+    /// 生存期 — 到期后自动消失
+    pub(crate) lifetime: Option<Lifetime>,
 }
 
 impl ShapeBuilder {
@@ -94,13 +114,8 @@ impl ShapeBuilder {
     /// 适用于聚类可视化、地面/障碍物分离等场景。
     pub fn point_cloud_grouped() -> Self {
         ShapeBuilder {
-            id: None,
-            mesh: ExMesh { u_mesh: None },
-            tx: 0.0, ty: 0.0, tz: 0.0,
-            rx: 0.0, ry: 0.0, rz: 0.0,
-            sx: 1.0, sy: 1.0, sz: 1.0,
-            material: None, tag_list: Vec::new(),
             groups: Some(Vec::new()),
+            ..Self::new(ExMesh { u_mesh: None })
         }
     }
 
@@ -132,33 +147,11 @@ impl ShapeBuilder {
         Self::new(ExMesh::from(Point::from((x, y, z))))
     }
 
-    /// 线段（起点 `(x1,y1,z1)` → 终点 `(x2,y2,z2)`）— 自动计算中点与朝向
+    /// 线段（起点 `(x1,y1,z1)` → 终点 `(x2,y2,z2)`，局部坐标）— 渲染端由端点生成带状线
+    ///
+    /// 默认线宽为屏幕 2 像素；用 `line_width()` / `screen_line_width()` / `dash()` 调整。
     pub fn line(x1: f32, y1: f32, z1: f32, x2: f32, y2: f32, z2: f32) -> Self {
-        let line_mesh = Line::from((Point { x: x1, y: y1, z: z1 }, Point { x: x2, y: y2, z: z2 }));
-        let dx = x2 - x1;
-        let dy = y2 - y1;
-        let dz = z2 - z1;
-        let len = (dx * dx + dy * dy + dz * dz).sqrt();
-
-        let (rx, ry, rz) = if len < 1e-6 {
-            (0.0, 0.0, 0.0)
-        } else {
-            let dir = Vector3::new(dx / len, dy / len, dz / len);
-            let q = UnitQuaternion::rotation_between(&Vector3::y(), &dir)
-                .unwrap_or(UnitQuaternion::identity());
-            q.euler_angles()
-        };
-
-        ShapeBuilder {
-            id: None,
-            mesh: ExMesh::from(line_mesh),
-            tx: (x1 + x2) / 2.0, ty: (y1 + y2) / 2.0, tz: (z1 + z2) / 2.0,
-            rx, ry, rz,
-            sx: 1.0, sy: 1.0, sz: 1.0,
-            material: None,
-            tag_list: Vec::new(),
-            groups: None,
-        }
+        Self::new(ExMesh::from(Line::from((Point { x: x1, y: y1, z: z1 }, Point { x: x2, y: y2, z: z2 }))))
     }
 
     /// 包围盒（8 个角点）— 自动计算 AABB 中心位置
@@ -190,16 +183,115 @@ impl ShapeBuilder {
         let cy = (min[1] + max[1]) / 2.0;
         let cz = (min[2] + max[2]) / 2.0;
 
-        ShapeBuilder {
-            id: None,
-            mesh: ExMesh::from(Cube { vertices: points }),
-            tx: cx, ty: cy, tz: cz,
-            rx: 0.0, ry: 0.0, rz: 0.0,
-            sx: 1.0, sy: 1.0, sz: 1.0,
-            material: None,
-            tag_list: Vec::new(),
-            groups: None,
+        Self::new(ExMesh::from(Cube { vertices: points, ..Default::default() })).at(cx, cy, cz)
+    }
+
+    /// 有向包围盒（`center` — 中心, `half_extents` — 半边长, `rotation` — 四元数 `[x, y, z, w]`）
+    ///
+    /// 位姿写在 mesh 内，实体变换保持单位变换；渲染端将两者组合。
+    pub fn oriented_box(center: [f32; 3], half_extents: [f32; 3], rotation: [f32; 4]) -> Self {
+        Self::new(ExMesh::from(OrientedBox::from((center, half_extents, rotation))))
+    }
+
+    /// 由 8 个角点（任意顺序）构造有向包围盒 — 适用于跟踪器输出的旋转检测框
+    ///
+    /// 角点不构成长方体（数量不为 8 或退化）时退化为 [`cube()`](Self::cube)。
+    pub fn oriented_box_from_corners(vertices: Vec<(f32, f32, f32)>) -> Self {
+        let corners: Vec<_> = vertices.iter()
+            .map(|&(x, y, z)| nalgebra::Vector3::new(x, y, z))
+            .collect();
+        match redra_geo::obb_from_corners(&corners) {
+            Some(obb) => Self::oriented_box(
+                obb.center.into(),
+                obb.half_extents.into(),
+                obb.rotation.coords.into(),
+            ),
+            None => {
+                log::warn!("角点无法构成有向包围盒，退化为 cube()");
+                Self::cube(vertices)
+            }
+        }
+    }
+
+    /// 只绘制 12 条棱，等同 `box_style(BoxStyle::Wireframe)`
+    pub fn wireframe(self) -> Self {
+        self.set_box_style("wireframe", BoxStyle::Wireframe)
+    }
+
+    /// 包围盒绘制样式：实体 / 线框 / 半透明 + 棱边（非 Cube、有向包围盒时忽略）
+    ///
+    /// 默认 `BoxStyle::Auto` 由材质决定，如 `bounding_box` 材质为半透明。
+    pub fn box_style(self, style: BoxStyle) -> Self {
+        self.set_box_style("box_style", style)
+    }
+
+    /// 三角网格（`vertices` — 局部坐标顶点, `indices` — 三个一组的顶点索引）
+    ///
+    /// `indices` 为空时按顶点顺序每三个构成一个三角形；未提供法线时由渲染端计算。
+    pub fn triangle_mesh(vertices: Vec<[f32; 3]>, indices: Vec<u32>) -> Self {
+        Self::new(ExMesh::from(TriangleMesh::from((vertices, indices))))
+    }
+
+    /// 折线 / 轨迹（`points` — 局部坐标顶点，至少 2 个）— 整条路径是一个实体
+    pub fn polyline(points: Vec<[f32; 3]>) -> Self {
+        Self::new(ExMesh::from(Polyline::from(points)))
+    }
+
+    /// 设置折线是否首尾相连（非折线时忽略）
+    pub fn closed(mut self, closed: bool) -> Self {
+        if let Some(polyline) = self.polyline_mut("closed") {
+            polyline.closed = closed;
         }
+        self
+    }
+
+    /// 设置线段或折线的线宽（世界单位；其他类型时忽略）
+    pub fn line_width(mut self, width: f32) -> Self {
+        match self.mesh_mut("line_width", "line() / polyline()", |m| matches!(m, UMesh::Line(_) | UMesh::Polyline(_))) {
+            Some(UMesh::Line(line)) => {
+                line.width = width;
+                line.screen_space = false;
+            }
+            Some(UMesh::Polyline(polyline)) => polyline.width = width,
+            _ => {}
+        }
+        self
+    }
+
+    /// 设置线段的屏幕线宽（像素，不随缩放变化；非线段时忽略）
+    pub fn screen_line_width(mut self, pixels: f32) -> Self {
+        if let Some(line) = self.line_mut("screen_line_width") {
+            line.width = pixels;
+            line.screen_space = true;
+        }
+        self
+    }
+
+    /// 设置线段的虚线模式（实线段 / 间隔长度交替，最多 4 个，单位同线宽；非线段时忽略）
+    pub fn dash(mut self, pattern: Vec<f32>) -> Self {
+        if let Some(line) = self.line_mut("dash") {
+            line.dash = pattern;
+        }
+        self
+    }
+
+    /// 设置三角网格的顶点法线（须与顶点等长；非三角网格时忽略）
+    pub fn normals(mut self, normals: Vec<[f32; 3]>) -> Self {
+        if let Some(mesh) = self.triangle_mesh_mut("normals") {
+            mesh.normals = normals.into_iter().map(Point::from).collect();
+        }
+        self
+    }
+
+    /// 设置三角网格或折线的逐顶点颜色 RGBA（须与顶点等长；其他类型时忽略）
+    pub fn vertex_colors(mut self, colors: Vec<[f32; 4]>) -> Self {
+        let colors = colors.into_iter().map(Into::into).collect();
+        match self.mesh_mut("vertex_colors", "triangle_mesh() / polyline()", |m| matches!(m, UMesh::TriangleMesh(_) | UMesh::Polyline(_))) {
+            Some(UMesh::TriangleMesh(mesh)) => mesh.colors = colors,
+            Some(UMesh::Polyline(polyline)) => polyline.colors = colors,
+            _ => {}
+        }
+        self
     }
 
     // ─── 链式配置 ─────────────────────────────────────────
@@ -209,6 +301,53 @@ impl ShapeBuilder {
         self.id = Some(id); self
     }
 
+    /// 挂到父实体下（同一会话内的实体 ID），位置 / 旋转 / 缩放均相对父实体
+    ///
+    /// 父实体移动时子实体随之移动，销毁父实体会一并销毁子实体。
+    pub fn parent(mut self, parent_id: u64) -> Self {
+        self.parent = Some(parent_id); self
+    }
+
+    /// 挂到已发送实体下（取句柄的主实体 ID）
+    pub fn parent_of(self, handle: &EntityHandle) -> Self {
+        match handle.id() {
+            Some(id) => self.parent(id),
+            None => self,
+        }
+    }
+
+    /// 变换在命名坐标系中表达（如 `"lidar"`），由服务端按坐标系树换算到固定坐标系
+    ///
+    /// 坐标系位姿通过 [`send_frame_transform`](crate::send_frame_transform) 发布；
+    /// 设置了 [`parent`](Self::parent) 时以父实体为准。
+    pub fn frame(mut self, frame_id: impl Into<String>) -> Self {
+        self.frame_id = Some(frame_id.into()); self
+    }
+
+    /// 归入命名空间（如 `"detections"`），可在界面图层面板中整体显隐，
+    /// 或通过 [`send_clear_namespace`](crate::send_clear_namespace) 整体清除
+    pub fn namespace(mut self, namespace: impl Into<String>) -> Self {
+        self.namespace = Some(namespace.into()); self
+    }
+
+    /// 设置生存期，到期后实体自动从后续帧中消失（无需显式销毁）
+    ///
+    /// 适合碰撞告警、单次检测结果等临时可视化，见 [`lifetime_ms`](Self::lifetime_ms)
+    /// 与 [`lifetime_frames`](Self::lifetime_frames)。
+    pub fn lifetime(mut self, lifetime: Lifetime) -> Self {
+        self.lifetime = Some(lifetime); self
+    }
+
+    /// 生成后存在 `duration_ms` 毫秒（按帧时间戳计算）
+    pub fn lifetime_ms(self, duration_ms: u64) -> Self {
+        self.lifetime(Lifetime::duration_ms(duration_ms))
+    }
+
+    /// 存在 `frames` 帧（含生成所在帧）
+    pub fn lifetime_frames(self, frames: u32) -> Self {
+        self.lifetime(Lifetime::frames(frames))
+    }
+
     /// 设置位置
     pub fn at(mut self, x: f32, y: f32, z: f32) -> Self {
         self.tx = x; self.ty = y; self.tz = z; self
@@ -224,20 +363,30 @@ impl ShapeBuilder {
         self.sx = s; self.sy = s; self.sz = s; self
     }
 
-    /// 设置旋转（弧度制欧拉角）
+    /// 设置旋转（弧度制欧拉角，XYZ 顺序）— 会清除已设置的四元数
     pub fn rotation(mut self, rx: f32, ry: f32, rz: f32) -> Self {
-        self.rx = rx; self.ry = ry; self.rz = rz; self
+        self.rx = rx; self.ry = ry; self.rz = rz;
+        self.rotation = None;
+        self
     }
 
-    /// 设置旋转（角度制欧拉角）
-    pub fn rotation_deg(mut self, rx: f32, ry: f32, rz: f32) -> Self {
+    /// 设置旋转（角度制欧拉角，XYZ 顺序）— 会清除已设置的四元数
+    pub fn rotation_deg(self, rx: f32, ry: f32, rz: f32) -> Self {
         let deg_to_rad = std::f32::consts::PI / 180.0;
-        self.rx = rx * deg_to_rad;
-        self.ry = ry * deg_to_rad;
-        self.rz = rz * deg_to_rad;
+        self.rotation(rx * deg_to_rad, ry * deg_to_rad, rz * deg_to_rad)
+    }
+
+    /// 设置旋转（四元数 `[x, y, z, w]`）— 优先于欧拉角，发送时同时回填欧拉角供旧服务端使用
+    pub fn quaternion(mut self, q: [f32; 4]) -> Self {
+        self.rotation = Some(q);
         self
     }
 
+    /// 设置旋转（四元数分量）
+    pub fn rotation_quat(self, x: f32, y: f32, z: f32, w: f32) -> Self {
+        self.quaternion([x, y, z, w])
+    }
+
     /// 设置半透明材质（自动追加 `_transparent` 后缀）。
     ///
     /// # 示例
@@ -288,74 +437,186 @@ impl ShapeBuilder {
         self
     }
 
+    /// 设置传感器时间（Unix 毫秒）；未设置时使用发送时刻的系统时间
+    pub fn timestamp(mut self, timestamp_ms: u64) -> Self {
+        self.timestamp = Some(timestamp_ms); self
+    }
+
     // ─── 发送 ─────────────────────────────────────────────
 
-    /// 构建 Unit 并发送
-    pub async fn send(self) -> Result<(), String> {
-        // 分组点云模式：每组一个 Unit，共享材质
+    /// 构建 Unit 并发送，返回可用于后续更新/销毁的 [`EntityHandle`]
+    ///
+    /// 未设置 ID 时自动从全局 ID 生成器分配；分组点云的所有点按
+    /// [`DEFAULT_MAX_UNIT_BYTES`] 打包成若干 Unit，一次写入连接。
+    pub async fn send(self) -> Result<EntityHandle, String> {
+        let (handle, units) = self.build();
+        send_units(&units).await?;
+        Ok(handle)
+    }
+
+    /// 只构建不发送 — 返回句柄与待发送的 Unit
+    pub fn build(self) -> (EntityHandle, Vec<Unit>) {
+        let timestamp = self.timestamp;
+        let entities = self.into_entities();
+        let ids = entities.iter()
+            .filter_map(|objects| match objects.first().and_then(|obj| obj.u_object.as_ref()) {
+                Some(UObject::Id(id)) => Some(*id),
+                _ => None,
+            })
+            .collect();
+        (EntityHandle::from_ids(ids), pack_entities(entities, DEFAULT_MAX_UNIT_BYTES, timestamp))
+    }
+
+    /// 拆解为实体对象组 — 每组以 `Id` 开头，对应服务端的一个实体
+    pub(crate) fn into_entities(self) -> Vec<Vec<ExObject>> {
+        // 分组点云模式：每个点一个实体，共享组材质
         if let Some(groups) = self.groups {
-            let link = get_link().await;
-            let mut id_counter: u64 = 1;
+            let mut entities = Vec::new();
             for group in &groups {
-                let mut unit = generate_unit();
                 for pos in &group.points {
-                    unit.objects.push(ExObject::from(id_counter));
-                    id_counter += 1;
                     let p: Point = (pos[0], pos[1], pos[2]).into();
-                    unit.objects.push(ExObject::from(ExMesh::from(p)));
-                    unit.objects.push(ExObject::from(ExTransform {
-                        x: pos[0], y: pos[1], z: pos[2],
-                        rx: 0.0, ry: 0.0, rz: 0.0,
-                        sx: 1.0, sy: 1.0, sz: 1.0,
-                    }));
-                    use expto::rdmp::ex_object::UObject;
-                    unit.objects.push(ExObject { u_object: Some(UObject::MaterialId(group.material.clone())) });
+                    entities.push(vec![
+                        ExObject::from(id_generator().next_id()),
+                        ExObject::from(ExMesh::from(p)),
+                        ExObject::from(ExTransform {
+                            x: pos[0], y: pos[1], z: pos[2],
+                            rx: 0.0, ry: 0.0, rz: 0.0,
+                            sx: 1.0, sy: 1.0, sz: 1.0,
+                            rotation: None,
+                        }),
+                        ExObject { u_object: Some(UObject::MaterialId(group.material.clone())) },
+                    ]);
+                    let objects = entities.last_mut().unwrap();
+                    if let Some(parent) = self.parent {
+                        objects.push(ExObject::parent(parent));
+                    }
+                    if let Some(frame_id) = &self.frame_id {
+                        objects.push(ExObject::frame_id(frame_id.clone()));
+                    }
+                    if let Some(namespace) = &self.namespace {
+                        objects.push(ExObject::namespace(namespace.clone()));
+                    }
+                    if let Some(lifetime) = self.lifetime {
+                        objects.push(ExObject::from(lifetime));
+                    }
                 }
-                let buf = encode(&unit).map_err(|e| format!("{}", e))?;
-                link.send(&buf).await?;
             }
-            return Ok(());
+            return entities;
         }
 
         // 单实体模式
-        let mut unit = generate_unit();
+        let id = self.id.unwrap_or_else(|| id_generator().next_id());
+        let transform = self.transform();
+        let mut objects = vec![
+            ExObject::from(id),
+            ExObject::from(self.mesh),
+            ExObject::from(transform),
+        ];
+
+        if let Some(mat) = self.material {
+            objects.push(ExObject { u_object: Some(UObject::MaterialId(mat)) });
+        }
 
-        if let Some(id) = self.id {
-            unit.objects.push(ExObject::from(id));
+        if let Some(parent) = self.parent {
+            objects.push(ExObject::parent(parent));
         }
 
-        unit.objects.push(ExObject::from(self.mesh));
-        unit.objects.push(ExObject::from(ExTransform {
+        if let Some(frame_id) = self.frame_id {
+            objects.push(ExObject::frame_id(frame_id));
+        }
+
+        if let Some(namespace) = self.namespace {
+            objects.push(ExObject::namespace(namespace));
+        }
+
+        if let Some(lifetime) = self.lifetime {
+            objects.push(ExObject::from(lifetime));
+        }
+
+        for tag in self.tag_list {
+            objects.push(ExObject::from(tag));
+        }
+
+        vec![objects]
+    }
+
+    // ─── 内部 ─────────────────────────────────────────────
+
+    /// 组装协议变换 — 设置了四元数时写入四元数并回填欧拉角
+    pub(crate) fn transform(&self) -> ExTransform {
+        let transform = ExTransform {
             x: self.tx, y: self.ty, z: self.tz,
             rx: self.rx, ry: self.ry, rz: self.rz,
             sx: self.sx, sy: self.sy, sz: self.sz,
-        }));
+            rotation: None,
+        };
+        match self.rotation {
+            Some(q) => transform.with_quaternion(q),
+            None => transform,
+        }
+    }
 
-        if let Some(mat) = self.material {
-            use expto::rdmp::ex_object::UObject;
-            unit.objects.push(ExObject { u_object: Some(UObject::MaterialId(mat)) });
+    /// 网格专用设置的目标；类型不符（`applies` 返回 false）时警告并返回 `None`
+    ///
+    /// `method` 为调用方方法名，`applies_to` 为适用的构造方法，均用于警告。
+    fn mesh_mut(&mut self, method: &str, applies_to: &str, applies: fn(&UMesh) -> bool) -> Option<&mut UMesh> {
+        match self.mesh.u_mesh.as_mut().filter(|mesh| applies(mesh)) {
+            Some(mesh) => Some(mesh),
+            None => {
+                log::warn!("{}() 仅适用于 {}，已忽略", method, applies_to);
+                None
+            }
         }
+    }
 
-        for tag in self.tag_list {
-            unit.objects.push(ExObject::from(tag));
+    /// 三角网格专用设置的目标
+    fn triangle_mesh_mut(&mut self, method: &str) -> Option<&mut TriangleMesh> {
+        match self.mesh_mut(method, "triangle_mesh()", |m| matches!(m, UMesh::TriangleMesh(_))) {
+            Some(UMesh::TriangleMesh(mesh)) => Some(mesh),
+            _ => None,
         }
+    }
 
-        let link = get_link().await;
-        let buf = encode(&unit).map_err(|e| format!("{}", e))?;
-        link.send(&buf).await?;
-        Ok(())
+    /// 线段专用设置的目标
+    fn line_mut(&mut self, method: &str) -> Option<&mut Line> {
+        match self.mesh_mut(method, "line()", |m| matches!(m, UMesh::Line(_))) {
+            Some(UMesh::Line(line)) => Some(line),
+            _ => None,
+        }
     }
 
-    // ─── 内部 ─────────────────────────────────────────────
+    /// 折线专用设置的目标
+    fn polyline_mut(&mut self, method: &str) -> Option<&mut Polyline> {
+        match self.mesh_mut(method, "polyline()", |m| matches!(m, UMesh::Polyline(_))) {
+            Some(UMesh::Polyline(polyline)) => Some(polyline),
+            _ => None,
+        }
+    }
+
+    /// 设置包围盒样式；`method` 为调用方方法名，用于警告
+    fn set_box_style(mut self, method: &str, style: BoxStyle) -> Self {
+        match self.mesh_mut(method, "cube() / oriented_box()", |m| matches!(m, UMesh::Cube(_) | UMesh::OrientedBox(_))) {
+            Some(UMesh::Cube(cube)) => cube.set_style(style),
+            Some(UMesh::OrientedBox(oriented_box)) => oriented_box.set_style(style),
+            _ => {}
+        }
+        self
+    }
 
     fn new(mesh: ExMesh) -> Self {
         ShapeBuilder {
             id: None, mesh,
             tx: 0.0, ty: 0.0, tz: 0.0,
             rx: 0.0, ry: 0.0, rz: 0.0,
+            rotation: None,
             sx: 1.0, sy: 1.0, sz: 1.0,
             material: None, tag_list: Vec::new(),
             groups: None,
+            timestamp: None,
+            parent: None,
+            frame_id: None,
+            namespace: None,
+            lifetime: None,
         }
     }
 }
@@ -407,6 +668,26 @@ pub fn spawn_point(pos: [f32; 3], material: impl Into<String>) -> ShapeBuilder {
     ShapeBuilder::point(pos[0], pos[1], pos[2]).material(material)
 }
 
+/// 有向包围盒（中心, 半边长, 四元数 `[x, y, z, w]`, 材质）
+pub fn spawn_oriented_box(center: [f32; 3], half_extents: [f32; 3], rotation: [f32; 4], material: impl Into<String>) -> ShapeBuilder {
+    ShapeBuilder::oriented_box(center, half_extents, rotation).material(material)
+}
+
+/// 三角网格（顶点, 索引, 材质）
+pub fn spawn_triangle_mesh(vertices: Vec<[f32; 3]>, indices: Vec<u32>, material: impl Into<String>) -> ShapeBuilder {
+    ShapeBuilder::triangle_mesh(vertices, indices).material(material)
+}
+
+/// 路径 / 轨迹（顶点, 材质）— 一条开放折线
+pub fn spawn_path(points: Vec<[f32; 3]>, material: impl Into<String>) -> ShapeBuilder {
+    ShapeBuilder::polyline(points).material(material)
+}
+
+/// 多边形轮廓（顶点, 材质）— 首尾相连的折线
+pub fn spawn_polygon(points: Vec<[f32; 3]>, material: impl Into<String>) -> ShapeBuilder {
+    ShapeBuilder::polyline(points).closed(true).material(material)
+}
+
 /// 线段（起点, 终点, 材质）
 pub fn spawn_line(from: [f32; 3], to: [f32; 3], material: impl Into<String>) -> ShapeBuilder {
     ShapeBuilder::line(from[0], from[1], from[2], to[0], to[1], to[2]).material(material)
@@ -441,10 +722,10 @@ pub fn spawn_line_transparent(from: [f32; 3], to: [f32; 3], name: &str) -> Shape
 
 /// 发送帧结束标记
 pub async fn send_frame_end() -> Result<(), String> {
-    let mut unit = generate_unit();
-    unit.command = Some(ExCommand { u_command: CommandType::Frameend as i32 });
-    let link = get_link().await;
-    let buf = encode(&unit).map_err(|e| format!("{}", e))?;
-    link.send(&buf).await?;
-    Ok(())
+    send_units(&[frame_end_unit(None)]).await
+}
+
+/// 发送带传感器时间（Unix 毫秒）的帧结束标记
+pub async fn send_frame_end_at(timestamp_ms: u64) -> Result<(), String> {
+    send_units(&[frame_end_unit(Some(timestamp_ms))]).await
 }
@@ -0,0 +1,186 @@
+//! 实体句柄 — `ShapeBuilder::send()` 的返回值，用于后续更新与销毁
+//!
+//! ```no_run
+//! use redra_client::{ShapeBuilder, ExTransform};
+//!
+//! # async fn run() -> Result<(), String> {
+//! let ball = ShapeBuilder::sphere(0.5).material("red").send().await?;
+//! ball.set_material("green").await?;
+//! ball.set_transform(ExTransform { x: 1.0, sx: 1.0, sy: 1.0, sz: 1.0, ..Default::default() }).await?;
+//! ball.destroy().await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! 每个方法都有对应的 `*_units` 版本，只构造 Unit 不发送，便于写入 [`SqlWriter`](crate::SqlWriter)
+//! 或 [`FrameBatch`](crate::FrameBatch) 之外的自定义传输。
+
+use expto::prelude::*;
+use expto::rdmp::ex_object::UObject;
+
+use super::batch::{DEFAULT_MAX_UNIT_BYTES, pack_entities, send_units};
+use super::builder::IntoTag;
+
+/// 已发送实体的句柄
+///
+/// 单实体构建器返回的句柄只包含一个 ID；分组点云返回的句柄包含该次发送的所有点，
+/// 方法会作用到其中的每个实体。
+///
+/// 服务端每帧重建场景，句柄只作用于当前帧内的实体；设置了生存期（`ShapeBuilder::lifetime`）
+/// 的实体跨帧延续，其句柄在 `Frameend` 之后依然有效，直到实体到期、被销毁或清空。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntityHandle {
+    ids: Vec<u64>,
+}
+
+impl EntityHandle {
+    /// 由已知 ID 构造句柄（例如服务端已有的实体）
+    pub fn from_id(id: u64) -> Self {
+        Self { ids: vec![id] }
+    }
+
+    pub(crate) fn from_ids(ids: Vec<u64>) -> Self {
+        Self { ids }
+    }
+
+    /// 主实体 ID（分组点云为第一个点的 ID；空句柄返回 `None`）
+    pub fn id(&self) -> Option<u64> {
+        self.ids.first().copied()
+    }
+
+    /// 句柄包含的全部实体 ID
+    pub fn ids(&self) -> &[u64] {
+        &self.ids
+    }
+
+    // ─── 发送 ─────────────────────────────────────────────
+
+    /// 更新变换（位置 / 旋转 / 缩放）
+    pub async fn set_transform(&self, transform: ExTransform) -> Result<(), String> {
+        send_units(&self.transform_units(transform)).await
+    }
+
+    /// 更新材质（短名称如 `"red"`，或完整 TOML 路径）
+    pub async fn set_material(&self, material: impl Into<String>) -> Result<(), String> {
+        send_units(&self.material_units(material)).await
+    }
+
+    /// 替换全部标签；空列表清除全部标签（同 [`clear_tags`](Self::clear_tags)）
+    pub async fn set_tags(&self, tags: Vec<impl IntoTag>) -> Result<(), String> {
+        send_units(&self.tags_units(tags)).await
+    }
+
+    /// 清除全部标签
+    pub async fn clear_tags(&self) -> Result<(), String> {
+        send_units(&self.clear_tags_units()).await
+    }
+
+    /// 挂到父实体下（`Some`），或解除父子关系回到世界坐标（`None`）
+    ///
+    /// 变换不会自动换算，调用方需随后按新的参考系更新变换。
+    pub async fn set_parent(&self, parent_id: Option<u64>) -> Result<(), String> {
+        send_units(&self.parent_units(parent_id)).await
+    }
+
+    /// 销毁实体（连同挂在其下的子实体），句柄随之失效
+    pub async fn destroy(self) -> Result<(), String> {
+        send_units(&self.destroy_units()).await
+    }
+
+    // ─── 构造 Unit ────────────────────────────────────────
+
+    /// 构造变换更新 Unit（所有实体按 Id 分组合并，超过大小上限时拆分）
+    pub fn transform_units(&self, transform: ExTransform) -> Vec<Unit> {
+        self.update_units(|_| vec![ExObject::from(transform)])
+    }
+
+    /// 构造材质更新 Unit（所有实体按 Id 分组合并）
+    pub fn material_units(&self, material: impl Into<String>) -> Vec<Unit> {
+        let material = material.into();
+        self.update_units(|_| vec![ExObject { u_object: Some(UObject::MaterialId(material.clone())) }])
+    }
+
+    /// 构造标签更新 Unit（所有实体按 Id 分组合并）
+    ///
+    /// Update 中缺少 `Tag` 表示不修改标签，因此空列表编码为一个空文本 `Tag`（服务端据此清空）。
+    pub fn tags_units(&self, tags: Vec<impl IntoTag>) -> Vec<Unit> {
+        let mut tags: Vec<Tag> = tags.into_iter().map(IntoTag::into_tag).collect();
+        if tags.is_empty() {
+            tags.push(Tag::new(""));
+        }
+        self.update_units(|_| tags.iter().cloned().map(ExObject::from).collect())
+    }
+
+    /// 构造清除标签的 Unit（所有实体按 Id 分组合并）
+    pub fn clear_tags_units(&self) -> Vec<Unit> {
+        self.tags_units(Vec::<Tag>::new())
+    }
+
+    /// 构造父实体更新 Unit（所有实体按 Id 分组合并）— 解除时以实体自身 ID 作为 `parent_id`
+    pub fn parent_units(&self, parent_id: Option<u64>) -> Vec<Unit> {
+        self.update_units(|id| vec![ExObject::parent(parent_id.unwrap_or(id))])
+    }
+
+    /// 构造销毁 Unit（所有实体的 Id 合并，超过大小上限时拆分）
+    pub fn destroy_units(&self) -> Vec<Unit> {
+        self.command_units(CommandType::Destroy, |_| Vec::new())
+    }
+
+    fn update_units(&self, payload: impl Fn(u64) -> Vec<ExObject>) -> Vec<Unit> {
+        self.command_units(CommandType::Update, payload)
+    }
+
+    /// 每个实体一个 `Id` 分组（`Id` + 载荷），打包为若干带指令的 Unit
+    fn command_units(&self, command: CommandType, payload: impl Fn(u64) -> Vec<ExObject>) -> Vec<Unit> {
+        let entities = self.ids.iter()
+            .map(|&id| std::iter::once(ExObject::from(id)).chain(payload(id)).collect())
+            .collect();
+        let mut units = pack_entities(entities, DEFAULT_MAX_UNIT_BYTES, None);
+        for unit in &mut units {
+            unit.command = Some(ExCommand { u_command: command as i32 });
+        }
+        units
+    }
+}
+
+impl From<u64> for EntityHandle {
+    fn from(id: u64) -> Self {
+        Self::from_id(id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_update_units_shape() {
+        let handle = EntityHandle::from_ids(vec![7, 8]);
+        let units = handle.material_units("red");
+
+        // 多个实体合并为一个 Unit，按 Id 分组
+        assert_eq!(units.len(), 1);
+        assert_eq!(units[0].command.unwrap().u_command, CommandType::Update as i32);
+        let material = ExObject { u_object: Some(UObject::MaterialId("red".to_string())) };
+        assert_eq!(units[0].objects, vec![ExObject::from(7u64), material.clone(), ExObject::from(8u64), material]);
+    }
+
+    #[test]
+    fn test_large_handle_splits_units() {
+        let handle = EntityHandle::from_ids((0..20_000).collect());
+        let units = handle.destroy_units();
+
+        assert!(units.len() > 1);
+        assert!(units.iter().all(|u| u.command.unwrap().u_command == CommandType::Destroy as i32));
+        assert_eq!(units.iter().map(|u| u.objects.len()).sum::<usize>(), 20_000);
+    }
+
+    #[test]
+    fn test_destroy_units_shape() {
+        let units = EntityHandle::from_id(3).destroy_units();
+
+        assert_eq!(units.len(), 1);
+        assert_eq!(units[0].command.unwrap().u_command, CommandType::Destroy as i32);
+        assert_eq!(units[0].objects, vec![ExObject::from(3u64)]);
+    }
+}
@@ -1,12 +1,12 @@
-use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
 use std::sync::LazyLock;
 use std::time::{SystemTime, UNIX_EPOCH};
 
+use expto::rdmp::auto::stamper::Stamper;
+
 
 /// 全局ID生成器实例
-pub static GLOBAL_ID_GENERATOR: LazyLock<IdGenerator> = LazyLock::new(|| {
-    IdGenerator::new(Some("global_session".to_string()))
-});
+pub static GLOBAL_ID_GENERATOR: LazyLock<IdGenerator> = LazyLock::new(IdGenerator::new);
 
 pub fn id_generator() -> &'static IdGenerator {
     &GLOBAL_ID_GENERATOR
@@ -17,23 +17,16 @@ pub fn id_generator() -> &'static IdGenerator {
 pub struct IdGenerator {
     /// 用于生成唯一ID的原子计数器
     counter: AtomicU64,
-    /// 会话ID，用于区分不同客户端的请求
-    session_id: String,
+    /// 序列号计数器，与实体 ID 分开计数，避免消耗 ID
+    sequence: AtomicU32,
 }
 
 impl IdGenerator {
     /// 创建新的ID生成器
-    pub fn new(session_id: Option<String>) -> Self {
+    pub fn new() -> Self {
         Self {
             counter: AtomicU64::new(0),
-            session_id: session_id.unwrap_or_else(|| {
-                // 使用当前时间作为默认会话ID
-                let now = SystemTime::now()
-                    .duration_since(UNIX_EPOCH)
-                    .expect("时间倒退")
-                    .as_millis() as u64;
-                format!("session_{}", now)
-            }),
+            sequence: AtomicU32::new(0),
         }
     }
 
@@ -51,19 +44,22 @@ impl IdGenerator {
     }
 
     /// 获取当前会话ID
-    pub fn session_id(&self) -> &str {
-        &self.session_id
+    ///
+    /// 服务端按 stamp 中的会话隔离实体 ID，因此直接取 [`Stamper::session_id`]，
+    /// 需要指定时调用 [`Stamper::set_session_id`]。
+    pub fn session_id(&self) -> &'static str {
+        Stamper::session_id()
     }
 
     /// 生成Unit结构时的序列号（递增）
     pub fn next_sequence_number(&self) -> u32 {
-        self.counter.fetch_add(1, Ordering::SeqCst) as u32
+        self.sequence.fetch_add(1, Ordering::SeqCst)
     }
 }
 
 impl Default for IdGenerator {
     fn default() -> Self {
-        Self::new(None)
+        Self::new()
     }
 }
 
@@ -79,11 +75,15 @@ mod tests {
         let id2 = generator.next_id();
         
         assert_eq!(id2, id1 + 1);
+
+        // 序列号不消耗实体 ID
+        generator.next_sequence_number();
+        assert_eq!(generator.next_id(), id2 + 1);
         
         let timestamp = generator.current_timestamp();
         assert!(timestamp > 0);
         
-        assert!(generator.session_id().starts_with("session_"));
+        assert_eq!(generator.session_id(), Stamper::session_id());
     }
     
     #[test]
@@ -96,6 +96,6 @@ mod tests {
         let timestamp = GLOBAL_ID_GENERATOR.current_timestamp();
         assert!(timestamp > 0);
         
-        assert_eq!(GLOBAL_ID_GENERATOR.session_id(), "global_session");
+        assert_eq!(GLOBAL_ID_GENERATOR.session_id(), Stamper::session_id());
     }
 }
\ No newline at end of file
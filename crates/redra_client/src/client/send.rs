@@ -18,12 +18,19 @@
 //! | `send_tag` / `send_tag_with_style` | 标签 |
 //! | `send_set_material` | 更新实体材质 |
 //! | `send_destroy` | 销毁实体 |
+//! | `send_clear_namespace` / `send_clear_all` | 批量清除本会话的实体 |
+//! | `update_many` / `update_many_with_materials` | 批量更新实体位姿 |
+//! | `send_frame_transform` | 声明命名坐标系的位姿（TF） |
 
 use expto::prelude::*;
+use expto::rdmp::auto::stamper::Stamper;
 use expto::rdmp::auto::unit::generate_unit;
-use expto::rdmp::{Cube, ExObject, ExMesh, Point, Cylinder, Cone, Tag, TagStyle};
-use nalgebra::{UnitQuaternion, Vector3};
+use expto::rdmp::{Cube, ExObject, ExMesh, FrameTransform, Point, PoseBatch, Cylinder, Cone, Tag, TagStyle};
+use prost::Message;
 
+use crate::client::batch::{DEFAULT_MAX_UNIT_BYTES, send_units};
+use crate::client::handle::EntityHandle;
+use crate::client::id::id_generator;
 use crate::client::link::get_link;
 
 // 定义一个 trait 来扩展 Unit 的功能
@@ -84,9 +91,9 @@ pub async fn send_point(
 pub async fn send_point_cloud(points: &[[f32; 3]]) -> Result<(), String> {
     let mut unit = generate_unit();
 
-    for (i, point) in points.iter().enumerate() {
-        // 自动生成 ID（从 1 开始，本地唯一即可）
-        unit.objects.push(ExObject::from(i as u64 + 1));
+    for point in points {
+        // 从全局 ID 生成器分配，多次调用之间不会复用
+        unit.objects.push(ExObject::from(id_generator().next_id()));
 
         let p: Point = (point[0], point[1], point[2]).into();
         let mesh: ExMesh = p.into();
@@ -97,6 +104,7 @@ pub async fn send_point_cloud(points: &[[f32; 3]]) -> Result<(), String> {
             x: point[0], y: point[1], z: point[2],
             rx: 0.0, ry: 0.0, rz: 0.0,
             sx: 1.0, sy: 1.0, sz: 1.0,
+            rotation: None,
         }));
     }
 
@@ -106,7 +114,7 @@ pub async fn send_point_cloud(points: &[[f32; 3]]) -> Result<(), String> {
 
 /// 发送线段（起点 → 终点）
 ///
-/// 自动计算中点位置与朝向。起终点距离 < 1e-6 时静默跳过。
+/// 端点按世界坐标发送（单位变换），默认屏幕 2 像素宽。起终点距离 < 1e-6 时静默跳过。
 pub async fn send_line(
     x1: f32,
     y1: f32,
@@ -117,32 +125,17 @@ pub async fn send_line(
 ) -> Result<(), String> {
     let mut unit = generate_unit();
 
-    let dx = x2 - x1;
-    let dy = y2 - y1;
-    let dz = z2 - z1;
-    let len = (dx * dx + dy * dy + dz * dz).sqrt();
-    if len < 1e-6 {
+    let start = Point { x: x1, y: y1, z: z1 };
+    let end = Point { x: x2, y: y2, z: z2 };
+    let (dx, dy, dz) = (x2 - x1, y2 - y1, z2 - z1);
+    if (dx * dx + dy * dy + dz * dz).sqrt() < 1e-6 {
         return Ok(());
     }
 
-    // Line mesh (server renders as Cylinder along Y-axis)
-    let line: Line = (Point { x: x1, y: y1, z: z1 }, Point { x: x2, y: y2, z: z2 }).into();
+    // 端点即世界坐标（单位变换），渲染端由端点生成带状线
+    let line: Line = (start, end).into();
     unit.objects.push(ExObject::from(ExMesh::from(line)));
-
-    // Transform: position at midpoint, rotate Y-axis to direction
-    let dir = Vector3::new(dx / len, dy / len, dz / len);
-    let y = Vector3::y();
-    let q = UnitQuaternion::rotation_between(&y, &dir)
-        .unwrap_or(UnitQuaternion::identity());
-    let (rx, ry, rz) = q.euler_angles();
-
-    unit.objects.push(ExObject::from(ExTransform {
-        x: (x1 + x2) / 2.0,
-        y: (y1 + y2) / 2.0,
-        z: (z1 + z2) / 2.0,
-        rx, ry, rz,
-        sx: 1.0, sy: 1.0, sz: 1.0,
-    }));
+    unit.objects.push(ExObject::from(ExTransform::identity()));
 
     unit.send().await?;
     Ok(())
@@ -294,7 +287,7 @@ pub async fn send_cube(
         );
     }
 
-    let cube = Cube { vertices: points };
+    let cube = Cube { vertices: points, ..Default::default() };
     unit.objects.push(ExObject::from(ExMesh::from(cube)));
 
     let cx = (min[0] + max[0]) / 2.0;
@@ -304,6 +297,7 @@ pub async fn send_cube(
         x: cx, y: cy, z: cz,
         rx: 0.0, ry: 0.0, rz: 0.0,
         sx: 1.0, sy: 1.0, sz: 1.0,
+        rotation: None,
     }));
 
     unit.send().await?;
@@ -328,7 +322,7 @@ pub async fn send_cube_with_tag(
         Point { x, y, z }
     }).collect();
 
-    let cube = Cube { vertices: points };
+    let cube = Cube { vertices: points, ..Default::default() };
     unit.objects.push(ExObject::from(ExMesh::from(cube)));
 
     let cx = (min[0] + max[0]) / 2.0;
@@ -338,6 +332,7 @@ pub async fn send_cube_with_tag(
         x: cx, y: cy, z: cz,
         rx: 0.0, ry: 0.0, rz: 0.0,
         sx: 1.0, sy: 1.0, sz: 1.0,
+        rotation: None,
     }));
 
     // 添加标签
@@ -368,18 +363,17 @@ pub async fn send_point_cloud_grouped(
     groups: &[(&[[f32; 3]], &str)],
 ) -> Result<(), String> {
     let link = get_link().await;
-    let mut id_counter: u64 = 1;
     for &(points, material) in groups {
         let mut unit = generate_unit();
         for pos in points {
-            unit.objects.push(ExObject::from(id_counter));
-            id_counter += 1;
+            unit.objects.push(ExObject::from(id_generator().next_id()));
             let p: Point = (pos[0], pos[1], pos[2]).into();
             unit.objects.push(ExObject::from(ExMesh::from(p)));
             unit.objects.push(ExObject::from(ExTransform {
                 x: pos[0], y: pos[1], z: pos[2],
                 rx: 0.0, ry: 0.0, rz: 0.0,
                 sx: 1.0, sy: 1.0, sz: 1.0,
+                rotation: None,
             }));
             use expto::rdmp::ex_object::UObject;
             unit.objects.push(ExObject { u_object: Some(UObject::MaterialId(material.to_string())) });
@@ -412,15 +406,7 @@ pub async fn send_set_material(
     entity_id: u64,
     material_id: impl Into<String>,
 ) -> Result<(), String> {
-    let mut unit = generate_unit();
-    unit.set_update().unwrap();
-    unit.objects.push(ExObject::from(entity_id));
-    use expto::rdmp::ex_object::UObject;
-    unit.objects.push(ExObject {
-        u_object: Some(UObject::MaterialId(material_id.into())),
-    });
-    unit.send().await?;
-    Ok(())
+    EntityHandle::from_id(entity_id).set_material(material_id).await
 }
 
 /// 删除指定 ID 的实体
@@ -436,9 +422,199 @@ pub async fn send_set_material(
 /// send_destroy(1).await.unwrap();
 /// ```
 pub async fn send_destroy(entity_id: u64) -> Result<(), String> {
+    EntityHandle::from_id(entity_id).destroy().await
+}
+
+/// 构造批量清除 Unit（不发送）
+///
+/// `namespaces` 为空时清除本会话的全部实体，否则只清除这些命名空间中的实体。
+/// 清除只作用于本会话，不影响其他客户端。
+pub fn clear_unit(namespaces: &[&str]) -> Unit {
     let mut unit = generate_unit();
-    unit.set_destroy().unwrap();
-    unit.objects.push(ExObject::from(entity_id));
-    unit.send().await?;
-    Ok(())
+    let _ = unit.set_clear();
+    unit.objects.extend(namespaces.iter().map(|&ns| ExObject::namespace(ns)));
+    unit
+}
+
+/// 清除本会话中某命名空间（见 `ShapeBuilder::namespace`）的全部实体
+///
+/// # 示例
+/// ```no_run
+/// use redra_client::*;
+/// # async fn run() -> Result<(), String> {
+/// ShapeBuilder::sphere(0.5).namespace("detections").send().await?;
+/// send_clear_namespace("detections").await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn send_clear_namespace(namespace: &str) -> Result<(), String> {
+    clear_unit(&[namespace]).send().await
+}
+
+/// 清除本会话发送的全部实体
+pub async fn send_clear_all() -> Result<(), String> {
+    clear_unit(&[]).send().await
+}
+
+// ==================== 批量更新 API ====================
+
+/// 构造批量位姿更新 Unit（不发送）
+///
+/// 每个 Unit 携带一个 [`PoseBatch`]，按编码大小拆分为不超过
+/// [`DEFAULT_MAX_UNIT_BYTES`] 的若干 Unit。`materials` 为空或与 `ids` 等长，
+/// 空字符串表示不修改该实体的材质。长度不一致时返回错误。
+pub fn pose_batch_units(
+    ids: &[u64],
+    transforms: &[ExTransform],
+    materials: &[String],
+) -> Result<Vec<Unit>, String> {
+    PoseBatch::new(ids.to_vec(), transforms.to_vec())?.with_materials(materials.to_vec())?;
+
+    // stamp、指令与各层长度前缀的余量
+    let overhead = pose_batch_overhead() + 32;
+    let mut units = Vec::new();
+    let mut batch = PoseBatch::default();
+    let mut batch_len = overhead;
+    for (i, (&id, &transform)) in ids.iter().zip(transforms).enumerate() {
+        let material = materials.get(i);
+        let entry_len = prost::encoding::encoded_len_varint(id)
+            + prost::encoding::message::encoded_len(2, &transform)
+            + material.map_or(0, |m| prost::encoding::string::encoded_len(3, m));
+        if !batch.is_empty() && batch_len + entry_len > DEFAULT_MAX_UNIT_BYTES {
+            units.push(pose_batch_unit(std::mem::take(&mut batch)));
+            batch_len = overhead;
+        }
+        batch.ids.push(id);
+        batch.transforms.push(transform);
+        if let Some(material) = material {
+            batch.materials.push(material.clone());
+        }
+        batch_len += entry_len;
+    }
+    if !batch.is_empty() {
+        units.push(pose_batch_unit(batch));
+    }
+    Ok(units)
+}
+
+/// 不含条目的位姿批量 Unit 的编码长度上限
+///
+/// stamp 各字段取最大值估算，不调用 `generate_unit`，避免消耗不会发送的序列号
+/// （服务端会将缺失的序列号计为丢包，开启重排时还会等待）。
+fn pose_batch_overhead() -> usize {
+    Unit {
+        stamp: Some(ExStamp {
+            timestamp: u64::MAX,
+            session_id: Stamper::session_id().to_string(),
+            sequence_number: u32::MAX,
+        }),
+        command: Some(ExCommand { u_command: CommandType::Update as i32 }),
+        objects: vec![],
+    }
+    .encoded_len()
+}
+
+fn pose_batch_unit(batch: PoseBatch) -> Unit {
+    let mut unit = generate_unit();
+    let _ = unit.set_update();
+    unit.objects.push(ExObject::from(batch));
+    unit
+}
+
+/// 批量更新多个实体的位姿 — 代替逐个发送 `Update`，一次写入连接
+///
+/// `ids` 与 `transforms` 一一对应，长度不一致时返回错误。
+///
+/// # 示例
+/// ```no_run
+/// use redra_client::*;
+/// # async fn run() -> Result<(), String> {
+/// let ids: Vec<u64> = (0..5000).collect();
+/// let poses: Vec<ExTransform> = ids.iter()
+///     .map(|&i| ExTransform { x: i as f32, ..ExTransform::identity() })
+///     .collect();
+/// update_many(&ids, &poses).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn update_many(ids: &[u64], transforms: &[ExTransform]) -> Result<(), String> {
+    send_units(&pose_batch_units(ids, transforms, &[])?).await
+}
+
+/// 批量更新位姿与材质（`materials` 与 `ids` 等长，空字符串表示不修改）
+pub async fn update_many_with_materials(
+    ids: &[u64],
+    transforms: &[ExTransform],
+    materials: &[String],
+) -> Result<(), String> {
+    send_units(&pose_batch_units(ids, transforms, materials)?).await
+}
+
+// ==================== 坐标系 API ====================
+
+/// 构造坐标系变换 Unit（不发送）
+///
+/// 一个 Unit 可携带多条坐标系变换，时间取 stamp（或 `timestamp_ms`）。
+pub fn frame_transform_unit(transforms: Vec<FrameTransform>, timestamp_ms: Option<u64>) -> Unit {
+    let mut unit = generate_unit();
+    if let (Some(stamp), Some(timestamp)) = (unit.stamp.as_mut(), timestamp_ms) {
+        stamp.timestamp = timestamp;
+    }
+    unit.objects.extend(transforms.into_iter().map(ExObject::from));
+    unit
+}
+
+/// 声明命名坐标系的位姿：`frame_id` 在 `parent_frame_id` 中的变换
+///
+/// 实体通过 `ShapeBuilder::frame` 指定所在坐标系后，服务端沿坐标系链
+/// 解析到界面上选择的固定坐标系显示。位姿随时间重复发布即可。
+///
+/// # 示例
+/// ```no_run
+/// use redra_client::*;
+/// # async fn run() -> Result<(), String> {
+/// send_frame_transform("odom", "map", ExTransform { x: 10.0, ..ExTransform::identity() }).await?;
+/// send_frame_transform("lidar", "base_link", ExTransform { z: 1.8, ..ExTransform::identity() }).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn send_frame_transform(
+    frame_id: impl Into<String>,
+    parent_frame_id: impl Into<String>,
+    transform: ExTransform,
+) -> Result<(), String> {
+    frame_transform_unit(vec![FrameTransform::new(frame_id, parent_frame_id, transform)], None)
+        .send()
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use expto::rdmp::ex_object::UObject;
+
+    #[test]
+    fn test_pose_batch_units_split_and_validate() {
+        let ids: Vec<u64> = (0..5000).collect();
+        let transforms: Vec<ExTransform> = ids.iter()
+            .map(|&i| ExTransform { x: i as f32, y: -1.5, z: 0.25, ..ExTransform::identity() })
+            .collect();
+        let materials: Vec<String> = ids.iter().map(|i| format!("cluster_{:02}", i % 12 + 1)).collect();
+
+        let units = pose_batch_units(&ids, &transforms, &materials).unwrap();
+        assert!(units.len() > 1);
+        let mut seen = Vec::new();
+        for unit in &units {
+            assert!(unit.encoded_len() <= DEFAULT_MAX_UNIT_BYTES);
+            assert_eq!(unit.command.unwrap().u_command, CommandType::Update as i32);
+            let Some(UObject::PoseBatch(batch)) = &unit.objects[0].u_object else { panic!("应为 PoseBatch") };
+            assert_eq!(batch.ids.len(), batch.transforms.len());
+            assert_eq!(batch.ids.len(), batch.materials.len());
+            seen.extend(batch.ids.iter().copied());
+        }
+        assert_eq!(seen, ids);
+
+        assert!(pose_batch_units(&ids, &transforms[1..], &[]).is_err());
+        assert!(pose_batch_units(&ids, &transforms, &materials[1..]).is_err());
+    }
 }
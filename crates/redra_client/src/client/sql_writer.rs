@@ -16,6 +16,7 @@ use std::collections::HashMap;
 use std::path::Path;
 
 use expto::prelude::*;
+use expto::schema::ADDED_ENTITY_COLUMNS;
 use rusqlite::{Connection, params};
 
 use super::builder::ShapeBuilder;
@@ -30,6 +31,17 @@ struct EntityData {
     rotation: [f32; 4],
     scale: [f32; 3],
     tags: Vec<Tag>,
+    parent: Option<u64>,
+    /// 变换所在的命名坐标系（写入 `frame_name` 列）
+    frame_id: String,
+    /// 命名空间（写入 `namespace` 列，也用于写入端批量清除）
+    namespace: String,
+    /// 生存期（不落库）；到期后不再写入后续帧
+    lifetime: Option<Lifetime>,
+    /// 首次写入帧的时间戳
+    spawned_at: Option<u64>,
+    /// 首次写入之后已写入的帧数
+    age_frames: u32,
 }
 
 // ─── SqlWriter ─────────────────────────────────────────────────
@@ -40,14 +52,15 @@ struct EntityData {
 /// 与 `RdraWriter` API 兼容：使用 `spawn()` / `end_frame()` / `save()`。
 /// 生成的数据库与 Redra 主程序的 `FrameStorage` 格式完全兼容。
 ///
-/// 实体状态跨帧持续：未显式删除的实体会自动继承到下一帧。
+/// 实体状态跨帧持续：未显式删除的实体会自动继承到下一帧；
+/// 设置了生存期（`ShapeBuilder::lifetime`）的实体到期后不再写入。
 pub struct SqlWriter {
     conn: Connection,
     /// 当前帧的实体快照（id → entity）
     entities: HashMap<u64, EntityData>,
     /// 自动分配 ID 的计数器
     next_auto_id: u64,
-    /// 帧时间戳计数器（每帧递增 200ms，模拟 5fps）
+    /// 帧时间戳计数器（每帧递增 200ms，模拟 5fps；`end_frame_at` 可指定真实时间）
     timestamp: u64,
 }
 
@@ -92,7 +105,10 @@ impl SqlWriter {
                 rw REAL NOT NULL DEFAULT 1,
                 sx REAL NOT NULL DEFAULT 1,
                 sy REAL NOT NULL DEFAULT 1,
-                sz REAL NOT NULL DEFAULT 1
+                sz REAL NOT NULL DEFAULT 1,
+                parent_id INTEGER,
+                frame_name TEXT,
+                namespace TEXT
             );
             CREATE TABLE IF NOT EXISTS entity_tags (
                 id INTEGER PRIMARY KEY AUTOINCREMENT,
@@ -108,7 +124,7 @@ impl SqlWriter {
             CREATE INDEX IF NOT EXISTS idx_tags_text ON entity_tags(tag_text);",
         )
         .map_err(|e| format!("初始化表结构失败: {}", e))?;
-        Ok(())
+        ensure_added_columns(&self.conn)
     }
 
     /// 将 ShapeBuilder 写入当前帧。
@@ -122,9 +138,8 @@ impl SqlWriter {
             id
         });
 
-        // 欧拉角 → 四元数
-        let q = nalgebra::UnitQuaternion::from_euler_angles(builder.rx, builder.ry, builder.rz);
-        let [qx, qy, qz, qw] = q.quaternion().coords.into();
+        // 四元数优先，否则按协议约定（XYZ 欧拉角）换算
+        let rotation = builder.transform().quaternion().to_array();
 
         self.entities.insert(
             id,
@@ -132,17 +147,28 @@ impl SqlWriter {
                 mesh: builder.mesh,
                 material: builder.material.unwrap_or_default(),
                 translation: [builder.tx, builder.ty, builder.tz],
-                rotation: [qx, qy, qz, qw],
+                rotation,
                 scale: [builder.sx, builder.sy, builder.sz],
                 tags: builder.tag_list,
+                parent: builder.parent,
+                frame_id: builder.frame_id.unwrap_or_default(),
+                namespace: builder.namespace.unwrap_or_default(),
+                lifetime: builder.lifetime,
+                spawned_at: None,
+                age_frames: 0,
             },
         );
         id
     }
 
-    /// 从当前帧中删除指定 ID 的实体
+    /// 从当前帧中删除指定 ID 的实体（连同其子孙实体）
     pub fn destroy(&mut self, id: u64) {
-        self.entities.remove(&id);
+        let mut pending = vec![id];
+        while let Some(id) = pending.pop() {
+            if self.entities.remove(&id).is_some() {
+                pending.extend(self.entities.iter().filter(|(_, e)| e.parent == Some(id)).map(|(&child, _)| child));
+            }
+        }
     }
 
     /// 修改已有实体的材质
@@ -152,6 +178,17 @@ impl SqlWriter {
         }
     }
 
+    /// 删除当前帧中某命名空间的全部实体（连同其子孙实体）
+    pub fn destroy_namespace(&mut self, namespace: &str) {
+        let ids: Vec<u64> = self.entities.iter()
+            .filter(|(_, e)| e.namespace == namespace)
+            .map(|(&id, _)| id)
+            .collect();
+        for id in ids {
+            self.destroy(id);
+        }
+    }
+
     /// 清除当前帧中所有实体
     pub fn destroy_all(&mut self) {
         self.entities.clear();
@@ -173,6 +210,23 @@ impl SqlWriter {
     /// 在一个事务中完成帧记录、实体、标签的插入。
     /// 实体状态会持续到下一帧（不清空）。
     pub fn end_frame(&mut self) -> Result<(), String> {
+        self.commit_frame(self.timestamp)?;
+        self.timestamp += 200;
+        Ok(())
+    }
+
+    /// 以指定时间戳（Unix 毫秒，如传感器采集时间）结束当前帧。
+    ///
+    /// 后续 `end_frame()` 从该时间继续按 200ms 递增。
+    pub fn end_frame_at(&mut self, timestamp_ms: u64) -> Result<(), String> {
+        self.commit_frame(timestamp_ms)?;
+        self.timestamp = timestamp_ms + 200;
+        Ok(())
+    }
+
+    /// 在事务中写入一帧
+    fn commit_frame(&mut self, timestamp: u64) -> Result<(), String> {
+        self.expire(timestamp);
         let frame_id = self.next_frame_id()?;
         let now = std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
@@ -184,7 +238,7 @@ impl SqlWriter {
             .execute_batch("BEGIN TRANSACTION")
             .map_err(|e| format!("开始事务失败: {}", e))?;
 
-        if let Err(e) = self.write_frame(frame_id, now) {
+        if let Err(e) = self.write_frame(frame_id, timestamp, now) {
             let _ = self.conn.execute_batch("ROLLBACK");
             return Err(e);
         }
@@ -193,17 +247,33 @@ impl SqlWriter {
             .execute_batch("COMMIT")
             .map_err(|e| format!("提交事务失败: {}", e))?;
 
-        self.timestamp += 200;
+        for entity in self.entities.values_mut().filter(|e| e.lifetime.is_some()) {
+            entity.age_frames = entity.age_frames.saturating_add(1);
+        }
         Ok(())
     }
 
+    /// 移除在时间 `timestamp` 的帧中已到期的实体（连同其子孙实体）
+    fn expire(&mut self, timestamp: u64) {
+        let expired: Vec<u64> = self.entities.iter_mut()
+            .filter_map(|(&id, e)| {
+                let lifetime = e.lifetime?;
+                let spawned_at = *e.spawned_at.get_or_insert(timestamp);
+                lifetime.is_expired(spawned_at, timestamp, e.age_frames).then_some(id)
+            })
+            .collect();
+        for id in expired {
+            self.destroy(id);
+        }
+    }
+
     /// 写入一帧的数据（已在事务中调用）
-    fn write_frame(&self, frame_id: i64, created_at: i64) -> Result<(), String> {
+    fn write_frame(&self, frame_id: i64, timestamp: u64, created_at: i64) -> Result<(), String> {
         // 插入帧记录
         self.conn
             .execute(
                 "INSERT INTO frames (frame_id, timestamp, created_at) VALUES (?1, ?2, ?3)",
-                params![frame_id, self.timestamp as i64, created_at],
+                params![frame_id, timestamp as i64, created_at],
             )
             .map_err(|e| format!("插入帧记录失败: {}", e))?;
 
@@ -215,8 +285,8 @@ impl SqlWriter {
             self.conn
                 .execute(
                     "INSERT INTO entities (entity_id, frame_id, material, mesh_data, \
-                     tx, ty, tz, rx, ry, rz, rw, sx, sy, sz) \
-                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)",
+                     tx, ty, tz, rx, ry, rz, rw, sx, sy, sz, parent_id, frame_name, namespace) \
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17)",
                     params![
                         entity_id as i64,
                         frame_id,
@@ -232,6 +302,9 @@ impl SqlWriter {
                         data.scale[0],
                         data.scale[1],
                         data.scale[2],
+                        data.parent.map(|id| id as i64),
+                        data.frame_id,
+                        data.namespace,
                     ],
                 )
                 .map_err(|e| format!("插入实体失败: {}", e))?;
@@ -286,18 +359,33 @@ impl SqlWriter {
         let conn = Connection::open(target_path)
             .map_err(|e| format!("打开目标数据库失败: {}", e))?;
         let source_str = source_path.to_str().ok_or_else(|| "路径包含无效 UTF-8".to_string())?;
+        ensure_added_columns(&conn)?;
         conn.execute("ATTACH DATABASE ? AS src", params![source_str])
             .map_err(|e| format!("ATTACH 失败: {}", e))?;
-        let r = conn.execute_batch(
+        // 旧版源库缺少的新增列按 NULL 复制
+        let source_columns = match entity_columns(&conn, "src") {
+            Ok(columns) => columns,
+            Err(e) => {
+                conn.execute_batch("DETACH DATABASE src").ok();
+                return Err(e);
+            }
+        };
+        let targets: Vec<&str> = ADDED_ENTITY_COLUMNS.iter().map(|&(column, _)| column).collect();
+        let sources: Vec<&str> = targets.iter()
+            .map(|&column| if source_columns.iter().any(|c| c == column) { column } else { "NULL" })
+            .collect();
+        let r = conn.execute_batch(&format!(
             "BEGIN; \
              INSERT INTO entities (entity_id, frame_id, material, mesh_data, \
-              tx, ty, tz, rx, ry, rz, rw, sx, sy, sz) \
+              tx, ty, tz, rx, ry, rz, rw, sx, sy, sz, {}) \
              SELECT entity_id, frame_id, material, mesh_data, \
-              tx, ty, tz, rx, ry, rz, rw, sx, sy, sz FROM src.entities; \
+              tx, ty, tz, rx, ry, rz, rw, sx, sy, sz, {} FROM src.entities; \
              INSERT INTO entity_tags (entity_id, frame_id, tag_index, tag_text, tag_data) \
              SELECT entity_id, frame_id, tag_index, tag_text, tag_data FROM src.entity_tags; \
              COMMIT;",
-        );
+            targets.join(", "),
+            sources.join(", "),
+        ));
         conn.execute_batch("DETACH DATABASE src").ok();
         r.map_err(|e| format!("合并实体失败: {}", e))
     }
@@ -315,3 +403,28 @@ impl SqlWriter {
         Ok(())
     }
 }
+
+// ─── 表结构迁移 ────────────────────────────────────────────────
+
+/// `schema` 库（`main` / ATTACH 名）的 entities 表的全部列名
+fn entity_columns(conn: &Connection, schema: &str) -> Result<Vec<String>, String> {
+    let mut stmt = conn
+        .prepare(&format!("PRAGMA {}.table_info(entities)", schema))
+        .map_err(|e| format!("查询 entities 表结构失败: {}", e))?;
+    let names = stmt
+        .query_map([], |row| row.get::<_, String>(1))
+        .map_err(|e| format!("查询 entities 表结构失败: {}", e))?;
+    names.collect::<Result<_, _>>().map_err(|e| format!("读取列名失败: {}", e))
+}
+
+/// 旧版数据库补充新增列
+fn ensure_added_columns(conn: &Connection) -> Result<(), String> {
+    let columns = entity_columns(conn, "main")?;
+    for (column, sql_type) in ADDED_ENTITY_COLUMNS {
+        if !columns.iter().any(|c| c == column) {
+            conn.execute_batch(&format!("ALTER TABLE entities ADD COLUMN {} {}", column, sql_type))
+                .map_err(|e| format!("迁移 entities 表（{}）失败: {}", column, e))?;
+        }
+    }
+    Ok(())
+}
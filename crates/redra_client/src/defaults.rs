@@ -17,8 +17,8 @@
 /// | Sphere | `ShapeBuilder::sphere(radius)` | radius > 0 |
 /// | Cylinder | `ShapeBuilder::cylinder(radius, height)` | radius > 0, height > 0 |
 /// | Cone | `ShapeBuilder::cone(radius, height)` | radius > 0, height > 0 |
-/// | Line | `ShapeBuilder::line(...)` | 起终点距离 > 0.001 |
-/// | Cube | `ShapeBuilder::cube(vertices)` | 8 个顶点，每个维度 > 0.001 |
+/// | Line | `ShapeBuilder::line(...)` | 起终点距离 > 0.001，默认屏幕 2 像素宽 |
+/// | Cube | `ShapeBuilder::cube(vertices)` | 8 个顶点，每个维度 > 0.001；`bounding_box` 材质默认半透明 + 棱边 |
 pub mod mesh_constraints {
     pub const MIN_CUBE_DIMENSION: f32 = 0.001;
     pub const MIN_LINE_LENGTH: f32 = 0.001;
@@ -106,7 +106,7 @@ pub mod cluster {
 /// | `point_cloud` | 默认点云 | 暖白色 (0.92, 0.90, 0.82) |
 /// | `ground` | 地面点 | 暗橄榄绿，低饱和不抢视线 |
 /// | `noise` | 噪声/无效点 | 低饱和灰紫，区别于有效数据 |
-/// | `bounding_box` | 包围盒 | 亮绿色，高可见性 |
+/// | `bounding_box` | 包围盒 | 亮绿色，高可见性；Cube / 有向包围盒默认半透明填充 + 棱边 |
 /// | `trajectory` | 轨迹线 | 亮青色，连续性强 |
 /// | `selected` | 选中实体 | 白金色 + 发光 |
 /// | `alert` | 警告/异常 | 琥珀色 + 发光 |
@@ -2,9 +2,12 @@
 //!
 //! # 两种 API 风格
 //!
-//! - **链式构建器** [`ShapeBuilder`] — 推荐，支持 ID、材质、标签、分组点云
+//! - **链式构建器** [`ShapeBuilder`] — 推荐，支持 ID、材质、标签、分组点云；
+//!   `send()` 返回 [`EntityHandle`]，可继续更新变换/材质/标签或销毁
 //! - **便捷函数** [`send_sphere`] / [`send_cube`] 等 — 单行调用，适合快速原型
 //!
+//! 一帧内实体较多时，用 [`FrameBatch`] 累积多个 `ShapeBuilder`，提交时合并为少量 Unit 一次写入。
+//!
 //! # 材质体系
 //!
 //! 通过 [`defaults`] 模块访问预设材质：
@@ -25,5 +28,11 @@ pub use client::send::*;
 // 导出 builder 模块（ShapeBuilder + 便捷函数）
 pub use client::builder::*;
 
+// 导出 handle 模块（EntityHandle）
+pub use client::handle::EntityHandle;
+
+// 导出 batch 模块（FrameBatch）
+pub use client::batch::FrameBatch;
+
 // 导出 sql_writer 模块（SqlWriter）
 pub use client::sql_writer::SqlWriter;
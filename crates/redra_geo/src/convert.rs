@@ -1,10 +1,14 @@
-use nalgebra::{UnitQuaternion, Vector3};
+use nalgebra::{Quaternion, UnitQuaternion, Vector3};
 use expto::rdmp::ExTransform;
 use crate::Transform3;
 
 /// 将协议 ExTransform 转换为几何 Transform3
+///
+/// 旋转取 [`ExTransform::quaternion`]（四元数优先，欧拉角按 XYZ 约定），
+/// 不使用 nalgebra 的 roll-pitch-yaw 约定。
 pub fn extransform_to_transform3(et: &ExTransform) -> Transform3 {
-    let rotation = UnitQuaternion::from_euler_angles(et.rx, et.ry, et.rz);
+    let q = et.quaternion();
+    let rotation = UnitQuaternion::new_normalize(Quaternion::new(q.w, q.x, q.y, q.z));
 
     Transform3 {
         translation: Vector3::new(et.x, et.y, et.z),
@@ -13,19 +17,18 @@ pub fn extransform_to_transform3(et: &ExTransform) -> Transform3 {
     }
 }
 
-/// 将几何 Transform3 转换为协议 ExTransform
+/// 将几何 Transform3 转换为协议 ExTransform（写入四元数并回填 XYZ 欧拉角）
 pub fn transform3_to_extransform(t: &Transform3) -> ExTransform {
-    let (rx, ry, rz) = t.rotation.euler_angles();
-
     ExTransform {
         x: t.translation.x,
         y: t.translation.y,
         z: t.translation.z,
-        rx, ry, rz,
         sx: t.scale,
         sy: t.scale,
         sz: t.scale,
+        ..Default::default()
     }
+    .with_quaternion(<[f32; 4]>::from(t.rotation.coords))
 }
 
 #[cfg(test)]
@@ -49,12 +52,24 @@ mod tests {
         assert!((t.scale - t_back.scale).abs() < 1e-6);
     }
 
+    #[test]
+    fn test_euler_follows_protocol_convention() {
+        // 欧拉角按 R = Rx · Ry · Rz 解释
+        let et = ExTransform { rx: 0.3, ry: -0.6, rz: 1.1, ..ExTransform::identity() };
+        let expected = UnitQuaternion::from_axis_angle(&Vector3::x_axis(), 0.3)
+            * UnitQuaternion::from_axis_angle(&Vector3::y_axis(), -0.6)
+            * UnitQuaternion::from_axis_angle(&Vector3::z_axis(), 1.1);
+        let t = extransform_to_transform3(&et);
+        assert!(t.rotation.angle_to(&expected) < 1e-5);
+    }
+
     #[test]
     fn test_identity() {
         let et = ExTransform {
             x: 0.0, y: 0.0, z: 0.0,
             rx: 0.0, ry: 0.0, rz: 0.0,
             sx: 1.0, sy: 1.0, sz: 1.0,
+            rotation: None,
         };
         let t = extransform_to_transform3(&et);
         assert!((t.translation - Vector3::zeros()).norm() < 1e-6);
@@ -1,6 +1,8 @@
 pub mod transform3;
 pub mod axis;
 pub mod convert;
+pub mod obb;
 pub mod prelude;
 
 pub use transform3::Transform3;
+pub use obb::{Obb, obb_from_corners};
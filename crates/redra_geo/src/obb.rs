@@ -0,0 +1,134 @@
+use nalgebra::{Matrix3, Rotation3, UnitQuaternion, Vector3};
+
+/// 有向包围盒：中心 + 半边长 + 旋转
+#[derive(Debug, Clone, PartialEq)]
+pub struct Obb {
+    pub center: Vector3<f32>,
+    pub half_extents: Vector3<f32>,
+    pub rotation: UnitQuaternion<f32>,
+}
+
+impl Obb {
+    /// 8 个角点（局部坐标 `(±x, ±y, ±z)` 经旋转平移后的世界坐标）
+    pub fn corners(&self) -> [Vector3<f32>; 8] {
+        let h = self.half_extents;
+        let mut corners = [Vector3::zeros(); 8];
+        for (i, corner) in corners.iter_mut().enumerate() {
+            let local = Vector3::new(
+                if i & 1 == 0 { -h.x } else { h.x },
+                if i & 2 == 0 { -h.y } else { h.y },
+                if i & 4 == 0 { -h.z } else { h.z },
+            );
+            *corner = self.center + self.rotation * local;
+        }
+        corners
+    }
+}
+
+/// 由 8 个角点（任意顺序）恢复有向包围盒
+///
+/// 取任一角点的三条最短邻边作为盒子的三个轴，正交化后投影得到半边长。
+/// 轴按与世界 X/Y/Z 的对齐程度排序并统一朝向，轴对齐的盒子得到单位旋转。
+/// 角点数不为 8 或盒子退化（任一边长 < `1e-6`）时返回 `None`。
+pub fn obb_from_corners(corners: &[Vector3<f32>]) -> Option<Obb> {
+    if corners.len() != 8 {
+        return None;
+    }
+    let center = corners.iter().sum::<Vector3<f32>>() / 8.0;
+
+    let origin = corners[0];
+    let mut edges: Vec<Vector3<f32>> = corners[1..].iter().map(|c| c - origin).collect();
+    edges.sort_by(|a, b| a.norm_squared().total_cmp(&b.norm_squared()));
+    if edges[0].norm() < 1e-6 {
+        return None;
+    }
+
+    // Gram-Schmidt 正交化
+    let a1 = edges[0].normalize();
+    let e2 = edges[1] - a1 * edges[1].dot(&a1);
+    if e2.norm() < 1e-6 {
+        return None;
+    }
+    let a2 = e2.normalize();
+    let a3 = a1.cross(&a2);
+
+    // 按与世界轴的对齐程度分配轴，并使其与对应世界轴同向
+    let mut axes = [a1, a2, a3];
+    for world in 0..3 {
+        let best = (world..3)
+            .max_by(|&i, &j| axes[i][world].abs().total_cmp(&axes[j][world].abs()))
+            .unwrap_or(world);
+        axes.swap(world, best);
+        if axes[world][world] < 0.0 {
+            axes[world] = -axes[world];
+        }
+    }
+    if axes[0].cross(&axes[1]).dot(&axes[2]) < 0.0 {
+        axes[2] = -axes[2];
+    }
+
+    let mut half_extents = Vector3::zeros();
+    for (i, axis) in axes.iter().enumerate() {
+        half_extents[i] = corners.iter()
+            .map(|c| (c - center).dot(axis).abs())
+            .fold(0.0, f32::max);
+    }
+    if half_extents.min() < 1e-6 {
+        return None;
+    }
+
+    let matrix = Matrix3::from_columns(&axes);
+    let rotation = UnitQuaternion::from_rotation_matrix(&Rotation3::from_matrix_unchecked(matrix));
+    Some(Obb { center, half_extents, rotation })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_axis_aligned_box() {
+        let obb = Obb {
+            center: Vector3::new(1.0, 2.0, 3.0),
+            half_extents: Vector3::new(2.0, 1.0, 0.5),
+            rotation: UnitQuaternion::identity(),
+        };
+        let mut corners = obb.corners().to_vec();
+        corners.reverse();
+
+        let recovered = obb_from_corners(&corners).unwrap();
+        assert!((recovered.center - obb.center).norm() < 1e-5);
+        assert!((recovered.half_extents - obb.half_extents).norm() < 1e-5);
+        assert!(recovered.rotation.angle() < 1e-5);
+    }
+
+    #[test]
+    fn test_rotated_box_round_trip() {
+        let obb = Obb {
+            center: Vector3::new(-4.0, 0.5, 10.0),
+            half_extents: Vector3::new(2.3, 0.9, 0.8),
+            rotation: UnitQuaternion::from_axis_angle(&Vector3::z_axis(), 0.6)
+                * UnitQuaternion::from_axis_angle(&Vector3::x_axis(), 0.2),
+        };
+        let recovered = obb_from_corners(&obb.corners()).unwrap();
+
+        // 盒子对称，比较角点集合而不是旋转本身
+        for corner in recovered.corners() {
+            let nearest = obb.corners().iter()
+                .map(|c| (c - corner).norm())
+                .fold(f32::MAX, f32::min);
+            assert!(nearest < 1e-4, "角点 {:?} 不在原盒子上", corner);
+        }
+    }
+
+    #[test]
+    fn test_degenerate_box() {
+        let flat = Obb {
+            center: Vector3::zeros(),
+            half_extents: Vector3::new(1.0, 1.0, 0.0),
+            rotation: UnitQuaternion::identity(),
+        };
+        assert!(obb_from_corners(&flat.corners()).is_none());
+        assert!(obb_from_corners(&flat.corners()[..4]).is_none());
+    }
+}
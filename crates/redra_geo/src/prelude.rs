@@ -1,3 +1,4 @@
 pub use crate::Transform3;
 pub use crate::axis::AxisConvention;
 pub use crate::convert::{extransform_to_transform3, transform3_to_extransform};
+pub use crate::obb::{Obb, obb_from_corners};
@@ -75,6 +75,7 @@ pub fn points_to_entities(points: &[(f32, f32, f32)]) -> Vec<(u64, ExMesh, ExTra
                 sx: 1.0,
                 sy: 1.0,
                 sz: 1.0,
+                rotation: None,
             };
             (id, mesh, transform)
         })
@@ -1,5 +1,5 @@
 use bevy::prelude::*;
-use expto::rdmp::Unit;
+use expto::rdmp::{ExStamp, Unit};
 use expto::rdmp::decoding::decode_and_next;
 use log::{info, error, debug};
 use tokio::sync::broadcast;
@@ -115,6 +115,15 @@ impl RDLinker {
         }
     }
     
+    /// 客户端未填写会话时，以连接标识作为会话，保证不同连接的实体 ID 互不覆盖
+    fn attach_session(&self, mut unit: Unit) -> Unit {
+        let stamp = unit.stamp.get_or_insert_with(ExStamp::default);
+        if stamp.session_id.is_empty() {
+            stamp.session_id = format!("link_{}", self.id);
+        }
+        unit
+    }
+
     /// 启动连接处理器，开始处理TCP连接数据
     /// 
     /// 该方法进入一个循环，持续从TCP连接中读取原始数据，
@@ -165,6 +174,7 @@ impl RDLinker {
                         match decode_and_next(&accum_buffer) {
                             Ok((unit, remaining)) => {
                                 // 发送解析出的协议单元
+                                let unit = self.attach_session(unit);
                                 if let Err(e) = self.sender.send(unit).await {
                                     error!("发送解析后的数据包失败: {}", e);
                                     break;
@@ -71,7 +71,7 @@ impl MaterialManager {
             "metal", "glass", "glow", "matte", "plastic", "wood",
         ]);
         self.register_category("mesh_types", &[
-            "point", "line", "sphere", "cylinder", "cone",
+            "point", "line", "sphere", "cylinder", "cone", "triangle_mesh",
         ]);
         self.register_category("ui", &[
             "wireframe", "highlight", "disabled",
@@ -180,6 +180,9 @@ impl MaterialManager {
                     Some(UMesh::Cylinder(_)) => Some("materials/mesh_types/cylinder.toml"),
                     Some(UMesh::Cone(_)) => Some("materials/mesh_types/cone.toml"),
                     Some(UMesh::Cube(_)) => Some("materials/mesh_types/cube.toml"),
+                    Some(UMesh::TriangleMesh(_)) => Some("materials/mesh_types/triangle_mesh.toml"),
+                    Some(UMesh::Polyline(_)) => Some("materials/mesh_types/line.toml"),
+                    Some(UMesh::OrientedBox(_)) => Some("materials/mesh_types/cube.toml"),
                     None => None,
                 };
             }
@@ -237,3 +240,16 @@ impl MaterialManager {
         }
     }
 }
+
+/// 通用材质的基础色（线性空间）— 仅 `StandardMaterial` 且已加载时可取得
+///
+/// 自定义着色材质（点 splat、带状线）以此跟随实体材质的颜色。
+pub fn generic_base_color(
+    generic_materials: &Assets<GenericMaterial>,
+    standard_materials: &Assets<StandardMaterial>,
+    handle: &Handle<GenericMaterial>,
+) -> Option<LinearRgba> {
+    let generic = generic_materials.get(handle)?;
+    let standard = generic.handle.inner().clone().try_typed::<StandardMaterial>().ok()?;
+    Some(standard_materials.get(&standard)?.base_color.to_linear())
+}
@@ -4,7 +4,9 @@
 //! - frame: 帧数据管理（FrameManager, KeyFrame, Inpto 等）
 //! - protocol: 协议数据的提取与转换（Unit → Inpto）
 //! - storage: 帧数据持久化
+//! - namespace: 命名空间（图层）显隐
 
 pub mod frame;
+pub mod namespace;
 pub mod protocol;
 pub mod tag;
@@ -7,15 +7,19 @@ pub mod manager;
 pub mod keyframe;
 pub mod inpto;
 pub mod unit_pack;
+pub mod sequence;
+pub mod tf;
 #[cfg(feature = "graph")]
 pub mod playback;
 #[cfg(feature = "graph")]
 pub mod storage;
 
 pub use manager::FrameManager;
-pub use keyframe::KeyFrame;
-pub use inpto::{Inpto, InptoTransform};
+pub use keyframe::{EntityNode, KeyFrame};
+pub use inpto::{Inpto, InptoLifetime, InptoTransform};
 pub use unit_pack::UnitPack;
+pub use sequence::{SequenceStats, SequenceTracker};
+pub use tf::FrameTree;
 #[cfg(feature = "graph")]
 pub use playback::{PlaybackState, FramePlaybackPlugin};
 #[cfg(feature = "graph")]
@@ -33,6 +37,7 @@ impl Plugin for FrameManagerPlugin {
             .init_resource::<PlaybackState>()
             .init_resource::<crate::data::tag::TagRegistry>()
             .init_resource::<crate::data::tag::TagFilter>()
+            .init_resource::<crate::data::namespace::NamespaceFilter>()
             .add_systems(Startup, setup_frame_manager)
             .add_systems(Update, update_frame_manager);
     }
@@ -1,4 +1,5 @@
-use expto::rdmp::{ExMesh, Tag};
+use expto::rdmp::{ExMesh, Lifetime, Tag};
+use nalgebra::{Quaternion, UnitQuaternion, Vector3};
 
 /// 与 bevy Transform 无关的内部变换表示
 #[derive(Clone, Copy, Debug)]
@@ -23,6 +24,36 @@ impl InptoTransform {
             sx: 1.0, sy: 1.0, sz: 1.0,
         }
     }
+
+    fn rotation(&self) -> UnitQuaternion<f32> {
+        UnitQuaternion::new_normalize(Quaternion::new(self.rw, self.rx, self.ry, self.rz))
+    }
+
+    fn from_parts(t: Vector3<f32>, r: UnitQuaternion<f32>, s: Vector3<f32>) -> Self {
+        Self {
+            tx: t.x, ty: t.y, tz: t.z,
+            rx: r.i, ry: r.j, rz: r.k, rw: r.w,
+            sx: s.x, sy: s.y, sz: s.z,
+        }
+    }
+
+    /// 复合变换 `self * child`（先施加 `child`），与 bevy `Transform * Transform` 一致
+    pub fn mul_transform(&self, child: &InptoTransform) -> Self {
+        let rotation = self.rotation();
+        let scale = Vector3::new(self.sx, self.sy, self.sz);
+        let t = Vector3::new(self.tx, self.ty, self.tz)
+            + rotation * scale.component_mul(&Vector3::new(child.tx, child.ty, child.tz));
+        Self::from_parts(t, rotation * child.rotation(), scale.component_mul(&Vector3::new(child.sx, child.sy, child.sz)))
+    }
+
+    /// 逆变换（非均匀缩放且带旋转时为近似）；零缩放分量保持为零
+    pub fn inverse(&self) -> Self {
+        let inv = |s: f32| if s.abs() > f32::EPSILON { 1.0 / s } else { 0.0 };
+        let rotation = self.rotation().inverse();
+        let scale = Vector3::new(inv(self.sx), inv(self.sy), inv(self.sz));
+        let t = -scale.component_mul(&(rotation * Vector3::new(self.tx, self.ty, self.tz)));
+        Self::from_parts(t, rotation, scale)
+    }
 }
 
 impl Default for InptoTransform {
@@ -35,32 +66,60 @@ impl Default for InptoTransform {
 
 impl From<expto::rdmp::ExTransform> for InptoTransform {
     fn from(t: expto::rdmp::ExTransform) -> Self {
-        // 欧拉角转四元数（与协议一致）
-        let (sx_half, cx_half) = (t.rx * 0.5).sin_cos();
-        let (sy_half, cy_half) = (t.ry * 0.5).sin_cos();
-        let (sz_half, cz_half) = (t.rz * 0.5).sin_cos();
+        // 四元数优先，缺省时按协议约定（XYZ 欧拉角）换算
+        let q = t.quaternion();
         Self {
             tx: t.x, ty: t.y, tz: t.z,
-            rx: sx_half * cy_half * cz_half + cx_half * sy_half * sz_half,
-            ry: cx_half * sy_half * cz_half - sx_half * cy_half * sz_half,
-            rz: cx_half * cy_half * sz_half + sx_half * sy_half * cz_half,
-            rw: cx_half * cy_half * cz_half - sx_half * sy_half * sz_half,
+            rx: q.x, ry: q.y, rz: q.z, rw: q.w,
             sx: t.sx, sy: t.sy, sz: t.sz,
         }
     }
 }
 
+/// 实体生存期状态 — 协议生存期加上生成时间与已存在的帧数
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct InptoLifetime {
+    pub lifetime: Lifetime,
+    /// 生成（或最近一次刷新生存期）时的时间戳
+    pub spawned_at: u64,
+    /// 生成所在帧之后经过的帧数
+    pub age_frames: u32,
+}
+
+impl InptoLifetime {
+    pub fn new(lifetime: Lifetime, spawned_at: u64) -> Self {
+        Self { lifetime, spawned_at, age_frames: 0 }
+    }
+
+    /// 推进到时间戳为 `now` 的下一帧；到期时返回 `None`
+    pub fn advance(&self, now: u64) -> Option<Self> {
+        let next = Self { age_frames: self.age_frames.saturating_add(1), ..*self };
+        (!self.lifetime.is_expired(next.spawned_at, now, next.age_frames)).then_some(next)
+    }
+}
+
 /// 中间表示 — 协议数据到渲染数据的转换单元
+#[derive(Clone)]
 pub struct Inpto {
     pub mesh: ExMesh,
     pub material: String,
     pub transform: InptoTransform,
     pub tags: Vec<Tag>,
+    /// 来源会话（`ExStamp.session_id` 或连接标识；本地加载的数据为空）
+    pub session: String,
+    /// 父实体 ID（与 `KeyFrame.ids` 同一 ID 空间）；存在时 `transform` 相对父实体
+    pub parent: Option<u64>,
+    /// `transform` 所在的命名坐标系（见 [`FrameTree`](crate::data::frame::FrameTree)）；为空表示固定坐标系
+    pub frame_id: String,
+    /// 命名空间（图层），用于分层显示与批量清除；为空表示默认命名空间
+    pub namespace: String,
+    /// 生存期；`None` 表示只存在于所属关键帧（不跨帧延续）
+    pub lifetime: Option<InptoLifetime>,
 }
 
 impl Inpto {
     pub fn new(mesh: ExMesh, material: String, transform: InptoTransform) -> Self {
-        Self { mesh, material, transform, tags: Vec::new() }
+        Self { mesh, material, transform, tags: Vec::new(), session: String::new(), parent: None, frame_id: String::new(), namespace: String::new(), lifetime: None }
     }
 
     pub fn with_tag(mut self, tag: Tag) -> Self {
@@ -85,6 +144,9 @@ impl Inpto {
             Some(UMesh::Cylinder(_)) => "materials/mesh_types/cylinder.toml",
             Some(UMesh::Cone(_)) => "materials/mesh_types/cone.toml",
             Some(UMesh::Cube(_)) => "materials/mesh_types/cube.toml",
+            Some(UMesh::TriangleMesh(_)) => "materials/mesh_types/triangle_mesh.toml",
+            Some(UMesh::Polyline(_)) => "materials/mesh_types/line.toml",
+            Some(UMesh::OrientedBox(_)) => "materials/mesh_types/cube.toml",
             None => "materials/default.toml",
         }.to_string()
     }
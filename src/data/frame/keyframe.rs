@@ -1,10 +1,12 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::time::{SystemTime, UNIX_EPOCH};
 
-use expto::rdmp::{CommandType, ExMesh, ExTransform, Tag, Unit, ex_object::UObject};
+use expto::rdmp::{CommandType, ExMesh, ExObject, ExTransform, Lifetime, PoseBatch, Tag, Unit, ex_object::UObject};
 
-use crate::data::protocol::{e2i_transform, parse_command, extract_id, extract_material_id, extract_tag};
-use crate::data::frame::Inpto;
+use crate::data::protocol::{
+    e2i_transform, parse_command, parse_session, scoped_entity_id, extract_material_id, extract_lifetime, extract_namespace, extract_tag,
+};
+use crate::data::frame::{Inpto, InptoLifetime};
 
 /// 生成一个单调递增的实体 ID（基于时间戳和当前 pack 数量）
 fn generate_entity_id(packs_len: usize) -> u64 {
@@ -15,7 +17,90 @@ fn generate_entity_id(packs_len: usize) -> u64 {
     ts ^ (packs_len as u64).rotate_left(32)
 }
 
+/// 按 `Id` 边界切分对象序列：每组以 `Id` 开头，到下一个 `Id` 之前结束；
+/// 首个 `Id` 之前的对象单独成组（该组不含 `Id`）
+fn split_id_groups(objects: &[ExObject]) -> Vec<&[ExObject]> {
+    let mut starts: Vec<usize> = objects.iter()
+        .enumerate()
+        .filter(|(i, obj)| *i > 0 && matches!(obj.u_object, Some(UObject::Id(_))))
+        .map(|(i, _)| i)
+        .collect();
+    starts.insert(0, 0);
+    starts.push(objects.len());
+    starts.windows(2)
+        .filter(|w| w[0] < w[1])
+        .map(|w| &objects[w[0]..w[1]])
+        .collect()
+}
+
+/// 一个 Id 分组中出现的实体字段（ID 均为客户端本地 ID，未做会话作用域换算）
+#[derive(Default)]
+struct GroupFields {
+    id: Option<u64>,
+    mesh: Option<ExMesh>,
+    transform: Option<ExTransform>,
+    material: Option<String>,
+    tags: Vec<Tag>,
+    parent: Option<u64>,
+    frame_id: Option<String>,
+    namespace: Option<String>,
+    lifetime: Option<Lifetime>,
+}
+
+impl GroupFields {
+    /// 同一字段出现多次时以最后一个为准（`Tag` 累加）
+    fn parse(group: &[ExObject]) -> Self {
+        let mut fields = Self::default();
+        for u_object in group.iter().filter_map(|obj| obj.u_object.as_ref()) {
+            match u_object {
+                UObject::Id(id) => fields.id = Some(*id),
+                UObject::Mesh(m) => fields.mesh = Some(m.clone()),
+                UObject::Transform(t) => fields.transform = Some(*t),
+                UObject::MaterialId(m) => fields.material = Some(m.clone()),
+                UObject::Tag(t) => fields.tags.push(t.clone()),
+                UObject::ParentId(p) => fields.parent = Some(*p),
+                UObject::FrameId(f) => fields.frame_id = Some(f.clone()),
+                UObject::Namespace(ns) => fields.namespace = Some(ns.clone()),
+                UObject::Lifetime(l) => fields.lifetime = Some(*l),
+                _ => {}
+            }
+        }
+        fields
+    }
+}
+
+/// 实体层级树节点（见 [`KeyFrame::tree`]）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntityNode {
+    pub id: u64,
+    pub children: Vec<EntityNode>,
+}
+
 /// 关键帧 — 某一时刻的场景快照
+///
+/// `ids` 的键是会话作用域的实体 ID（见 [`scoped_entity_id`]），
+/// 不同客户端使用相同的本地 ID 也不会互相覆盖。
+///
+/// 实体可通过 `Inpto.parent` 挂到同帧内的其他实体下，变换相对父实体解释；
+/// 父实体不存在或成环时按根实体处理。
+///
+/// 带生存期（`Inpto.lifetime`）的实体经 [`KeyFrame::successor`] 延续到后续关键帧，
+/// 到期后自动消失；其余实体只存在于所属关键帧。
+///
+/// # 各指令的对象序列约定
+///
+/// Spawn / Update / Destroy 使用相同的分组规则：对象序列按 `Id` 切分，
+/// 每个 `Id` 连同其后直到下一个 `Id` 之前的对象构成一个实体分组。
+///
+/// | 指令 | 分组内容 | 说明 |
+/// |------|----------|------|
+/// | Spawn | `Id, Mesh, [Transform], [MaterialId], [Tag…], [ParentId], [FrameId], [Namespace], [Lifetime]` | 缺少 `Mesh` 的分组被忽略；ID 已存在时原位替换；整个 Unit 不含 `Id` 时按旧格式生成一个自动 ID 的实体 |
+/// | Update | `Id` 加任意字段子集 | 只修改出现的字段：`Mesh` 替换网格，`Tag` 整体替换标签列表（空文本的 `Tag` 被丢弃，只含空文本时即清空），`ParentId` 等于自身 ID 时解除父子关系；`PoseBatch` 可出现在任意位置，不属于分组 |
+/// | Destroy | `Id` | 每个 `Id` 销毁一个实体（连同子孙实体），其余对象忽略 |
+/// | Clear | `[Namespace…]` | 见 [`KeyFrame::clear_namespace`] / [`KeyFrame::clear_session`] |
+///
+/// Spawn 中首个 `Id` 之前的对象自成一组并分配自动 ID；Update 中这些对象不属于任何实体，被忽略。
+/// 同一分组内字段重复时以最后一个为准。目标实体不在本帧中的 Update / Destroy 分组被忽略。
 pub struct KeyFrame {
     pub timestamp: u64,
     pub ids: HashMap<u64, usize>,
@@ -27,6 +112,34 @@ impl KeyFrame {
         Self { timestamp, ids: HashMap::new(), packs: Vec::new() }
     }
 
+    /// 下一关键帧的初始状态 — 延续本帧中未到期的带生存期实体
+    ///
+    /// `timestamp` 为下一帧的时间戳；到期实体的子孙实体一并移除（与销毁一致）。
+    pub fn successor(&self, timestamp: u64) -> KeyFrame {
+        let mut next = KeyFrame::new(timestamp);
+        let expired: HashSet<u64> = self.iter_entities()
+            .filter(|(_, e)| e.lifetime.is_some_and(|l| l.advance(timestamp).is_none()))
+            .map(|(id, _)| id)
+            .collect();
+
+        // 按原有顺序延续，保持渲染与存储顺序稳定
+        let parents = self.parent_map();
+        let mut carried: Vec<(u64, usize)> = self.ids.iter()
+            .map(|(&id, &idx)| (id, idx))
+            .filter(|&(id, idx)| {
+                self.packs[idx].lifetime.is_some()
+                    && !std::iter::successors(Some(id), |id| parents.get(id).copied()).any(|id| expired.contains(&id))
+            })
+            .collect();
+        carried.sort_unstable_by_key(|&(_, idx)| idx);
+        for (id, idx) in carried {
+            let mut inpto = self.packs[idx].clone();
+            inpto.lifetime = inpto.lifetime.and_then(|l| l.advance(timestamp));
+            next.insert_inpto(id, inpto);
+        }
+        next
+    }
+
     pub fn update(&mut self, unit: &Unit) {
         let command = parse_command(unit).unwrap_or(CommandType::Spawn);
         self.match_command(&command, unit);
@@ -39,59 +152,43 @@ impl KeyFrame {
             CommandType::Update => self.react_update(unit),
             CommandType::Destroy => self.react_destroy(unit),
             CommandType::Frameend => {}
+            CommandType::Clear => self.react_clear(unit),
         }
     }
 
-    /// 将 Unit 对象列表按 Id 边界分组，每组生成一个实体
+    /// 按 Id 分组生成实体，每组一个实体（分组规则见 [`split_id_groups`]）
     fn react_spawn(&mut self, unit: &Unit) {
-        let has_ids = unit.objects.iter().any(|obj| {
-            obj.u_object.as_ref().is_some_and(|u| matches!(u, UObject::Id(_)))
-        });
-
+        let has_ids = unit.objects.iter().any(|obj| matches!(obj.u_object, Some(UObject::Id(_))));
         if !has_ids {
             return self.spawn_legacy(unit);
         }
 
-        let mut groups: Vec<Vec<usize>> = Vec::new();
-        for (i, obj) in unit.objects.iter().enumerate() {
-            let is_id = obj.u_object.as_ref().is_some_and(|u| matches!(u, UObject::Id(_)));
-            if is_id && !groups.is_empty() {
-                groups.push(vec![i]);
-            } else if groups.is_empty() {
-                groups.push(vec![i]);
-            } else {
-                groups.last_mut().unwrap().push(i);
-            }
-        }
-
-        for indices in &groups {
-            let mut entity_id: Option<u64> = None;
-            let mut mesh: Option<ExMesh> = None;
-            let mut transform: Option<ExTransform> = None;
-            let mut material: Option<String> = None;
-            let mut tag_list: Vec<Tag> = Vec::new();
-
-            for &idx in indices {
-                if let Some(u_object) = &unit.objects[idx].u_object {
-                    match u_object {
-                        UObject::Id(id) => entity_id = Some(*id),
-                        UObject::Mesh(m) => mesh = Some(m.clone()),
-                        UObject::Transform(t) => transform = Some(*t),
-                        UObject::MaterialId(m) => material = Some(m.clone()),
-                        UObject::Tag(t) => tag_list.push(t.clone()),
-                        _ => {}
-                    }
-                }
-            }
-
-            let id = entity_id.unwrap_or_else(|| generate_entity_id(self.packs.len()));
-            if let Some(m) = mesh {
-                let bevy_t = transform.map(|t| e2i_transform(t)).unwrap_or_default();
-                let mat = material.unwrap_or_default();
-                self.ids.insert(id, self.packs.len());
-                let inpto = Inpto { mesh: m, material: mat, transform: bevy_t, tags: tag_list };
-                self.packs.push(inpto);
-            }
+        let session = parse_session(unit);
+        let spawned_at = self.unit_timestamp(unit);
+        for group in split_id_groups(&unit.objects) {
+            let fields = GroupFields::parse(group);
+            let id = fields.id
+                .map(|id| scoped_entity_id(session, id))
+                .unwrap_or_else(|| generate_entity_id(self.packs.len()));
+            let Some(mesh) = fields.mesh else {
+                log::debug!("Spawn 中实体 {} 的对象组缺少 Mesh，已忽略", id);
+                continue;
+            };
+            let parent = fields.parent
+                .map(|p| scoped_entity_id(session, p))
+                .filter(|&p| p != id);
+            let inpto = Inpto {
+                mesh,
+                material: fields.material.unwrap_or_default(),
+                transform: fields.transform.map(e2i_transform).unwrap_or_default(),
+                tags: fields.tags,
+                session: session.to_string(),
+                parent,
+                frame_id: fields.frame_id.unwrap_or_default(),
+                namespace: fields.namespace.unwrap_or_default(),
+                lifetime: fields.lifetime.map(|l| InptoLifetime::new(l, spawned_at)),
+            };
+            self.insert_inpto(id, inpto);
         }
     }
 
@@ -125,60 +222,203 @@ impl KeyFrame {
             let bevy_transform = transform.map(|t| e2i_transform(t)).unwrap_or_default();
 
             self.ids.insert(entity_id, self.packs.len());
-            let inpto = Inpto { mesh: mesh_data, material: material_id, transform: bevy_transform, tags: tag_list };
+            let inpto = Inpto {
+                mesh: mesh_data, material: material_id, transform: bevy_transform, tags: tag_list,
+                session: parse_session(unit).to_string(), parent: None,
+                frame_id: String::new(), namespace: extract_namespace(unit).unwrap_or_default(),
+                lifetime: extract_lifetime(unit).map(|l| InptoLifetime::new(l, self.unit_timestamp(unit))),
+            };
             self.packs.push(inpto);
         }
     }
 
+    /// 按 Id 分组更新实体：每组只修改出现的字段，`Mesh` 替换网格，`Tag` 整体替换标签
     fn react_update(&mut self, unit: &Unit) {
-        let mut id: Option<u64> = None;
-        let mut transform: Option<ExTransform> = None;
-        let mut material_id: Option<String> = None;
-        let mut tag_list: Vec<Tag> = Vec::new();
-
+        let session = parse_session(unit);
         for obj in &unit.objects {
-            if let Some(u_object) = &obj.u_object {
-                match u_object {
-                    UObject::Id(obj_id) => id = Some(*obj_id),
-                    UObject::Transform(transform_data) => transform = Some(*transform_data),
-                    UObject::MaterialId(mat_id) => material_id = Some(mat_id.clone()),
-                    UObject::Tag(tag_data) => tag_list.push(tag_data.clone()),
-                    _ => {}
-                }
+            if let Some(UObject::PoseBatch(batch)) = &obj.u_object {
+                self.apply_pose_batch(session, batch);
             }
         }
 
-        if let Some(entity_id) = id {
-            if let Some(idx) = self.ids.get(&entity_id) {
-                if let Some(transform_data) = transform {
-                    self.packs[*idx].transform = e2i_transform(transform_data);
-                }
-                if let Some(mat_id) = material_id {
-                    self.packs[*idx].material = mat_id;
-                }
-                if !tag_list.is_empty() {
-                    self.packs[*idx].tags = tag_list;
+        let updated_at = self.unit_timestamp(unit);
+        for group in split_id_groups(&unit.objects) {
+            let fields = GroupFields::parse(group);
+            let Some(entity_id) = fields.id.map(|id| scoped_entity_id(session, id)) else {
+                if group.iter().any(|obj| !matches!(obj.u_object, Some(UObject::PoseBatch(_)) | None)) {
+                    log::warn!("Update 中首个 Id 之前的 {} 个对象不属于任何实体，已忽略", group.len());
                 }
+                continue;
+            };
+            let Some(&idx) = self.ids.get(&entity_id) else {
+                log::debug!("Update 的目标实体 {} 不在当前帧中，已忽略", entity_id);
+                continue;
+            };
+            let inpto = &mut self.packs[idx];
+            if let Some(mesh) = fields.mesh {
+                inpto.mesh = mesh;
+            }
+            // parent_id 等于自身 ID 表示解除父子关系
+            if let Some(parent_id) = fields.parent.map(|p| scoped_entity_id(session, p)) {
+                inpto.parent = (parent_id != entity_id).then_some(parent_id);
+            }
+            if let Some(frame_id) = fields.frame_id {
+                inpto.frame_id = frame_id;
+            }
+            if let Some(namespace) = fields.namespace {
+                inpto.namespace = namespace;
+            }
+            // 更新生存期会从当前时间重新计时
+            if let Some(lifetime) = fields.lifetime {
+                inpto.lifetime = Some(InptoLifetime::new(lifetime, updated_at));
+            }
+            if let Some(transform) = fields.transform {
+                inpto.transform = e2i_transform(transform);
+            }
+            if let Some(material) = fields.material {
+                inpto.material = material;
+            }
+            if !fields.tags.is_empty() {
+                inpto.tags = fields.tags.into_iter().filter(|tag| !tag.text.is_empty()).collect();
             }
         }
     }
 
+    /// 销毁 Unit 中每个 Id 对应的实体（连同子孙实体），其余对象忽略
     fn react_destroy(&mut self, unit: &Unit) {
-        if let Some(entity_id) = extract_id(unit) {
-            if let Some(idx) = self.ids.get(&entity_id) {
-                let idx = *idx;
-                self.packs.remove(idx);
-                self.rebuild_index_after_remove(idx);
+        let session = parse_session(unit);
+        let targets: Vec<u64> = unit.objects.iter()
+            .filter_map(|obj| match obj.u_object {
+                Some(UObject::Id(id)) => Some(scoped_entity_id(session, id)),
+                _ => None,
+            })
+            .collect();
+        self.remove_entities(targets);
+    }
+
+    /// 批量位姿更新：按 ID 顺序一次性写入变换（及可选材质），本帧不存在的 ID 忽略
+    fn apply_pose_batch(&mut self, session: &str, batch: &PoseBatch) {
+        if batch.transforms.len() != batch.ids.len() {
+            log::warn!("批量位姿更新的 ID 数量（{}）与变换数量（{}）不一致，已忽略", batch.ids.len(), batch.transforms.len());
+            return;
+        }
+        let materials = if batch.materials.is_empty() || batch.materials.len() == batch.ids.len() {
+            batch.materials.as_slice()
+        } else {
+            log::warn!("批量位姿更新的材质数量（{}）与 ID 数量（{}）不一致，忽略材质", batch.materials.len(), batch.ids.len());
+            &[]
+        };
+
+        let mut missing = 0;
+        for (i, (&id, &transform)) in batch.ids.iter().zip(&batch.transforms).enumerate() {
+            let Some(&idx) = self.ids.get(&scoped_entity_id(session, id)) else {
+                missing += 1;
+                continue;
+            };
+            let inpto = &mut self.packs[idx];
+            inpto.transform = e2i_transform(transform);
+            if let Some(material) = materials.get(i).filter(|m| !m.is_empty()) {
+                inpto.material = material.clone();
+            }
+        }
+        if missing > 0 {
+            log::debug!("批量位姿更新：{} / {} 个实体不在当前帧中", missing, batch.ids.len());
+        }
+    }
+
+    /// 插入实体；ID 已存在时原位替换（如重新生成延续而来的实体）
+    fn insert_inpto(&mut self, id: u64, inpto: Inpto) {
+        match self.ids.get(&id) {
+            Some(&idx) => self.packs[idx] = inpto,
+            None => {
+                self.ids.insert(id, self.packs.len());
+                self.packs.push(inpto);
             }
         }
     }
 
-    fn rebuild_index_after_remove(&mut self, removed_idx: usize) {
-        for (_, idx) in self.ids.iter_mut() {
-            if *idx > removed_idx {
-                *idx -= 1;
+    /// Unit 的时间戳，缺失时取本帧时间戳
+    fn unit_timestamp(&self, unit: &Unit) -> u64 {
+        unit.stamp.as_ref().map(|s| s.timestamp).filter(|&t| t > 0).unwrap_or(self.timestamp)
+    }
+
+    /// 批量清除本会话的实体：携带 namespace 对象时只清除这些命名空间，否则清除整个会话
+    fn react_clear(&mut self, unit: &Unit) {
+        let session = parse_session(unit);
+        let namespaces: Vec<&str> = unit.objects.iter()
+            .filter_map(|obj| match &obj.u_object {
+                Some(UObject::Namespace(ns)) => Some(ns.as_str()),
+                _ => None,
+            })
+            .collect();
+        if namespaces.is_empty() {
+            self.clear_session(session);
+        } else {
+            for namespace in namespaces {
+                self.clear_namespace(session, namespace);
+            }
+        }
+    }
+
+    /// 清除指定会话中某命名空间的实体（连同其子孙实体），返回移除的数量
+    pub fn clear_namespace(&mut self, session: &str, namespace: &str) -> usize {
+        let targets: Vec<u64> = self.iter_entities()
+            .filter(|(_, e)| e.session == session && e.namespace == namespace)
+            .map(|(id, _)| id)
+            .collect();
+        self.remove_entities(targets)
+    }
+
+    /// 清除指定会话的全部实体（连同挂在其下的其他会话实体），返回移除的数量
+    pub fn clear_session(&mut self, session: &str) -> usize {
+        let targets: Vec<u64> = self.iter_entities()
+            .filter(|(_, e)| e.session == session)
+            .map(|(id, _)| id)
+            .collect();
+        self.remove_entities(targets)
+    }
+
+    /// 移除一组实体及其所有子孙实体，返回移除的数量；不在本帧中的 ID 忽略
+    ///
+    /// 父子关系表只构建一次，`packs` 按原有顺序单次过滤，`ids` 只重建一次。
+    pub fn remove_entities(&mut self, entity_ids: impl IntoIterator<Item = u64>) -> usize {
+        let children = self.children_map();
+        let mut removed = vec![false; self.packs.len()];
+        let mut count = 0;
+        let mut pending: Vec<u64> = entity_ids.into_iter().collect();
+        while let Some(id) = pending.pop() {
+            let Some(&idx) = self.ids.get(&id) else { continue };
+            if !removed[idx] {
+                removed[idx] = true;
+                count += 1;
+                pending.extend(children.get(&id).into_iter().flatten().copied());
             }
         }
+        if count == 0 {
+            return 0;
+        }
+
+        let mut remap = Vec::with_capacity(removed.len());
+        let mut next = 0;
+        for &gone in &removed {
+            remap.push((!gone).then_some(next));
+            next += usize::from(!gone);
+        }
+        let mut flags = removed.iter();
+        self.packs.retain(|_| flags.next().is_some_and(|&gone| !gone));
+        self.ids.retain(|_, idx| match remap[*idx] {
+            Some(new_idx) => {
+                *idx = new_idx;
+                true
+            }
+            None => false,
+        });
+        count
+    }
+
+    /// 移除实体及其所有子孙实体，返回移除的数量
+    pub fn remove_entity(&mut self, entity_id: u64) -> usize {
+        self.remove_entities([entity_id])
     }
 
     // ==================== 外部构造接口 ====================
@@ -202,6 +442,141 @@ impl KeyFrame {
         self.ids.get(&entity_id).map(|&idx| &self.packs[idx])
     }
 
+    /// 本帧出现的命名空间及其实体数（按名称排序，默认命名空间为空字符串）
+    pub fn namespaces(&self) -> Vec<(String, usize)> {
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+        for inpto in &self.packs {
+            *counts.entry(inpto.namespace.as_str()).or_default() += 1;
+        }
+        let mut result: Vec<(String, usize)> = counts.into_iter()
+            .map(|(ns, count)| (ns.to_string(), count))
+            .collect();
+        result.sort();
+        result
+    }
+
+    // ==================== 层级接口 ====================
+
+    /// 父实体在本帧中存在时的父实体 ID（不检查成环）
+    fn declared_parent(&self, entity_id: u64) -> Option<u64> {
+        self.get_entity(entity_id)?.parent.filter(|parent| self.ids.contains_key(parent))
+    }
+
+    /// 实体的有效父实体 — 父实体须在本帧中存在，且父链不成环
+    ///
+    /// 逐个实体查询时使用；需要全部实体的父子关系时用 [`parent_map`](Self::parent_map)。
+    pub fn parent_of(&self, entity_id: u64) -> Option<u64> {
+        let parent = self.declared_parent(entity_id)?;
+        // Brent 判环：沿父链前进，不分配内存
+        let (mut tortoise, mut hare) = (entity_id, parent);
+        let (mut power, mut steps) = (1u32, 1u32);
+        loop {
+            if hare == tortoise {
+                return None;
+            }
+            if steps == power {
+                tortoise = hare;
+                power = power.saturating_mul(2);
+                steps = 0;
+            }
+            hare = match self.declared_parent(hare) {
+                Some(next) => next,
+                None => return Some(parent),
+            };
+            steps += 1;
+        }
+    }
+
+    /// 所有实体的有效父实体（子 → 父），一次遍历父链得出，与 [`parent_of`](Self::parent_of) 一致
+    pub fn parent_map(&self) -> HashMap<u64, u64> {
+        // 父链是否终止：`None` 表示正在本次遍历的链上，再次遇到即成环
+        let mut terminates: HashMap<u64, Option<bool>> = HashMap::with_capacity(self.ids.len());
+        let mut chain = Vec::new();
+        for &start in self.ids.keys() {
+            let mut current = Some(start);
+            let result = loop {
+                let Some(id) = current else { break true };
+                match terminates.get(&id) {
+                    Some(&Some(known)) => break known,
+                    Some(None) => break false,
+                    None => {
+                        terminates.insert(id, None);
+                        chain.push(id);
+                        current = self.declared_parent(id);
+                    }
+                }
+            };
+            for id in chain.drain(..) {
+                terminates.insert(id, Some(result));
+            }
+        }
+        self.ids.keys()
+            .filter(|id| terminates.get(id) == Some(&Some(true)))
+            .filter_map(|&id| Some((id, self.declared_parent(id)?)))
+            .collect()
+    }
+
+    /// 直接子实体（按 ID 排序）
+    pub fn children_of(&self, entity_id: u64) -> Vec<u64> {
+        let mut children: Vec<u64> = self.parent_map().into_iter()
+            .filter(|&(_, parent)| parent == entity_id)
+            .map(|(id, _)| id)
+            .collect();
+        children.sort_unstable();
+        children
+    }
+
+    /// 所有子孙实体（广度优先）
+    pub fn descendants(&self, entity_id: u64) -> Vec<u64> {
+        let children = self.children_map();
+        let mut result = Vec::new();
+        let mut queue = std::collections::VecDeque::from([entity_id]);
+        while let Some(id) = queue.pop_front() {
+            for &child in children.get(&id).map(Vec::as_slice).unwrap_or_default() {
+                result.push(child);
+                queue.push_back(child);
+            }
+        }
+        result
+    }
+
+    /// 根实体（无有效父实体，按 ID 排序）
+    pub fn roots(&self) -> Vec<u64> {
+        let parents = self.parent_map();
+        let mut roots: Vec<u64> = self.ids.keys()
+            .copied()
+            .filter(|id| !parents.contains_key(id))
+            .collect();
+        roots.sort_unstable();
+        roots
+    }
+
+    /// 完整层级树（供大纲视图使用，同级按 ID 排序）
+    pub fn tree(&self) -> Vec<EntityNode> {
+        fn build(id: u64, children: &HashMap<u64, Vec<u64>>) -> EntityNode {
+            EntityNode {
+                id,
+                children: children.get(&id)
+                    .map(|ids| ids.iter().map(|&child| build(child, children)).collect())
+                    .unwrap_or_default(),
+            }
+        }
+        let children = self.children_map();
+        self.roots().into_iter().map(|id| build(id, &children)).collect()
+    }
+
+    /// 父实体 → 子实体列表（仅有效父子关系，子实体按 ID 排序）
+    pub fn children_map(&self) -> HashMap<u64, Vec<u64>> {
+        let mut map: HashMap<u64, Vec<u64>> = HashMap::new();
+        for (id, parent) in self.parent_map() {
+            map.entry(parent).or_default().push(id);
+        }
+        for children in map.values_mut() {
+            children.sort_unstable();
+        }
+        map
+    }
+
     /// 更新指定实体的 Tag 文本（替换第一个匹配的 tag，或追加新 tag）
     pub fn update_entity_tag(&mut self, entity_id: u64, text: String) {
         if let Some(&idx) = self.ids.get(&entity_id) {
@@ -251,6 +626,7 @@ mod tests {
                 x: position[0], y: position[1], z: position[2],
                 rx: 0.0, ry: 0.0, rz: 0.0,
                 sx: scale[0], sy: scale[1], sz: scale[2],
+                rotation: None,
             })),
         });
         unit.objects.push(expto::rdmp::ExObject { u_object: Some(UObject::MaterialId(material)) });
@@ -282,7 +658,7 @@ mod tests {
         let mut update_unit = Unit { stamp: None, command: None, objects: Vec::new() };
         update_unit.objects.push(expto::rdmp::ExObject { u_object: Some(UObject::Id(1)) });
         update_unit.objects.push(expto::rdmp::ExObject {
-            u_object: Some(UObject::Transform(ExTransform { x: 10.0, y: 20.0, z: 30.0, rx: 0.0, ry: 0.0, rz: 0.0, sx: 1.0, sy: 1.0, sz: 1.0 })),
+            u_object: Some(UObject::Transform(ExTransform { x: 10.0, y: 20.0, z: 30.0, rx: 0.0, ry: 0.0, rz: 0.0, sx: 1.0, sy: 1.0, sz: 1.0, rotation: None })),
         });
         update_unit.objects.push(expto::rdmp::ExObject {
             u_object: Some(UObject::Tag(Tag { text: "新标签".to_string(), offset: None, style: None })),
@@ -309,4 +685,361 @@ mod tests {
         assert_eq!(inpto.material, "");
         assert!(inpto.tags.is_empty());
     }
+
+    #[test]
+    fn test_client_entity_handle() {
+        use redra_client::{ShapeBuilder, IntoTag};
+
+        let mut keyframe = KeyFrame::new(0);
+        let (ball, units) = ShapeBuilder::sphere(1.0).material("red").build();
+        let (cube, more) = ShapeBuilder::cone(0.5, 1.0).id(900).build();
+        for unit in units.iter().chain(&more) {
+            keyframe.update(unit);
+        }
+        assert_eq!(keyframe.entity_count(), 2);
+        assert_eq!(cube.id(), Some(900));
+        assert_ne!(ball.id(), Some(900));
+
+        // 客户端 stamp 带有会话，服务端以会话作用域 ID 存储
+        let session = expto::rdmp::auto::Stamper::session_id();
+        let ball_id = scoped_entity_id(session, ball.id().unwrap());
+        let cone_id = scoped_entity_id(session, 900);
+        assert_eq!(keyframe.get_entity(ball_id).unwrap().session, session);
+
+        let moved = ExTransform { x: 4.0, y: 5.0, z: 6.0, rx: 0.0, ry: 0.0, rz: 0.0, sx: 2.0, sy: 2.0, sz: 2.0, rotation: None };
+        for unit in ball.transform_units(moved)
+            .into_iter()
+            .chain(ball.material_units("green"))
+            .chain(ball.tags_units(vec!["a".into_tag(), "b".into_tag()]))
+        {
+            keyframe.update(&unit);
+        }
+        let inpto = keyframe.get_entity(ball_id).unwrap();
+        assert!((inpto.transform.tx - 4.0).abs() < f32::EPSILON);
+        assert!((inpto.transform.sz - 2.0).abs() < f32::EPSILON);
+        assert_eq!(inpto.material, "green");
+        assert_eq!(inpto.tags.iter().map(|t| t.text.as_str()).collect::<Vec<_>>(), ["a", "b"]);
+        assert_eq!(keyframe.get_entity(cone_id).unwrap().material, "");
+
+        // 空列表清除标签，而不是被当作未修改
+        for unit in ball.tags_units(Vec::<Tag>::new()) {
+            keyframe.update(&unit);
+        }
+        assert!(keyframe.get_entity(ball_id).unwrap().tags.is_empty());
+        for unit in ball.tags_units(vec!["c"]).into_iter().chain(ball.clear_tags_units()) {
+            keyframe.update(&unit);
+        }
+        assert!(keyframe.get_entity(ball_id).unwrap().tags.is_empty());
+
+        for unit in ball.destroy_units() {
+            keyframe.update(&unit);
+        }
+        assert_eq!(keyframe.entity_count(), 1);
+        assert!(keyframe.get_entity(ball_id).is_none());
+        assert!(keyframe.get_entity(cone_id).is_some());
+    }
+
+    #[test]
+    fn test_client_grouped_handle_destroy() {
+        use redra_client::ShapeBuilder;
+
+        let mut keyframe = KeyFrame::new(0);
+        let (cloud, units) = ShapeBuilder::point_cloud_grouped()
+            .group(vec![[0.0, 0.0, 0.0], [1.0, 0.0, 0.0]], "red")
+            .group(vec![[0.0, 1.0, 0.0]], "blue")
+            .build();
+        for unit in &units {
+            keyframe.update(unit);
+        }
+        assert_eq!(cloud.ids().len(), 3);
+        assert_eq!(keyframe.entity_count(), 3);
+
+        for unit in cloud.destroy_units() {
+            keyframe.update(&unit);
+        }
+        assert_eq!(keyframe.entity_count(), 0);
+        assert!(keyframe.ids.is_empty());
+    }
+
+    #[test]
+    fn test_two_sessions_same_ids() {
+        fn from_session(mut unit: Unit, session: &str) -> Unit {
+            unit.stamp = Some(expto::rdmp::ExStamp { session_id: session.to_string(), ..Default::default() });
+            unit
+        }
+
+        let mut keyframe = KeyFrame::new(0);
+        for (session, material) in [("lidar", "red"), ("camera", "blue")] {
+            for id in 1..=2 {
+                let unit = create_test_unit_with_tag(id, [0.0; 3], [1.0; 3], material.to_string(), session.to_string());
+                keyframe.update(&from_session(unit, session));
+            }
+        }
+        assert_eq!(keyframe.entity_count(), 4);
+
+        // 只更新 lidar 会话的实体 1
+        let mut update = Unit { stamp: None, command: None, objects: Vec::new() };
+        update.set_update().unwrap();
+        update.objects.push(expto::rdmp::ExObject { u_object: Some(UObject::Id(1)) });
+        update.objects.push(expto::rdmp::ExObject { u_object: Some(UObject::MaterialId("green".to_string())) });
+        keyframe.update(&from_session(update, "lidar"));
+
+        let lidar_1 = keyframe.get_entity(scoped_entity_id("lidar", 1)).unwrap();
+        let camera_1 = keyframe.get_entity(scoped_entity_id("camera", 1)).unwrap();
+        assert_eq!(lidar_1.material, "green");
+        assert_eq!(lidar_1.session, "lidar");
+        assert_eq!(camera_1.material, "blue");
+        assert_eq!(camera_1.session, "camera");
+
+        // 销毁同样只作用于本会话
+        let mut destroy = Unit { stamp: None, command: None, objects: Vec::new() };
+        destroy.set_destroy().unwrap();
+        destroy.objects.push(expto::rdmp::ExObject { u_object: Some(UObject::Id(2)) });
+        keyframe.update(&from_session(destroy, "camera"));
+        assert_eq!(keyframe.entity_count(), 3);
+        assert!(keyframe.get_entity(scoped_entity_id("lidar", 2)).is_some());
+        assert!(keyframe.get_entity(scoped_entity_id("camera", 2)).is_none());
+    }
+
+    #[test]
+    fn test_hierarchy_and_cascading_destroy() {
+        use redra_client::ShapeBuilder;
+
+        let mut keyframe = KeyFrame::new(0);
+        let (vehicle, mut units) = ShapeBuilder::cube(vec![
+            (-2.0, -1.0, 0.0), (2.0, -1.0, 0.0), (2.0, 1.0, 0.0), (-2.0, 1.0, 0.0),
+            (-2.0, -1.0, 1.5), (2.0, -1.0, 1.5), (2.0, 1.0, 1.5), (-2.0, 1.0, 1.5),
+        ]).id(100).build();
+        let (lidar, more) = ShapeBuilder::cylinder(0.1, 0.2).id(101).parent_of(&vehicle).at(0.0, 0.0, 1.6).build();
+        units.extend(more);
+        units.extend(ShapeBuilder::sphere(0.05).id(102).parent(101).build().1);
+        units.extend(ShapeBuilder::sphere(1.0).id(200).build().1);
+        for unit in &units {
+            keyframe.update(unit);
+        }
+
+        let session = expto::rdmp::auto::Stamper::session_id();
+        let id = |local| scoped_entity_id(session, local);
+        assert_eq!(keyframe.parent_of(id(101)), Some(id(100)));
+        assert_eq!(keyframe.children_of(id(100)), vec![id(101)]);
+        assert_eq!(keyframe.descendants(id(100)), vec![id(101), id(102)]);
+        let mut roots = vec![id(100), id(200)];
+        roots.sort_unstable();
+        assert_eq!(keyframe.roots(), roots);
+        let vehicle_node = keyframe.tree().into_iter().find(|n| n.id == id(100)).unwrap();
+        assert_eq!(vehicle_node.children[0].children[0].id, id(102));
+        // 变换保持相对父实体
+        assert!((keyframe.get_entity(id(101)).unwrap().transform.tz - 1.6).abs() < f32::EPSILON);
+
+        // 解除父子关系后成为根实体
+        for unit in lidar.parent_units(None) {
+            keyframe.update(&unit);
+        }
+        assert_eq!(keyframe.parent_of(id(101)), None);
+        for unit in lidar.parent_units(Some(100)) {
+            keyframe.update(&unit);
+        }
+
+        // 销毁父实体会一并销毁子孙实体
+        for unit in vehicle.destroy_units() {
+            keyframe.update(&unit);
+        }
+        assert_eq!(keyframe.entity_count(), 1);
+        assert!(keyframe.get_entity(id(200)).is_some());
+        assert!(keyframe.ids.values().all(|&idx| idx < keyframe.packs.len()));
+    }
+
+    #[test]
+    fn test_parent_cycle_is_treated_as_roots() {
+        let mut keyframe = KeyFrame::new(0);
+        // 5 的父链进入 1 ↔ 2 的环，6 以自身为父，8 → 7 → 3 正常终止
+        for (id, parent) in [(1, Some(2)), (2, Some(1)), (3, Some(99)), (4, Some(3)), (5, Some(1)), (6, Some(6)), (7, Some(3)), (8, Some(7))] {
+            keyframe.insert_entity(id, ExMesh::from(Point { x: 0.0, y: 0.0, z: 0.0 }), ExTransform::identity());
+            keyframe.packs.last_mut().unwrap().parent = parent;
+        }
+        assert_eq!(keyframe.roots(), vec![1, 2, 3, 5, 6]);
+        assert_eq!(keyframe.parent_of(4), Some(3));
+        assert_eq!(keyframe.parent_of(8), Some(7));
+        let parents = keyframe.parent_map();
+        for id in 1..=8 {
+            assert_eq!(parents.get(&id).copied(), keyframe.parent_of(id), "实体 {}", id);
+        }
+        assert_eq!(keyframe.remove_entity(1), 1);
+        assert_eq!(keyframe.parent_of(2), None);
+    }
+
+    #[test]
+    fn test_remove_entities_keeps_order_and_index() {
+        let mut keyframe = KeyFrame::new(0);
+        for (id, parent) in [(1, None), (2, Some(1)), (3, None), (4, Some(2)), (5, None), (6, Some(5))] {
+            keyframe.insert_entity(id, ExMesh::from(Point { x: 0.0, y: 0.0, z: 0.0 }), ExTransform::identity());
+            keyframe.packs.last_mut().unwrap().parent = parent;
+        }
+        // 同时列出父实体与其子孙实体、以及不存在的 ID，每个实体只计一次
+        assert_eq!(keyframe.remove_entities([2, 4, 6, 99]), 3);
+        assert_eq!(keyframe.remove_entity(2), 0);
+        let order: Vec<u64> = {
+            let mut ids: Vec<(usize, u64)> = keyframe.ids.iter().map(|(&id, &idx)| (idx, id)).collect();
+            ids.sort_unstable();
+            ids.into_iter().map(|(_, id)| id).collect()
+        };
+        assert_eq!(order, vec![1, 3, 5]);
+        assert_eq!(keyframe.packs.len(), 3);
+        assert_eq!(keyframe.remove_entity(1), 1);
+        assert_eq!(keyframe.ids[&5], 1);
+    }
+
+    #[test]
+    fn test_namespaces_and_clear() {
+        use redra_client::{ShapeBuilder, clear_unit};
+
+        fn from_session(mut unit: Unit, session: &str) -> Unit {
+            unit.stamp = Some(expto::rdmp::ExStamp { session_id: session.to_string(), ..Default::default() });
+            unit
+        }
+
+        let mut keyframe = KeyFrame::new(0);
+        for session in ["lidar", "camera"] {
+            let mut units = ShapeBuilder::sphere(1.0).id(1).namespace("detections").build().1;
+            units.extend(ShapeBuilder::sphere(1.0).id(2).namespace("detections").build().1);
+            units.extend(ShapeBuilder::sphere(0.1).id(3).parent(1).build().1);
+            units.extend(ShapeBuilder::sphere(1.0).id(4).namespace("map").build().1);
+            units.extend(ShapeBuilder::sphere(1.0).id(5).build().1);
+            for unit in units {
+                keyframe.update(&from_session(unit, session));
+            }
+        }
+        assert_eq!(keyframe.namespaces(), vec![
+            (String::new(), 4), ("detections".to_string(), 4), ("map".to_string(), 2),
+        ]);
+
+        // 更新可改变命名空间
+        let mut update = Unit { stamp: None, command: None, objects: Vec::new() };
+        update.set_update().unwrap();
+        update.objects.push(expto::rdmp::ExObject { u_object: Some(UObject::Id(5)) });
+        update.objects.push(expto::rdmp::ExObject::namespace("map"));
+        keyframe.update(&from_session(update, "lidar"));
+        assert_eq!(keyframe.get_entity(scoped_entity_id("lidar", 5)).unwrap().namespace, "map");
+
+        // 按命名空间清除只作用于本会话，子实体随父实体一并清除
+        keyframe.update(&from_session(clear_unit(&["detections"]), "lidar"));
+        let lidar = |id| keyframe.get_entity(scoped_entity_id("lidar", id)).is_some();
+        assert_eq!([1, 2, 3, 4, 5].map(lidar), [false, false, false, true, true]);
+        assert_eq!(keyframe.entity_count(), 7);
+
+        // 不带命名空间清除整个会话
+        keyframe.update(&from_session(clear_unit(&[]), "camera"));
+        assert_eq!(keyframe.entity_count(), 2);
+        assert!(keyframe.iter_entities().all(|(_, e)| e.session == "lidar"));
+        assert!(keyframe.ids.values().all(|&idx| idx < keyframe.packs.len()));
+        // 剩余实体保持原有顺序，索引与实体一一对应
+        let mut indices: Vec<usize> = keyframe.ids.values().copied().collect();
+        indices.sort_unstable();
+        assert_eq!(indices, vec![0, 1]);
+        assert_eq!(keyframe.packs[keyframe.ids[&scoped_entity_id("lidar", 4)]].namespace, "map");
+    }
+
+    #[test]
+    fn test_pose_batch_updates_in_one_pass() {
+        use redra_client::{ShapeBuilder, pose_batch_units};
+
+        let mut keyframe = KeyFrame::new(0);
+        for id in 0..1000 {
+            for unit in ShapeBuilder::sphere(0.1).id(id).material("red").build().1 {
+                keyframe.update(&unit);
+            }
+        }
+        // 其他会话的同 ID 实体不受影响
+        let mut other = create_test_unit_with_tag(3, [0.0; 3], [1.0; 3], "blue".to_string(), String::new());
+        other.stamp = Some(expto::rdmp::ExStamp { session_id: "other".to_string(), ..Default::default() });
+        keyframe.update(&other);
+
+        let ids: Vec<u64> = (0..1000).chain([5000]).collect();
+        let transforms: Vec<ExTransform> = ids.iter()
+            .map(|&i| ExTransform { x: i as f32, ..ExTransform::identity() })
+            .collect();
+        let materials: Vec<String> = ids.iter().map(|i| if i % 2 == 0 { "green".to_string() } else { String::new() }).collect();
+        for unit in pose_batch_units(&ids, &transforms, &materials).unwrap() {
+            keyframe.update(&unit);
+        }
+
+        let session = expto::rdmp::auto::Stamper::session_id();
+        for id in [0, 3, 999] {
+            let inpto = keyframe.get_entity(scoped_entity_id(session, id)).unwrap();
+            assert!((inpto.transform.tx - id as f32).abs() < f32::EPSILON);
+            assert_eq!(inpto.material, if id % 2 == 0 { "green" } else { "red" });
+        }
+        let untouched = keyframe.get_entity(scoped_entity_id("other", 3)).unwrap();
+        assert!(untouched.transform.tx.abs() < f32::EPSILON);
+        assert_eq!(untouched.material, "blue");
+        assert_eq!(keyframe.entity_count(), 1001);
+    }
+
+    #[test]
+    fn test_multi_group_update_and_destroy() {
+        use expto::rdmp::ExObject;
+
+        fn unit(command: CommandType, objects: Vec<ExObject>) -> Unit {
+            let mut unit = Unit { stamp: None, command: None, objects };
+            unit.command = Some(expto::rdmp::ExCommand { u_command: command as i32 });
+            unit
+        }
+        let sphere = || ExObject::from(ExMesh::from(Sphere { location: None, radius: 1.0 }));
+        let cube_mesh = ExMesh::from(Point { x: 1.0, y: 2.0, z: 3.0 });
+        let material = |m: &str| ExObject { u_object: Some(UObject::MaterialId(m.to_string())) };
+        let at = |x: f32| ExObject::from(ExTransform { x, ..ExTransform::identity() });
+
+        let mut keyframe = KeyFrame::new(0);
+        let mut spawn = Vec::new();
+        for id in 1..=50u64 {
+            spawn.extend([ExObject::from(id), sphere(), material("red")]);
+        }
+        // 缺少 Mesh 的分组被忽略；分组内重复字段以最后一个为准
+        spawn.extend([ExObject::from(51u64), material("blue")]);
+        spawn.extend([ExObject::from(52u64), sphere(), at(1.0), at(2.0)]);
+        keyframe.update(&unit(CommandType::Spawn, spawn));
+        assert_eq!(keyframe.entity_count(), 51);
+        assert!((keyframe.get_entity(52).unwrap().transform.tx - 2.0).abs() < f32::EPSILON);
+
+        // 一个 Unit 更新多个实体，各组只修改出现的字段；首个 Id 之前的对象被忽略
+        keyframe.update(&unit(CommandType::Update, vec![
+            material("ignored"),
+            ExObject::from(1u64), material("green"),
+            ExObject::from(2u64), at(5.0), ExObject::from(cube_mesh.clone()),
+            ExObject::from(999u64), material("missing"),
+        ]));
+        let first = keyframe.get_entity(1).unwrap();
+        assert_eq!(first.material, "green");
+        assert!(first.transform.tx.abs() < f32::EPSILON);
+        let second = keyframe.get_entity(2).unwrap();
+        assert_eq!(second.material, "red");
+        assert!((second.transform.tx - 5.0).abs() < f32::EPSILON);
+        assert_eq!(second.mesh, cube_mesh);
+        assert!(keyframe.iter_entities().all(|(_, e)| e.material != "ignored" && e.material != "missing"));
+
+        // 一个 Unit 销毁全部 50 个实体
+        keyframe.update(&unit(CommandType::Destroy, (1..=50u64).map(ExObject::from).collect()));
+        assert_eq!(keyframe.entity_count(), 1);
+        assert!(keyframe.get_entity(52).is_some());
+        assert!(keyframe.ids.values().all(|&idx| idx < keyframe.packs.len()));
+    }
+
+    #[test]
+    fn test_split_id_groups() {
+        use expto::rdmp::ExObject;
+
+        let material = ExObject { u_object: Some(UObject::MaterialId("red".to_string())) };
+        let objects = vec![material.clone(), ExObject::from(1u64), material.clone(), ExObject::from(2u64)];
+        let groups = split_id_groups(&objects);
+        assert_eq!(groups.iter().map(|g| g.len()).collect::<Vec<_>>(), vec![1, 2, 1]);
+        assert!(split_id_groups(&[]).is_empty());
+        assert_eq!(split_id_groups(&objects[1..]).len(), 2);
+    }
+
+    #[test]
+    fn test_unstamped_ids_are_not_scoped() {
+        assert_eq!(scoped_entity_id("", 42), 42);
+        assert_ne!(scoped_entity_id("a", 42), scoped_entity_id("b", 42));
+        assert_ne!(scoped_entity_id("a", 1), scoped_entity_id("a", 2));
+    }
 }
@@ -1,14 +1,15 @@
 use std::time::Instant;
 
-use expto::rdmp::{CommandType, Unit};
+use expto::rdmp::{CommandType, Unit, ex_object::UObject};
 
-use crate::data::frame::{KeyFrame, UnitPack};
+use crate::data::frame::{FrameTree, KeyFrame, SequenceTracker, UnitPack};
 
 /// 帧管理器 — 核心数据管理资源
 #[derive(Default)]
 #[cfg_attr(feature = "graph", derive(bevy::prelude::Resource))]
 pub struct FrameManager {
     pub current_frame: usize,
+    /// 最近收到的 Unit 时间戳（Unix 毫秒或传感器时间）
     timestamp: u64,
     keyframes: Vec<KeyFrame>,
     frames: Vec<UnitPack>,
@@ -16,6 +17,12 @@ pub struct FrameManager {
     temp_keyframe: Option<KeyFrame>,
     first_temp_unit_timestamp: Option<u64>,
     first_temp_unit_at: Option<Instant>,
+    /// 按会话的序列号跟踪与重排
+    pub sequences: SequenceTracker,
+    /// 命名坐标系树（TF）
+    pub tf: FrameTree,
+    /// 帧数据修订号：关键帧或坐标系变换每次变化时递增，渲染层据此跳过未变化的帧
+    revision: u64,
 }
 
 impl FrameManager {
@@ -25,13 +32,45 @@ impl FrameManager {
 
     pub fn add_keyframe(&mut self, keyframe: KeyFrame) {
         self.keyframes.push(keyframe);
+        self.touch();
+    }
+
+    /// 当前修订号（见 [`touch`](Self::touch)）
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    /// 标记帧数据已变化；经 `_mut` 访问器取得可变引用时会自动调用
+    pub fn touch(&mut self) {
+        self.revision = self.revision.wrapping_add(1);
     }
 
     pub fn add_frame(&mut self, frame: UnitPack) {
         self.frames.push(frame);
     }
 
+    /// 提交一个 Unit — 先经序列号跟踪（去重、可选重排），再组装帧
     pub fn submit(&mut self, unit: &Unit) {
+        for ready in self.sequences.accept(unit) {
+            self.assemble(&ready);
+        }
+    }
+
+    fn assemble(&mut self, unit: &Unit) {
+        if let Some(stamp) = &unit.stamp {
+            self.timestamp = stamp.timestamp;
+        }
+        // 只含坐标系变换的 Unit 不参与帧组装
+        let is_frame_end = unit.command.is_some_and(|c| c.u_command == CommandType::Frameend as i32);
+        let tf_count = self.tf.ingest(unit);
+        if tf_count > 0 {
+            self.touch();
+        }
+        if tf_count > 0 && !is_frame_end
+            && unit.objects.iter().all(|obj| matches!(obj.u_object, Some(UObject::FrameTransform(_))))
+        {
+            return;
+        }
         match unit.command {
             Some(cmd) => {
                 if let Some(command_type) = CommandType::try_from(cmd.u_command).ok() {
@@ -40,12 +79,16 @@ impl FrameManager {
                             self.add_frame(UnitPack::new_frame(self.last_keyframe_idx(), &self.temp_units));
 
                             if !self.temp_units.is_empty() {
-                                let mut keyframe = KeyFrame::new(self.timestamp);
+                                let mut keyframe = self.next_keyframe();
                                 for temp_unit in &self.temp_units {
                                     keyframe.update(temp_unit);
                                 }
                                 self.add_keyframe(keyframe);
                                 log::info!("帧管理器：完成一帧，包含 {} 个 Unit，生成 KeyFrame", self.temp_units.len());
+                            } else if self.keyframes.last().is_some_and(|k| k.packs.iter().any(|e| e.lifetime.is_some())) {
+                                // 空帧同样推进生存期：延续中的实体照常到期
+                                let keyframe = self.next_keyframe();
+                                self.add_keyframe(keyframe);
                             } else {
                                 log::warn!("帧管理器：收到 Frameend 但 temp_units 为空");
                             }
@@ -85,10 +128,11 @@ impl FrameManager {
     }
 
     pub fn generate_keyframe(&mut self) {
+        for ready in self.sequences.flush_expired() {
+            self.assemble(&ready);
+        }
         if self.should_generate_keyframe() {
-            let mut temp_keyframe = self.temp_keyframe.take().unwrap_or_else(|| {
-                KeyFrame::new(self.timestamp)
-            });
+            let mut temp_keyframe = self.temp_keyframe.take().unwrap_or_else(|| self.next_keyframe());
             for unit in self.temp_units.drain(..) {
                 temp_keyframe.update(&unit);
             }
@@ -96,6 +140,20 @@ impl FrameManager {
         }
     }
 
+    /// 待组装帧的初始关键帧 — 延续上一关键帧中未到期的带生存期实体
+    fn next_keyframe(&self) -> KeyFrame {
+        let timestamp = self.frame_timestamp();
+        match self.keyframes.last() {
+            Some(last) => last.successor(timestamp),
+            None => KeyFrame::new(timestamp),
+        }
+    }
+
+    /// 待组装帧的时间 — 帧内第一个 Unit 的时间戳，缺失时取最近收到的时间戳
+    fn frame_timestamp(&self) -> u64 {
+        self.first_temp_unit_timestamp.unwrap_or(self.timestamp)
+    }
+
     pub fn should_generate_keyframe(&self) -> bool {
         if self.temp_units.is_empty() {
             return false;
@@ -134,6 +192,7 @@ impl FrameManager {
     }
 
     pub fn get_current_keyframe_mut(&mut self) -> Option<&mut KeyFrame> {
+        self.touch();
         self.keyframes.get_mut(self.current_frame)
     }
 
@@ -177,6 +236,7 @@ impl FrameManager {
     }
 
     pub fn get_all_keyframes_mut(&mut self) -> &mut [KeyFrame] {
+        self.touch();
         &mut self.keyframes
     }
 
@@ -189,6 +249,9 @@ impl FrameManager {
         self.temp_keyframe = None;
         self.first_temp_unit_timestamp = None;
         self.first_temp_unit_at = None;
+        self.sequences.clear();
+        self.tf.clear();
+        self.touch();
         log::info!("帧管理器已清空");
     }
 
@@ -196,30 +259,259 @@ impl FrameManager {
         !self.keyframes.is_empty()
     }
 
+    /// 从所有关键帧删除实体（连同其子孙实体），返回删除的实体数
     pub fn delete_entities(&mut self, entity_ids: &[u64]) -> usize {
-        let mut deleted_count = 0;
-
-        for keyframe in &mut self.keyframes {
-            for &entity_id in entity_ids {
-                if keyframe.ids.contains_key(&entity_id) {
-                    if let Some(idx) = keyframe.ids.get(&entity_id).copied() {
-                        keyframe.packs.remove(idx);
-                        keyframe.ids.remove(&entity_id);
-                        for (_, idx_ref) in keyframe.ids.iter_mut() {
-                            if *idx_ref > idx {
-                                *idx_ref -= 1;
-                            }
-                        }
-                        deleted_count += 1;
-                    }
-                }
-            }
-        }
+        let deleted_count: usize = self.keyframes.iter_mut()
+            .map(|keyframe| keyframe.remove_entities(entity_ids.iter().copied()))
+            .sum();
 
         if deleted_count > 0 {
+            self.touch();
             log::info!("从帧数据中删除了 {} 个实体", deleted_count);
         }
 
         deleted_count
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use expto::rdmp::{ExCommand, ExMesh, ExObject, ExStamp, Point};
+
+    fn stamped_unit(timestamp: u64, id: u64) -> Unit {
+        Unit {
+            stamp: Some(ExStamp { timestamp, ..Default::default() }),
+            command: None,
+            objects: vec![
+                ExObject::from(id),
+                ExObject::from(ExMesh::from(Point { x: 0.0, y: 0.0, z: 0.0 })),
+            ],
+        }
+    }
+
+    fn frame_end(timestamp: u64) -> Unit {
+        Unit {
+            stamp: Some(ExStamp { timestamp, ..Default::default() }),
+            command: Some(ExCommand { u_command: CommandType::Frameend as i32 }),
+            objects: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_keyframe_keeps_sensor_time() {
+        let mut manager = FrameManager::new();
+        manager.submit(&stamped_unit(1_700_000_000_000, 1));
+        manager.submit(&stamped_unit(1_700_000_000_040, 2));
+        manager.submit(&frame_end(1_700_000_000_050));
+        manager.submit(&stamped_unit(1_700_000_000_100, 1));
+        manager.submit(&frame_end(1_700_000_000_150));
+
+        assert_eq!(manager.total_frames(), 2);
+        assert_eq!(manager.get_keyframe(0).unwrap().timestamp, 1_700_000_000_000);
+        assert_eq!(manager.get_keyframe(1).unwrap().timestamp, 1_700_000_000_100);
+    }
+
+    fn session_unit(seq: u32, command: Option<CommandType>, id: u64) -> Unit {
+        Unit {
+            stamp: Some(ExStamp { timestamp: 0, session_id: "lidar".to_string(), sequence_number: seq }),
+            command: command.map(|c| ExCommand { u_command: c as i32 }),
+            objects: match command {
+                Some(_) => Vec::new(),
+                None => vec![
+                    ExObject::from(id),
+                    ExObject::from(ExMesh::from(Point { x: 0.0, y: 0.0, z: 0.0 })),
+                ],
+            },
+        }
+    }
+
+    #[test]
+    fn test_reorder_before_frame_assembly() {
+        let mut manager = FrameManager::new();
+        manager.sequences.reorder_window = 4;
+        // Frameend 先于帧内最后一个 Unit 到达
+        manager.submit(&session_unit(0, None, 1));
+        manager.submit(&session_unit(2, Some(CommandType::Frameend), 0));
+        assert_eq!(manager.total_frames(), 0);
+        manager.submit(&session_unit(1, None, 2));
+
+        assert_eq!(manager.total_frames(), 1);
+        assert_eq!(manager.get_keyframe(0).unwrap().ids.len(), 2);
+    }
+
+    #[test]
+    fn test_duplicates_are_dropped() {
+        let mut manager = FrameManager::new();
+        manager.submit(&session_unit(0, None, 1));
+        manager.submit(&session_unit(0, None, 1));
+        manager.submit(&session_unit(1, Some(CommandType::Frameend), 0));
+
+        assert_eq!(manager.total_frames(), 1);
+        let stats = manager.sequences.stats();
+        assert_eq!(stats[0].0, "lidar");
+        assert_eq!(stats[0].1.duplicates, 1);
+    }
+
+    #[test]
+    fn test_frame_transforms_feed_tf_tree() {
+        use expto::rdmp::{ExTransform, FrameTransform};
+        use redra_client::{ShapeBuilder, frame_transform_unit};
+
+        let mut manager = FrameManager::new();
+        manager.submit(&frame_transform_unit(vec![
+            FrameTransform::new("odom", "map", ExTransform { x: 10.0, ..ExTransform::identity() }),
+            FrameTransform::new("lidar", "odom", ExTransform { z: 2.0, ..ExTransform::identity() }),
+        ], Some(1_000)));
+        assert!(!manager.should_generate_keyframe(), "纯坐标系变换不应进入帧组装");
+
+        let (_, units) = ShapeBuilder::sphere(0.1).frame("lidar").at(1.0, 0.0, 0.0).build();
+        for unit in &units {
+            manager.submit(unit);
+        }
+        manager.submit(&frame_end(1_000));
+
+        let keyframe = manager.get_keyframe(0).unwrap();
+        let (_, inpto) = keyframe.iter_entities().next().unwrap();
+        assert_eq!(inpto.frame_id, "lidar");
+        let offset = manager.tf.resolve(&inpto.frame_id, keyframe.timestamp).unwrap();
+        let world = offset.mul_transform(&inpto.transform);
+        assert!((world.tx - 11.0).abs() < 1e-5 && (world.tz - 2.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_timestamp_span_triggers_keyframe() {
+        let mut manager = FrameManager::new();
+        manager.submit(&stamped_unit(10_000, 1));
+        assert!(!manager.should_generate_keyframe());
+
+        manager.submit(&stamped_unit(15_000, 2));
+        assert!(manager.should_generate_keyframe());
+
+        manager.generate_keyframe();
+        assert_eq!(manager.total_frames(), 1);
+        assert_eq!(manager.get_keyframe(0).unwrap().timestamp, 10_000);
+    }
+
+    #[test]
+    fn test_lifetime_expires_across_frame_ends() {
+        use expto::rdmp::Lifetime;
+
+        fn transient(timestamp: u64, id: u64, lifetime: Lifetime, parent: Option<u64>) -> Unit {
+            let mut unit = stamped_unit(timestamp, id);
+            unit.objects.push(ExObject::from(lifetime));
+            if let Some(parent) = parent {
+                unit.objects.push(ExObject::parent(parent));
+            }
+            unit
+        }
+
+        let mut manager = FrameManager::new();
+        manager.submit(&transient(1_000, 1, Lifetime::duration_ms(250), None));
+        manager.submit(&transient(1_000, 2, Lifetime::frames(2), None));
+        manager.submit(&stamped_unit(1_000, 3));
+        manager.submit(&transient(1_000, 5, Lifetime::frames(10), Some(1)));
+        manager.submit(&frame_end(1_000));
+        // 后续帧只发送无关实体，带生存期的实体自动延续
+        for timestamp in [1_100, 1_200, 1_300] {
+            manager.submit(&stamped_unit(timestamp, 99));
+            manager.submit(&frame_end(timestamp));
+        }
+
+        let ids = |index: usize| {
+            let mut ids: Vec<u64> = manager.get_keyframe(index).unwrap().ids.keys().copied().collect();
+            ids.sort_unstable();
+            ids
+        };
+        assert_eq!(ids(0), vec![1, 2, 3, 5]);
+        assert_eq!(ids(1), vec![1, 2, 5, 99]);
+        assert_eq!(ids(2), vec![1, 5, 99]);
+        // 实体 1 到期，其子实体 5 一并移除
+        assert_eq!(ids(3), vec![99]);
+        assert_eq!(manager.get_keyframe(2).unwrap().get_entity(5).unwrap().lifetime.unwrap().age_frames, 2);
+
+        // 重新生成延续中的实体：原位替换并重新计时，不产生重复
+        manager.submit(&transient(1_400, 7, Lifetime::duration_ms(150), None));
+        manager.submit(&frame_end(1_400));
+        manager.submit(&transient(1_500, 7, Lifetime::duration_ms(150), None));
+        manager.submit(&frame_end(1_500));
+        manager.submit(&stamped_unit(1_600, 99));
+        manager.submit(&frame_end(1_600));
+        let keyframe = manager.get_keyframe(5).unwrap();
+        assert_eq!(keyframe.entity_count(), 1);
+        assert_eq!(keyframe.get_entity(7).unwrap().lifetime.unwrap().spawned_at, 1_500);
+        assert_eq!(manager.get_keyframe(6).unwrap().entity_count(), 2);
+    }
+
+    #[test]
+    fn test_empty_frame_end_only_advances_lifetimes() {
+        use expto::rdmp::Lifetime;
+
+        // 只有普通实体时，空帧不生成关键帧
+        let mut manager = FrameManager::new();
+        manager.submit(&stamped_unit(1_000, 1));
+        manager.submit(&frame_end(1_000));
+        manager.submit(&frame_end(1_100));
+        assert_eq!(manager.total_frames(), 1);
+
+        // 有带生存期的实体时，空帧推进生存期且不延续普通实体；到期后空帧不再生成关键帧
+        let mut transient = stamped_unit(1_200, 2);
+        transient.objects.push(ExObject::from(Lifetime::frames(2)));
+        manager.submit(&stamped_unit(1_200, 1));
+        manager.submit(&transient);
+        for timestamp in [1_200, 1_300, 1_400, 1_500] {
+            manager.submit(&frame_end(timestamp));
+        }
+        assert_eq!(manager.total_frames(), 4);
+        assert_eq!(manager.get_keyframe(2).unwrap().ids.keys().copied().collect::<Vec<_>>(), vec![2]);
+        assert_eq!(manager.get_keyframe(3).unwrap().entity_count(), 0);
+    }
+
+    #[test]
+    fn test_handle_update_after_frame_end() {
+        use redra_client::ShapeBuilder;
+
+        let stamp_of = |units: &[Unit]| units[0].stamp.as_ref().unwrap().timestamp;
+        let mut manager = FrameManager::new();
+        let (handle, units) = ShapeBuilder::sphere(0.5).material("red").lifetime_frames(5).build();
+        manager.submit_units(&units);
+        manager.submit(&frame_end(stamp_of(&units)));
+
+        // 句柄在下一帧更新延续中的带生存期实体
+        let units = handle.material_units("green");
+        manager.submit_units(&units);
+        manager.submit(&frame_end(stamp_of(&units)));
+
+        assert_eq!(manager.total_frames(), 2);
+        let (_, inpto) = manager.get_keyframe(1).unwrap().iter_entities().next().unwrap();
+        assert_eq!(inpto.material, "green");
+        assert_eq!(manager.get_keyframe(0).unwrap().iter_entities().next().unwrap().1.material, "red");
+    }
+
+    #[test]
+    fn test_revision_tracks_frame_changes() {
+        let mut manager = FrameManager::new();
+        let start = manager.revision();
+
+        // 帧未完成前不影响可渲染内容
+        manager.submit(&stamped_unit(100, 1));
+        assert_eq!(manager.revision(), start);
+        manager.submit(&frame_end(100));
+        let after_frame = manager.revision();
+        assert!(after_frame > start);
+
+        // 只读访问与切帧不改变修订号
+        let _ = manager.get_current_keyframe();
+        manager.seek_to_frame(0);
+        assert_eq!(manager.revision(), after_frame);
+
+        manager.get_current_keyframe_mut();
+        let after_mut = manager.revision();
+        assert!(after_mut > after_frame);
+
+        assert_eq!(manager.delete_entities(&[42]), 0);
+        assert_eq!(manager.revision(), after_mut);
+        assert_eq!(manager.delete_entities(&[1]), 1);
+        assert!(manager.revision() > after_mut);
+    }
+}
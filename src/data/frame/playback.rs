@@ -16,18 +16,20 @@ impl Plugin for FramePlaybackPlugin {
 pub struct PlaybackState {
     pub is_playing: bool,
     pub playback_speed: f32,
+    /// 按关键帧时间戳的真实间隔播放（`playback_speed / 30` 为倍速）
+    pub follow_timestamps: bool,
     accumulated_time: f32,
 }
 
 impl Default for PlaybackState {
     fn default() -> Self {
-        Self { is_playing: false, playback_speed: 30.0, accumulated_time: 0.0 }
+        Self::new()
     }
 }
 
 impl PlaybackState {
     pub fn new() -> Self {
-        Self { is_playing: false, playback_speed: 30.0, accumulated_time: 0.0 }
+        Self { is_playing: false, playback_speed: 30.0, follow_timestamps: false, accumulated_time: 0.0 }
     }
 
     pub fn play(&mut self) {
@@ -50,6 +52,16 @@ impl PlaybackState {
     }
 }
 
+/// 当前帧到下一帧的时间间隔（秒，已按倍速缩放）；时间戳缺失或不递增时返回 `None`
+fn timestamp_interval(frame_manager: &FrameManager, speed: f32) -> Option<f32> {
+    let current = frame_manager.get_current_keyframe()?.timestamp;
+    let next = frame_manager.get_keyframe(frame_manager.current_frame_index() + 1)?.timestamp;
+    if current == 0 || next <= current {
+        return None;
+    }
+    Some((next - current) as f32 / 1000.0 / (speed / 30.0))
+}
+
 fn auto_advance_frame(
     mut frame_manager: ResMut<FrameManager>,
     mut playback_state: ResMut<PlaybackState>,
@@ -57,6 +69,23 @@ fn auto_advance_frame(
 ) {
     if !playback_state.is_playing { return; }
     playback_state.accumulated_time += time.delta_secs();
+
+    if playback_state.follow_timestamps {
+        // 逐帧按时间戳间隔推进；间隔无效的帧退回固定帧率
+        loop {
+            let interval = timestamp_interval(&frame_manager, playback_state.playback_speed)
+                .unwrap_or(1.0 / playback_state.playback_speed);
+            if playback_state.accumulated_time < interval { break; }
+            if !frame_manager.next_frame() {
+                playback_state.pause();
+                log::info!("播放完毕");
+                break;
+            }
+            playback_state.accumulated_time -= interval;
+        }
+        return;
+    }
+
     let frame_interval = 1.0 / playback_state.playback_speed;
     if playback_state.accumulated_time >= frame_interval {
         // 推进多帧以追赶实际时间，避免卡顿后累计太多延迟
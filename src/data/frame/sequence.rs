@@ -0,0 +1,298 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::time::{Duration, Instant};
+
+use expto::rdmp::Unit;
+
+use crate::data::protocol::parse_session;
+
+/// 序列号回退或前跳超过该值时视为客户端重启（或序列号损坏），重置该会话的统计基线
+const RESTART_THRESHOLD: i32 = 4096;
+/// 每个会话最多记住的缺失序列号数量（用于识别迟到的 Unit）
+const MAX_TRACKED_MISSING: usize = 1024;
+
+/// 单个会话的序列号统计
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SequenceStats {
+    /// 收到的 Unit 总数（含重复）
+    pub received: u64,
+    /// 判定为丢失的序列号数量（迟到补齐的会扣除）
+    pub gaps: u64,
+    /// 重复到达并被丢弃的 Unit 数量
+    pub duplicates: u64,
+    /// 乱序到达的 Unit 数量（在重排窗口内纠正或迟到补齐）
+    pub reordered: u64,
+}
+
+struct SessionSequence {
+    stats: SequenceStats,
+    next: u32,
+    missing: BTreeSet<u32>,
+    pending: BTreeMap<u32, Unit>,
+    pending_since: Option<Instant>,
+}
+
+impl SessionSequence {
+    fn new(first: u32) -> Self {
+        Self {
+            stats: SequenceStats::default(),
+            next: first,
+            missing: BTreeSet::new(),
+            pending: BTreeMap::new(),
+            pending_since: None,
+        }
+    }
+
+    /// 记录 `[from, to)` 为丢失；只记住最后 [`MAX_TRACKED_MISSING`] 个序列号
+    fn mark_missing(&mut self, from: u32, to: u32) {
+        let count = to.wrapping_sub(from);
+        self.stats.gaps += count as u64;
+        let tracked = count.min(MAX_TRACKED_MISSING as u32);
+        self.missing.extend((0..tracked).map(|i| to.wrapping_sub(tracked - i)));
+        while self.missing.len() > MAX_TRACKED_MISSING {
+            self.missing.pop_first();
+        }
+    }
+
+    /// 交付 `next` 起连续的缓冲 Unit
+    fn drain_contiguous(&mut self, ready: &mut Vec<Unit>) {
+        while let Some(unit) = self.pending.remove(&self.next) {
+            ready.push(unit);
+            self.next = self.next.wrapping_add(1);
+        }
+        if self.pending.is_empty() {
+            self.pending_since = None;
+        }
+    }
+
+    /// 放弃等待缺口，交付缓冲区中最早的 Unit 及其后连续部分
+    fn skip_gap(&mut self, ready: &mut Vec<Unit>) {
+        if let Some(&first) = self.pending.keys().next() {
+            self.mark_missing(self.next, first);
+            self.next = first;
+            self.drain_contiguous(ready);
+        }
+    }
+}
+
+/// 按会话跟踪序列号 — 识别丢包、重复与乱序，并可在小窗口内重排
+///
+/// 没有 stamp 或会话为空的 Unit（静态场景、本地文件）不参与跟踪，直接交付。
+pub struct SequenceTracker {
+    /// 重排窗口：每个会话最多缓冲的乱序 Unit 数，0 表示只统计不重排
+    pub reorder_window: usize,
+    /// 缓冲 Unit 的最长等待时间，超时后放弃等待缺口
+    pub max_wait: Duration,
+    sessions: HashMap<String, SessionSequence>,
+}
+
+impl Default for SequenceTracker {
+    fn default() -> Self {
+        Self { reorder_window: 0, max_wait: Duration::from_millis(200), sessions: HashMap::new() }
+    }
+}
+
+impl SequenceTracker {
+    /// 接收一个 Unit，返回按序可交付的 Unit（可能为空，也可能包含之前缓冲的 Unit）
+    pub fn accept(&mut self, unit: &Unit) -> Vec<Unit> {
+        let session = parse_session(unit);
+        let Some(stamp) = unit.stamp.as_ref().filter(|_| !session.is_empty()) else {
+            return vec![unit.clone()];
+        };
+        let seq = stamp.sequence_number;
+        let window = self.reorder_window;
+        let state = self.sessions.entry(session.to_string())
+            .or_insert_with(|| SessionSequence::new(seq));
+        state.stats.received += 1;
+
+        let mut ready = Vec::new();
+        let offset = seq.wrapping_sub(state.next) as i32;
+        if !(-RESTART_THRESHOLD..=RESTART_THRESHOLD).contains(&offset) {
+            log::warn!("会话 {} 序列号从 {} 跳变到 {}，视为客户端重启", session, state.next, seq);
+            state.drain_contiguous(&mut ready);
+            ready.extend(std::mem::take(&mut state.pending).into_values());
+            state.next = seq.wrapping_add(1);
+            state.missing.clear();
+            state.pending_since = None;
+            ready.push(unit.clone());
+        } else if offset < 0 {
+            if state.missing.remove(&seq) {
+                state.stats.gaps -= 1;
+                state.stats.reordered += 1;
+                ready.push(unit.clone());
+            } else {
+                state.stats.duplicates += 1;
+            }
+        } else if offset == 0 {
+            ready.push(unit.clone());
+            state.next = seq.wrapping_add(1);
+            state.drain_contiguous(&mut ready);
+        } else if window == 0 {
+            state.mark_missing(state.next, seq);
+            state.next = seq.wrapping_add(1);
+            ready.push(unit.clone());
+        } else if state.pending.contains_key(&seq) {
+            state.stats.duplicates += 1;
+        } else {
+            state.stats.reordered += 1;
+            state.pending.insert(seq, unit.clone());
+            state.pending_since.get_or_insert_with(Instant::now);
+            while state.pending.len() > window {
+                state.skip_gap(&mut ready);
+            }
+        }
+        ready
+    }
+
+    /// 交付等待超过 `max_wait` 的缓冲 Unit（缺口记为丢失）
+    pub fn flush_expired(&mut self) -> Vec<Unit> {
+        let mut ready = Vec::new();
+        for state in self.sessions.values_mut() {
+            if state.pending_since.is_some_and(|t| t.elapsed() >= self.max_wait) {
+                while !state.pending.is_empty() {
+                    state.skip_gap(&mut ready);
+                }
+            }
+        }
+        ready
+    }
+
+    /// 各会话的统计，按会话名排序
+    pub fn stats(&self) -> Vec<(&str, &SequenceStats)> {
+        let mut stats: Vec<_> = self.sessions.iter()
+            .map(|(session, state)| (session.as_str(), &state.stats))
+            .collect();
+        stats.sort_by_key(|(session, _)| *session);
+        stats
+    }
+
+    pub fn clear(&mut self) {
+        self.sessions.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use expto::rdmp::{ExObject, ExStamp};
+
+    fn unit(session: &str, seq: u32) -> Unit {
+        Unit {
+            stamp: Some(ExStamp { timestamp: 0, session_id: session.to_string(), sequence_number: seq }),
+            command: None,
+            objects: vec![ExObject::from(seq as u64)],
+        }
+    }
+
+    fn seqs(units: &[Unit]) -> Vec<u32> {
+        units.iter().map(|u| u.stamp.as_ref().unwrap().sequence_number).collect()
+    }
+
+    fn stats_of<'a>(tracker: &'a SequenceTracker, session: &str) -> &'a SequenceStats {
+        tracker.stats().into_iter().find(|(s, _)| *s == session).unwrap().1
+    }
+
+    #[test]
+    fn test_gap_and_duplicate_without_window() {
+        let mut tracker = SequenceTracker::default();
+        let mut delivered = Vec::new();
+        for seq in [5, 6, 9, 9, 10] {
+            delivered.extend(tracker.accept(&unit("a", seq)));
+        }
+        assert_eq!(seqs(&delivered), [5, 6, 9, 10]);
+
+        let stats = stats_of(&tracker, "a");
+        assert_eq!(stats.received, 5);
+        assert_eq!(stats.gaps, 2);
+        assert_eq!(stats.duplicates, 1);
+
+        // 迟到的 7 补齐一个缺口
+        delivered = tracker.accept(&unit("a", 7));
+        assert_eq!(seqs(&delivered), [7]);
+        let stats = stats_of(&tracker, "a");
+        assert_eq!(stats.gaps, 1);
+        assert_eq!(stats.reordered, 1);
+    }
+
+    #[test]
+    fn test_reorder_within_window() {
+        let mut tracker = SequenceTracker { reorder_window: 4, ..Default::default() };
+        let mut delivered = Vec::new();
+        for seq in [0, 2, 3, 1, 4] {
+            delivered.extend(tracker.accept(&unit("a", seq)));
+        }
+        assert_eq!(seqs(&delivered), [0, 1, 2, 3, 4]);
+        assert_eq!(stats_of(&tracker, "a").gaps, 0);
+        assert_eq!(stats_of(&tracker, "a").reordered, 2);
+    }
+
+    #[test]
+    fn test_window_overflow_gives_up_on_gap() {
+        let mut tracker = SequenceTracker { reorder_window: 2, ..Default::default() };
+        let mut delivered = Vec::new();
+        for seq in [0, 2, 3, 4] {
+            delivered.extend(tracker.accept(&unit("a", seq)));
+        }
+        assert_eq!(seqs(&delivered), [0, 2, 3, 4]);
+        assert_eq!(stats_of(&tracker, "a").gaps, 1);
+    }
+
+    #[test]
+    fn test_flush_expired() {
+        let mut tracker = SequenceTracker { reorder_window: 8, max_wait: Duration::ZERO, ..Default::default() };
+        assert_eq!(seqs(&tracker.accept(&unit("a", 0))), [0]);
+        assert!(tracker.accept(&unit("a", 3)).is_empty());
+        assert_eq!(seqs(&tracker.flush_expired()), [3]);
+        assert_eq!(stats_of(&tracker, "a").gaps, 2);
+    }
+
+    #[test]
+    fn test_sessions_are_independent() {
+        let mut tracker = SequenceTracker::default();
+        tracker.accept(&unit("a", 0));
+        tracker.accept(&unit("b", 100));
+        tracker.accept(&unit("a", 1));
+        tracker.accept(&unit("b", 101));
+        assert_eq!(stats_of(&tracker, "a").gaps, 0);
+        assert_eq!(stats_of(&tracker, "b").gaps, 0);
+    }
+
+    #[test]
+    fn test_restart_resets_baseline() {
+        let mut tracker = SequenceTracker::default();
+        tracker.accept(&unit("a", 100_000));
+        let delivered = tracker.accept(&unit("a", 0));
+        assert_eq!(seqs(&delivered), [0]);
+        assert_eq!(seqs(&tracker.accept(&unit("a", 1))), [1]);
+        assert_eq!(stats_of(&tracker, "a").duplicates, 0);
+    }
+
+    #[test]
+    fn test_large_gaps_are_counted_not_enumerated() {
+        let mut tracker = SequenceTracker::default();
+        tracker.accept(&unit("a", 0));
+        // 阈值内的缺口全部计数，只记住最后 MAX_TRACKED_MISSING 个
+        let seq = RESTART_THRESHOLD as u32;
+        assert_eq!(seqs(&tracker.accept(&unit("a", seq))), [seq]);
+        assert_eq!(stats_of(&tracker, "a").gaps, seq as u64 - 1);
+        assert_eq!(seqs(&tracker.accept(&unit("a", seq - 1))), [seq - 1]);
+        assert!(tracker.accept(&unit("a", 1)).is_empty());
+        assert_eq!(stats_of(&tracker, "a").gaps, seq as u64 - 2);
+
+        // 远超阈值的前跳按重启处理，不逐个记录缺口
+        let far = seq + (1 << 30);
+        assert_eq!(seqs(&tracker.accept(&unit("a", far))), [far]);
+        assert_eq!(seqs(&tracker.accept(&unit("a", far + 1))), [far + 1]);
+        let stats = stats_of(&tracker, "a");
+        assert_eq!(stats.gaps, seq as u64 - 2);
+        assert_eq!(stats.duplicates, 1);
+    }
+
+    #[test]
+    fn test_unstamped_units_pass_through() {
+        let mut tracker = SequenceTracker::default();
+        let plain = Unit { stamp: None, command: None, objects: Vec::new() };
+        assert_eq!(tracker.accept(&plain).len(), 1);
+        assert_eq!(tracker.accept(&plain).len(), 1);
+        assert!(tracker.stats().is_empty());
+    }
+}
@@ -5,6 +5,7 @@ use sea_orm::{Database, DatabaseConnection, Set, QuerySelect, QueryOrder, Schema
 use std::path::{Path, PathBuf};
 use std::collections::HashMap;
 use chrono::Utc;
+use expto::schema::ADDED_ENTITY_COLUMNS;
 
 #[cfg(feature = "graph")]
 use bevy::prelude::*;
@@ -55,6 +56,12 @@ mod entities_table {
         pub sx: f32,
         pub sy: f32,
         pub sz: f32,
+        /// 父实体 ID（旧数据库由 `init_tables` 迁移补列，值为 NULL）
+        pub parent_id: Option<i64>,
+        /// 变换所在的命名坐标系（`Inpto.frame_id`，不同于帧序号 `frame_id`）；旧数据库补列后为 NULL
+        pub frame_name: Option<String>,
+        /// 命名空间（`Inpto.namespace`）；旧数据库补列后为 NULL
+        pub namespace: Option<String>,
     }
 
     #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -91,6 +98,8 @@ mod entity_tags_table {
 /// SQLite 存储管理器。
 ///
 /// 每个实体存储为 SQL 行，支持按帧、材质、标签查询。
+/// 每帧保存的是展开后的快照：到期实体在后续帧中已不存在，因此生存期（`Inpto.lifetime`）不落库，
+/// 加载的实体没有生存期。
 /// 内部使用 sea-orm 进行 ORM 操作，通过 Tokio Runtime 桥接同步/异步。
 #[cfg_attr(feature = "graph", derive(Resource))]
 pub struct FrameStorage {
@@ -183,6 +192,7 @@ impl FrameStorage {
                 schema.create_table_from_entity(entities_table::Entity).if_not_exists(),
             );
             self.conn.execute(stmt).await.map_err(|e| format!("创建表 entities 失败: {}", e))?;
+            self.migrate_entities_columns().await?;
 
             let stmt = backend.build(
                 schema.create_table_from_entity(entity_tags_table::Entity).if_not_exists(),
@@ -207,6 +217,27 @@ impl FrameStorage {
         })
     }
 
+    /// 旧版数据库的 entities 表缺少后续版本新增的列，按需补齐
+    async fn migrate_entities_columns(&self) -> Result<(), String> {
+        let backend = self.conn.get_database_backend();
+        let columns = self.conn
+            .query_all(sea_orm::Statement::from_string(backend, "PRAGMA table_info(entities)".to_owned()))
+            .await
+            .map_err(|e| format!("查询 entities 表结构失败: {}", e))?;
+        let names: Vec<String> = columns.iter()
+            .filter_map(|row| row.try_get::<String>("", "name").ok())
+            .collect();
+        for (column, sql_type) in ADDED_ENTITY_COLUMNS {
+            if names.iter().any(|name| name == column) {
+                continue;
+            }
+            let stmt = sea_orm::Statement::from_string(backend, format!("ALTER TABLE entities ADD COLUMN {} {}", column, sql_type));
+            self.conn.execute(stmt).await.map_err(|e| format!("迁移 entities 表（{}）失败: {}", column, e))?;
+            log::info!("已为旧数据库 entities 表补充 {} 列", column);
+        }
+        Ok(())
+    }
+
     // ── 流式写入 ──
 
     /// 追加一帧到数据库，返回分配的 frame_id（由 AUTOINCREMENT 自动生成）。
@@ -247,6 +278,9 @@ impl FrameStorage {
                     sx: Set(t.sx),
                     sy: Set(t.sy),
                     sz: Set(t.sz),
+                    parent_id: Set(inpto.parent.map(|id| id as i64)),
+                    frame_name: Set(Some(inpto.frame_id.clone())),
+                    namespace: Set(Some(inpto.namespace.clone())),
                 }
                 .insert(&self.conn)
                 .await
@@ -312,8 +346,7 @@ impl FrameStorage {
             let mut tag_map: HashMap<(i64, i32), Vec<(usize, expto::rdmp::Tag)>> =
                 HashMap::new();
             for tr in &tag_rows {
-                let tag: expto::rdmp::Tag = bincode::deserialize(&tr.tag_data)
-                    .map_err(|e| format!("反序列化标签失败: {}", e))?;
+                let tag = decode_tag(&tr.tag_data)?;
                 tag_map
                     .entry((tr.entity_id, tr.frame_id))
                     .or_default()
@@ -323,8 +356,7 @@ impl FrameStorage {
             let mut keyframe = KeyFrame::new(frame.timestamp as u64);
 
             for er in &entity_rows {
-                let mesh: expto::rdmp::ExMesh = bincode::deserialize(&er.mesh_data)
-                    .map_err(|e| format!("反序列化 mesh 失败: {}", e))?;
+                let mesh = decode_mesh(&er.mesh_data)?;
 
                 let transform = crate::data::frame::inpto::InptoTransform {
                     tx: er.tx, ty: er.ty, tz: er.tz,
@@ -346,6 +378,12 @@ impl FrameStorage {
                     material: er.material.clone(),
                     transform,
                     tags,
+                    session: String::new(),
+                    parent: er.parent_id.map(|id| id as u64),
+                    frame_id: er.frame_name.clone().unwrap_or_default(),
+                    namespace: er.namespace.clone().unwrap_or_default(),
+                    // 生存期已体现在各帧快照中，不落库
+                    lifetime: None,
                 };
                 keyframe.ids.insert(er.entity_id as u64, keyframe.packs.len());
                 keyframe.packs.push(inpto);
@@ -475,3 +513,389 @@ impl FrameStorage {
         })
     }
 }
+
+/// 反序列化 Tag；兼容偏移变换的四元数参与序列化之前写入的记录
+fn decode_tag(data: &[u8]) -> Result<expto::rdmp::Tag, String> {
+    // bincode 允许尾随字节，旧记录可能被误读为新布局：重新编码与原数据一致才采用
+    let err = match bincode::deserialize::<expto::rdmp::Tag>(data) {
+        Ok(tag) if bincode::serialize(&tag).is_ok_and(|bytes| bytes == data) => return Ok(tag),
+        Ok(_) => "记录与当前布局不一致".to_string(),
+        Err(e) => e.to_string(),
+    };
+    decode_legacy_tag(data).ok_or_else(|| format!("反序列化标签失败: {}", err))
+}
+
+/// 按旧版字段解码：偏移变换只有平移、欧拉角与缩放，没有四元数
+fn decode_legacy_tag(data: &[u8]) -> Option<expto::rdmp::Tag> {
+    use expto::rdmp::{ExTransform, Tag, TagStyle};
+
+    type LegacyTransform = (f32, f32, f32, f32, f32, f32, f32, f32, f32);
+    let (text, offset, style): (String, Option<LegacyTransform>, Option<TagStyle>) = bincode::deserialize(data).ok()?;
+    let offset = offset.map(|(x, y, z, rx, ry, rz, sx, sy, sz)| ExTransform { x, y, z, rx, ry, rz, sx, sy, sz, rotation: None });
+    Some(Tag { text, offset, style })
+}
+
+/// 反序列化 mesh；兼容线段、Cube 增加字段之前写入的记录
+fn decode_mesh(data: &[u8]) -> Result<expto::rdmp::ExMesh, String> {
+    let err = match bincode::deserialize(data) {
+        Ok(mesh) => return Ok(mesh),
+        Err(e) => e,
+    };
+    decode_legacy_mesh(data).ok_or_else(|| format!("反序列化 mesh 失败: {}", err))
+}
+
+/// 按旧版字段解码：Option 标记（1 为 Some）与 UMesh 变体序号之后为各类型的旧字段
+///
+/// - 线段（序号 1）：旧版只有起终点，由实体变换定位在中点并旋转到沿 Y 轴，渲染为半径 0.02 的圆柱；
+///   转换为局部 Y 轴上等长、世界线宽 0.04 的线段，保持旧录制的外观
+/// - Cube（序号 5）：没有绘制样式字段，按 `AUTO` 处理
+fn decode_legacy_mesh(data: &[u8]) -> Option<expto::rdmp::ExMesh> {
+    use expto::rdmp::{Cube, Line, Point};
+
+    let (some, variant): (u8, u32) = bincode::deserialize(data).ok()?;
+    let body = data.get(5..)?;
+    if some != 1 {
+        return None;
+    }
+    match variant {
+        1 => {
+            let (start, end): (Option<Point>, Option<Point>) = bincode::deserialize(body).ok()?;
+            let (start, end) = (start?, end?);
+            let half = ((end.x - start.x).powi(2) + (end.y - start.y).powi(2) + (end.z - start.z).powi(2)).sqrt() * 0.5;
+            let mut line = Line::from((Point { x: 0.0, y: -half, z: 0.0 }, Point { x: 0.0, y: half, z: 0.0 }));
+            line.width = 0.04;
+            Some(line.into())
+        }
+        5 => {
+            let vertices: Vec<Point> = bincode::deserialize(body).ok()?;
+            Some(Cube::from(vertices).into())
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::frame::InptoTransform;
+    use expto::rdmp::{ExMesh, Polyline, TriangleMesh};
+
+    fn temp_storage(name: &str) -> (FrameStorage, PathBuf) {
+        let path = std::env::temp_dir().join(format!("redra_test_{}_{}.db", name, std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        (FrameStorage::new(&path).unwrap(), path)
+    }
+
+    #[test]
+    fn test_triangle_mesh_round_trip() {
+        let (storage, path) = temp_storage("triangle_mesh");
+        let mut triangle_mesh = TriangleMesh::from((
+            vec![[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]],
+            vec![0, 1, 2],
+        ));
+        triangle_mesh.colors = vec![[1.0, 0.0, 0.0, 1.0].into(); 3];
+        let mesh = ExMesh::from(triangle_mesh);
+
+        let mut keyframe = KeyFrame::new(1_700_000_000_000);
+        keyframe.ids.insert(7, 0);
+        keyframe.packs.push(Inpto::new(mesh.clone(), String::new(), InptoTransform::identity()));
+
+        let frame_id = storage.append_frame(&keyframe).unwrap();
+        let loaded = storage.load_frame(frame_id).unwrap();
+        assert_eq!(loaded.timestamp, 1_700_000_000_000);
+        assert_eq!(loaded.packs[loaded.ids[&7]].mesh, mesh);
+
+        drop(storage);
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn test_polyline_round_trip() {
+        let (storage, path) = temp_storage("polyline");
+        let trajectory: Vec<[f32; 3]> = (0..500).map(|i| [i as f32 * 0.1, (i as f32 * 0.05).sin(), 0.0]).collect();
+        let mut polyline = Polyline::from(trajectory);
+        polyline.colors = vec![[0.2, 0.6, 1.0, 1.0].into(); 500];
+        polyline.closed = true;
+        polyline.width = 0.05;
+        let mesh = ExMesh::from(polyline);
+
+        let mut keyframe = KeyFrame::new(42);
+        keyframe.ids.insert(1, 0);
+        keyframe.packs.push(Inpto::new(mesh.clone(), "materials/base/blue.toml".to_string(), InptoTransform::identity()));
+
+        let frame_id = storage.append_frame(&keyframe).unwrap();
+        let loaded = storage.load_frame(frame_id).unwrap();
+        let inpto = &loaded.packs[loaded.ids[&1]];
+        assert_eq!(inpto.mesh, mesh);
+        assert_eq!(inpto.material, "materials/base/blue.toml");
+
+        drop(storage);
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn test_legacy_line_decoding() {
+        use expto::rdmp::{Line, Point};
+
+        let start = Point { x: 1.0, y: 0.0, z: 0.0 };
+        let end = Point { x: 1.0, y: 0.0, z: 4.0 };
+        let line = Line::from((start, end));
+        let mesh = ExMesh::from(line.clone());
+        assert_eq!(decode_mesh(&bincode::serialize(&mesh).unwrap()).unwrap(), mesh);
+
+        // 旧版记录：Some + Line 变体 + 起终点，没有线宽与虚线
+        let legacy = bincode::serialize(&(1u8, 1u32, Some(start), Some(end))).unwrap();
+        let Some(expto::rdmp::mesh::ex_mesh::UMesh::Line(decoded)) = decode_mesh(&legacy).unwrap().u_mesh else {
+            panic!("应解码为线段");
+        };
+        assert_eq!(decoded.start, Some(Point { x: 0.0, y: -2.0, z: 0.0 }));
+        assert_eq!(decoded.end, Some(Point { x: 0.0, y: 2.0, z: 0.0 }));
+        assert_eq!(decoded.width, 0.04);
+        assert!(!decoded.screen_space);
+
+        assert!(decode_mesh(&[1, 2, 3]).is_err());
+    }
+
+    #[test]
+    fn test_legacy_box_decoding() {
+        use expto::rdmp::mesh::ex_mesh::UMesh;
+        use expto::rdmp::{BoxStyle, Point};
+
+        let vertices: Vec<Point> = (0..8).map(|i| Point { x: (i & 1) as f32, y: (i >> 1 & 1) as f32, z: (i >> 2) as f32 }).collect();
+        let legacy = bincode::serialize(&(1u8, 5u32, vertices.clone())).unwrap();
+        let Some(UMesh::Cube(cube)) = decode_mesh(&legacy).unwrap().u_mesh else {
+            panic!("应解码为 Cube");
+        };
+        assert_eq!(cube.vertices, vertices);
+        assert_eq!(cube.style(), BoxStyle::Auto);
+
+        // 新版记录按当前字段解码，样式保留
+        let mut cube = expto::rdmp::Cube::from(vertices);
+        cube.set_style(BoxStyle::Translucent);
+        let mesh = ExMesh::from(cube);
+        assert_eq!(decode_mesh(&bincode::serialize(&mesh).unwrap()).unwrap(), mesh);
+    }
+
+    #[test]
+    fn test_tag_offset_rotation_round_trip() {
+        use expto::rdmp::{ExTransform, Quaternion, Tag, TagStyle};
+
+        let rotation = Quaternion { x: 0.0, y: 0.0, z: std::f32::consts::FRAC_1_SQRT_2, w: std::f32::consts::FRAC_1_SQRT_2 };
+        let offset = ExTransform { y: 2.0, rotation: Some(rotation), ..ExTransform::identity() };
+        for style in [None, Some(TagStyle { font_size: 16.0, ..Default::default() })] {
+            let tag = Tag { text: "车辆".into(), offset: Some(offset), style };
+            assert_eq!(decode_tag(&bincode::serialize(&tag).unwrap()).unwrap(), tag);
+
+            // 旧版记录：偏移没有四元数字段
+            let legacy_offset = (0.0f32, 2.0f32, 0.0f32, 0.0f32, 0.0f32, 0.0f32, 1.0f32, 1.0f32, 1.0f32);
+            let legacy = bincode::serialize(&(&tag.text, Some(legacy_offset), style)).unwrap();
+            let decoded = decode_tag(&legacy).unwrap();
+            assert_eq!(decoded.offset, Some(ExTransform { rotation: None, ..offset }));
+            assert_eq!(decoded.style, style);
+        }
+
+        // 写入数据库后四元数保留
+        let (storage, path) = temp_storage("tag_rotation");
+        let mut keyframe = KeyFrame::new(0);
+        keyframe.insert_entity(1, ExMesh::from(expto::rdmp::Point { x: 0.0, y: 0.0, z: 0.0 }), ExTransform::identity());
+        keyframe.packs[0].tags.push(Tag { text: "a".into(), offset: Some(offset), style: None });
+        let frame_id = storage.append_frame(&keyframe).unwrap();
+        let loaded = storage.load_frame(frame_id).unwrap();
+        assert_eq!(loaded.get_entity(1).unwrap().tags[0].offset.unwrap().rotation, Some(rotation));
+
+        drop(storage);
+        let _ = std::fs::remove_file(path);
+    }
+
+    /// 模拟旧版数据库：删除 entities 表中后续版本新增的列
+    fn drop_added_columns(storage: &FrameStorage) {
+        storage.rt.block_on(async {
+            let backend = storage.conn.get_database_backend();
+            for (column, _) in ADDED_ENTITY_COLUMNS {
+                let stmt = sea_orm::Statement::from_string(backend, format!("ALTER TABLE entities DROP COLUMN {}", column));
+                storage.conn.execute(stmt).await.unwrap();
+            }
+        });
+    }
+
+    #[test]
+    fn test_parent_round_trip_and_migration() {
+        let (storage, path) = temp_storage("parent");
+        drop_added_columns(&storage);
+        drop(storage);
+
+        let storage = FrameStorage::new(&path).unwrap();
+        let mesh = ExMesh::from(expto::rdmp::Point { x: 0.0, y: 0.0, z: 0.0 });
+        let mut keyframe = KeyFrame::new(0);
+        keyframe.insert_entity(1, mesh.clone(), expto::rdmp::ExTransform::identity());
+        keyframe.insert_entity(2, mesh, expto::rdmp::ExTransform::identity());
+        keyframe.packs[1].parent = Some(1);
+
+        let frame_id = storage.append_frame(&keyframe).unwrap();
+        let loaded = storage.load_frame(frame_id).unwrap();
+        assert_eq!(loaded.get_entity(1).unwrap().parent, None);
+        assert_eq!(loaded.parent_of(2), Some(1));
+
+        drop(storage);
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn test_frame_name_and_namespace_round_trip() {
+        let (storage, path) = temp_storage("frame_name");
+        drop_added_columns(&storage);
+        drop(storage);
+
+        let storage = FrameStorage::new(&path).unwrap();
+        let mesh = ExMesh::from(expto::rdmp::Point { x: 0.0, y: 0.0, z: 0.0 });
+        let mut keyframe = KeyFrame::new(0);
+        keyframe.insert_entity(1, mesh.clone(), expto::rdmp::ExTransform::identity());
+        keyframe.insert_entity(2, mesh, expto::rdmp::ExTransform::identity());
+        keyframe.packs[0].frame_id = "lidar".to_string();
+        keyframe.packs[0].namespace = "obstacles".to_string();
+
+        let frame_id = storage.append_frame(&keyframe).unwrap();
+        let loaded = storage.load_frame(frame_id).unwrap();
+        assert_eq!(loaded.get_entity(1).unwrap().frame_id, "lidar");
+        assert_eq!(loaded.get_entity(1).unwrap().namespace, "obstacles");
+        assert_eq!(loaded.get_entity(2).unwrap().frame_id, "");
+        assert_eq!(loaded.get_entity(2).unwrap().namespace, "");
+        drop(storage);
+        let _ = std::fs::remove_file(path);
+
+        // SqlWriter 写入并合并到另一个库后坐标系名与命名空间保留
+        use redra_client::{ShapeBuilder, SqlWriter};
+        let source = std::env::temp_dir().join(format!("redra_test_frame_name_src_{}.db", std::process::id()));
+        let target = std::env::temp_dir().join(format!("redra_test_frame_name_dst_{}.db", std::process::id()));
+        let _ = std::fs::remove_file(&source);
+        let _ = std::fs::remove_file(&target);
+        let mut writer = SqlWriter::new(&source).unwrap();
+        writer.spawn(ShapeBuilder::sphere(1.0).id(1).frame("lidar").namespace("obstacles"));
+        writer.end_frame().unwrap();
+        drop(writer);
+        // 合并不复制帧记录，目标库需已有同一帧
+        let mut writer = SqlWriter::new(&target).unwrap();
+        writer.spawn(ShapeBuilder::sphere(1.0).id(2));
+        writer.end_frame().unwrap();
+        drop(writer);
+        SqlWriter::merge_db(&target, &source).unwrap();
+
+        let storage = FrameStorage::new(&target).unwrap();
+        let frames = storage.load_all_frames().unwrap();
+        assert_eq!(frames[0].get_entity(1).unwrap().frame_id, "lidar");
+        assert_eq!(frames[0].get_entity(1).unwrap().namespace, "obstacles");
+        assert_eq!(frames[0].get_entity(2).unwrap().frame_id, "");
+        drop(storage);
+        let _ = std::fs::remove_file(source);
+        let _ = std::fs::remove_file(target);
+    }
+
+    #[test]
+    fn test_recorded_lifetime_expiry() {
+        use redra_client::{ShapeBuilder, SqlWriter};
+
+        let path = std::env::temp_dir().join(format!("redra_test_lifetime_{}.db", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let mut writer = SqlWriter::new(&path).unwrap();
+        writer.spawn(ShapeBuilder::sphere(1.0).id(1));
+        writer.spawn(ShapeBuilder::sphere(0.5).id(2).lifetime_frames(2));
+        writer.spawn(ShapeBuilder::sphere(0.5).id(3).lifetime_ms(300));
+        writer.spawn(ShapeBuilder::sphere(0.1).id(4).parent(3));
+        for _ in 0..4 {
+            writer.end_frame().unwrap();
+        }
+        writer.save().unwrap();
+        drop(writer);
+
+        let storage = FrameStorage::new(&path).unwrap();
+        let frames = storage.load_all_frames().unwrap();
+        let ids: Vec<Vec<u64>> = frames.iter()
+            .map(|frame| {
+                let mut ids: Vec<u64> = frame.ids.keys().copied().collect();
+                ids.sort_unstable();
+                ids
+            })
+            .collect();
+        // 每帧间隔 200ms：实体 2 存在 2 帧，实体 3 在 300ms 后到期并带走子实体 4
+        assert_eq!(ids, vec![vec![1, 2, 3, 4], vec![1, 2, 3, 4], vec![1], vec![1]]);
+        assert!(frames[0].get_entity(2).unwrap().lifetime.is_none());
+
+        drop(storage);
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn test_live_and_recorded_frames_agree() {
+        use expto::rdmp::{CommandType, ExCommand, ExStamp, Unit};
+        use expto::rdmp::auto::stamper::Stamper;
+        use redra_client::{ShapeBuilder, SqlWriter};
+        use crate::data::frame::FrameManager;
+        use crate::data::protocol::scoped_entity_id;
+
+        // 同一组场景分别走实时（FrameManager）与录制（SqlWriter）两条路径；
+        // 无生存期的实体每帧重发，带生存期的实体只在生成时发送一次
+        fn scene(frame: usize) -> Vec<ShapeBuilder> {
+            let mut builders = vec![ShapeBuilder::sphere(1.0).id(1)];
+            if frame > 0 {
+                builders.push(ShapeBuilder::sphere(0.2).id(5));
+            }
+            match frame {
+                0 => builders.extend([
+                    ShapeBuilder::sphere(0.5).id(2).lifetime_frames(2),
+                    ShapeBuilder::sphere(0.5).id(3).lifetime_ms(300),
+                    ShapeBuilder::sphere(0.1).id(4).parent(3).lifetime_frames(10),
+                ]),
+                3 => builders.push(ShapeBuilder::sphere(0.5).id(2).lifetime_frames(1)),
+                _ => {}
+            }
+            builders
+        }
+        let timestamps = [1_000, 1_200, 1_400, 1_600, 1_800];
+
+        let path = std::env::temp_dir().join(format!("redra_test_live_recorded_{}.db", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let mut writer = SqlWriter::new(&path).unwrap();
+        let mut manager = FrameManager::new();
+        for (frame, &timestamp) in timestamps.iter().enumerate() {
+            for builder in scene(frame) {
+                for unit in builder.timestamp(timestamp).build().1 {
+                    manager.submit(&unit);
+                }
+            }
+            manager.submit(&Unit {
+                stamp: Some(ExStamp { timestamp, ..Default::default() }),
+                command: Some(ExCommand { u_command: CommandType::Frameend as i32 }),
+                objects: Vec::new(),
+            });
+            for builder in scene(frame) {
+                writer.spawn(builder);
+            }
+            writer.end_frame_at(timestamp).unwrap();
+        }
+        writer.save().unwrap();
+        drop(writer);
+
+        let storage = FrameStorage::new(&path).unwrap();
+        let sorted = |frame: &KeyFrame| {
+            let mut ids: Vec<u64> = frame.ids.keys().copied().collect();
+            ids.sort_unstable();
+            ids
+        };
+        let recorded: Vec<Vec<u64>> = storage.load_all_frames().unwrap().iter().map(sorted).collect();
+        // 实时路径的 ID 带会话作用域，换算回客户端本地 ID 后比较
+        let session = Stamper::session_id();
+        let live: Vec<Vec<u64>> = (0..manager.total_frames())
+            .map(|index| {
+                let frame = manager.get_keyframe(index).unwrap();
+                let mut ids: Vec<u64> = frame.ids.keys().map(|&id| scoped_entity_id(session, id)).collect();
+                ids.sort_unstable();
+                ids
+            })
+            .collect();
+        assert_eq!(recorded, vec![vec![1, 2, 3, 4], vec![1, 2, 3, 4, 5], vec![1, 5], vec![1, 2, 5], vec![1, 5]]);
+        assert_eq!(live, recorded);
+
+        drop(storage);
+        let _ = std::fs::remove_file(path);
+    }
+}
@@ -0,0 +1,217 @@
+//! 命名坐标系树（TF）
+//!
+//! 生产者通过 `FrameTransform` 声明 `frame_id` 在 `parent_frame_id` 中的位姿，
+//! 实体通过 `ExObject.frame_id` 说明自身 transform 所在的坐标系。
+//! 渲染时沿坐标系链解析到固定（显示）坐标系。
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use expto::rdmp::{FrameTransform, Unit, ex_object::UObject};
+
+use crate::data::frame::InptoTransform;
+
+/// 每个坐标系保留的历史位姿数上限（超出时丢弃最早的）
+const MAX_SAMPLES_PER_FRAME: usize = 4096;
+
+struct FrameNode {
+    parent: String,
+    /// 按时间戳升序的历史位姿
+    samples: VecDeque<(u64, InptoTransform)>,
+}
+
+/// 坐标系树 — 记录各坐标系随时间变化的位姿，并解析坐标系链
+#[derive(Default)]
+pub struct FrameTree {
+    frames: HashMap<String, FrameNode>,
+    /// 固定（显示）坐标系；为空时使用各坐标系所在树的根
+    pub fixed_frame: String,
+}
+
+impl FrameTree {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 记录 Unit 中的全部坐标系变换（时间取 stamp，缺失为 0），返回记录条数
+    pub fn ingest(&mut self, unit: &Unit) -> usize {
+        let timestamp = unit.stamp.as_ref().map(|s| s.timestamp).unwrap_or(0);
+        let mut count = 0;
+        for obj in &unit.objects {
+            if let Some(UObject::FrameTransform(frame_transform)) = &obj.u_object
+                && self.update(frame_transform, timestamp)
+            {
+                count += 1;
+            }
+        }
+        count
+    }
+
+    /// 记录一条坐标系变换；坐标系名为空或以自身为父时忽略并返回 `false`
+    ///
+    /// 父坐标系变化时丢弃旧的历史位姿（它们相对旧的父坐标系）。
+    pub fn update(&mut self, frame_transform: &FrameTransform, timestamp: u64) -> bool {
+        let FrameTransform { frame_id, parent_frame_id, transform } = frame_transform;
+        if frame_id.is_empty() || frame_id == parent_frame_id {
+            log::warn!("忽略无效的坐标系变换: '{}' → '{}'", frame_id, parent_frame_id);
+            return false;
+        }
+        let transform = transform.map(InptoTransform::from).unwrap_or_default();
+
+        let node = self.frames.entry(frame_id.clone()).or_insert_with(|| FrameNode {
+            parent: parent_frame_id.clone(),
+            samples: VecDeque::new(),
+        });
+        if node.parent != *parent_frame_id {
+            log::info!("坐标系 '{}' 的父坐标系由 '{}' 变为 '{}'", frame_id, node.parent, parent_frame_id);
+            node.parent = parent_frame_id.clone();
+            node.samples.clear();
+        }
+
+        match node.samples.binary_search_by_key(&timestamp, |(t, _)| *t) {
+            Ok(i) => node.samples[i].1 = transform,
+            Err(i) => node.samples.insert(i, (timestamp, transform)),
+        }
+        if node.samples.len() > MAX_SAMPLES_PER_FRAME {
+            node.samples.pop_front();
+        }
+        true
+    }
+
+    /// `source` 坐标系中的坐标 → `target` 坐标系中的坐标
+    ///
+    /// `target` 为空表示 `source` 所在树的根。两个坐标系不在同一棵树上、
+    /// 或坐标系链成环时返回 `None`。
+    pub fn lookup(&self, target: &str, source: &str, timestamp: u64) -> Option<InptoTransform> {
+        if target == source {
+            return Some(InptoTransform::identity());
+        }
+        let (source_root, root_from_source) = self.to_root(source, timestamp)?;
+        if target.is_empty() {
+            return Some(root_from_source);
+        }
+        let (target_root, root_from_target) = self.to_root(target, timestamp)?;
+        if source_root != target_root {
+            return None;
+        }
+        Some(root_from_target.inverse().mul_transform(&root_from_source))
+    }
+
+    /// 实体坐标系 → 固定坐标系（实体未指定坐标系时为单位变换）
+    pub fn resolve(&self, frame_id: &str, timestamp: u64) -> Option<InptoTransform> {
+        if frame_id.is_empty() {
+            return Some(InptoTransform::identity());
+        }
+        self.lookup(&self.fixed_frame, frame_id, timestamp)
+    }
+
+    /// 全部已知坐标系名称（包括只作为父坐标系出现的，如 `"map"`），按名称排序
+    pub fn frame_ids(&self) -> Vec<String> {
+        let mut ids: Vec<String> = self.frames.iter()
+            .flat_map(|(id, node)| [id.clone(), node.parent.clone()])
+            .filter(|id| !id.is_empty())
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        ids.sort();
+        ids
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// 清空坐标系历史（保留固定坐标系选择）
+    pub fn clear(&mut self) {
+        self.frames.clear();
+    }
+
+    /// 指定时间的位姿 — 取不晚于 `timestamp` 的最近一条，全部晚于时取最早一条
+    fn sample(&self, node: &FrameNode, timestamp: u64) -> InptoTransform {
+        let i = node.samples.partition_point(|(t, _)| *t <= timestamp);
+        node.samples.get(i.saturating_sub(1)).map(|(_, t)| *t).unwrap_or_default()
+    }
+
+    /// 坐标系所在树的根名称，以及坐标系 → 根的变换
+    fn to_root(&self, frame_id: &str, timestamp: u64) -> Option<(String, InptoTransform)> {
+        let mut transform = InptoTransform::identity();
+        let mut visited = HashSet::new();
+        let mut current = frame_id;
+        while let Some(node) = self.frames.get(current) {
+            if !visited.insert(current) {
+                log::warn!("坐标系链成环: {}", frame_id);
+                return None;
+            }
+            transform = self.sample(node, timestamp).mul_transform(&transform);
+            if node.parent.is_empty() {
+                break;
+            }
+            current = &node.parent;
+        }
+        Some((current.to_string(), transform))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use expto::rdmp::ExTransform;
+
+    fn translation(x: f32, y: f32, z: f32) -> ExTransform {
+        ExTransform { x, y, z, ..ExTransform::identity() }
+    }
+
+    fn assert_translation(t: InptoTransform, expected: [f32; 3]) {
+        let actual = [t.tx, t.ty, t.tz];
+        for i in 0..3 {
+            assert!((actual[i] - expected[i]).abs() < 1e-5, "{:?} != {:?}", actual, expected);
+        }
+    }
+
+    fn robot_tree() -> FrameTree {
+        let mut tree = FrameTree::new();
+        tree.update(&FrameTransform::new("odom", "map", translation(100.0, 0.0, 0.0)), 0);
+        // base_link 绕 Z 轴旋转 90°
+        let yaw = translation(1.0, 0.0, 0.0).with_quaternion([0.0, 0.0, std::f32::consts::FRAC_1_SQRT_2, std::f32::consts::FRAC_1_SQRT_2]);
+        tree.update(&FrameTransform::new("base_link", "odom", yaw), 1000);
+        tree.update(&FrameTransform::new("base_link", "odom", translation(5.0, 0.0, 0.0)), 2000);
+        tree.update(&FrameTransform::new("lidar", "base_link", translation(1.0, 0.0, 2.0)), 0);
+        tree
+    }
+
+    #[test]
+    fn test_chain_resolution() {
+        let tree = robot_tree();
+        // lidar 的 (1,0,2) 经 90° 偏航 → (0,1,2)，再叠加 base_link 与 odom 的平移
+        assert_translation(tree.lookup("map", "lidar", 1500).unwrap(), [101.0, 1.0, 2.0]);
+        assert_translation(tree.lookup("", "lidar", 1500).unwrap(), [101.0, 1.0, 2.0]);
+        assert_translation(tree.lookup("odom", "lidar", 2500).unwrap(), [6.0, 0.0, 2.0]);
+
+        // 反向查询：map 原点在 lidar 坐标系中
+        let map_in_lidar = tree.lookup("lidar", "map", 2500).unwrap();
+        assert_translation(map_in_lidar, [-106.0, 0.0, -2.0]);
+    }
+
+    #[test]
+    fn test_samples_by_time() {
+        let tree = robot_tree();
+        // 早于第一条位姿时取最早一条
+        assert_translation(tree.lookup("odom", "base_link", 0).unwrap(), [1.0, 0.0, 0.0]);
+        assert_translation(tree.lookup("odom", "base_link", 1999).unwrap(), [1.0, 0.0, 0.0]);
+        assert_translation(tree.lookup("odom", "base_link", 2000).unwrap(), [5.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_disconnected_and_cyclic_frames() {
+        let mut tree = robot_tree();
+        tree.update(&FrameTransform::new("camera", "rig", translation(0.0, 0.0, 1.0)), 0);
+        assert!(tree.lookup("map", "camera", 0).is_none());
+        assert_eq!(tree.frame_ids(), ["base_link", "camera", "lidar", "map", "odom", "rig"]);
+
+        tree.update(&FrameTransform::new("rig", "camera", translation(0.0, 0.0, 1.0)), 0);
+        assert!(tree.lookup("", "camera", 0).is_none());
+
+        tree.fixed_frame = "odom".to_string();
+        assert_translation(tree.resolve("", 0).unwrap(), [0.0, 0.0, 0.0]);
+        assert_translation(tree.resolve("lidar", 2000).unwrap(), [6.0, 0.0, 2.0]);
+    }
+}
@@ -0,0 +1,30 @@
+use std::collections::HashSet;
+
+/// Bevy Resource：按命名空间隐藏实体（图层显隐）
+///
+/// 只记录被隐藏的命名空间，新出现的命名空间默认可见。
+#[cfg_attr(feature = "graph", derive(bevy::prelude::Resource))]
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct NamespaceFilter {
+    pub hidden: HashSet<String>,
+}
+
+impl NamespaceFilter {
+    pub fn is_visible(&self, namespace: &str) -> bool {
+        !self.hidden.contains(namespace)
+    }
+
+    /// 设置命名空间的可见性
+    pub fn set_visible(&mut self, namespace: &str, visible: bool) {
+        if visible {
+            self.hidden.remove(namespace);
+        } else {
+            self.hidden.insert(namespace.to_string());
+        }
+    }
+
+    /// 显示全部命名空间
+    pub fn show_all(&mut self) {
+        self.hidden.clear();
+    }
+}
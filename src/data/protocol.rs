@@ -1,4 +1,4 @@
-use expto::rdmp::{CommandType, ExMesh, ExTransform, Unit, Tag, ex_object::UObject};
+use expto::rdmp::{CommandType, ExMesh, ExTransform, Lifetime, Unit, Tag, ex_object::UObject};
 
 use crate::data::frame::InptoTransform;
 
@@ -91,6 +91,43 @@ pub fn extract_tags(unit: &Unit) -> Vec<Tag> {
         .collect()
 }
 
+/// 从 Unit 提取生存期
+pub fn extract_lifetime(unit: &Unit) -> Option<Lifetime> {
+    unit.objects.iter().find_map(|obj| match &obj.u_object {
+        Some(UObject::Lifetime(lifetime)) => Some(*lifetime),
+        _ => None,
+    })
+}
+
+/// 从 Unit 提取命名空间（`String` 的提取已用于材质 ID，故单独实现）
+pub fn extract_namespace(unit: &Unit) -> Option<String> {
+    unit.objects.iter().find_map(|obj| match &obj.u_object {
+        Some(UObject::Namespace(ns)) => Some(ns.clone()),
+        _ => None,
+    })
+}
+
+/// 提取 Unit 的来源会话（无 stamp 时为空串）
+pub fn parse_session(unit: &Unit) -> &str {
+    unit.stamp.as_ref().map(|s| s.session_id.as_str()).unwrap_or("")
+}
+
+/// 会话作用域的实体 ID
+///
+/// 各客户端的本地 ID 都从 0 开始分配；服务端将本地 ID 与会话名 FNV-1a 哈希的高 32 位异或，
+/// 使不同会话的相同本地 ID 落在不同的命名空间。空会话（静态场景、本地文件）保持原 ID。
+pub fn scoped_entity_id(session: &str, local_id: u64) -> u64 {
+    if session.is_empty() {
+        return local_id;
+    }
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in session.bytes() {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    local_id ^ (hash & 0xFFFF_FFFF_0000_0000)
+}
+
 pub fn parse_command(unit: &Unit) -> Option<CommandType> {
     match unit.command {
         Some(cmd) => CommandType::try_from(cmd.u_command).ok(),
@@ -102,40 +139,19 @@ pub fn parse_timestamp(unit: &Unit) -> u64 {
     if let Some(stamp) = &unit.stamp { stamp.timestamp } else { 0 }
 }
 
-/// 将 ExTransform 转为内部 InptoTransform（欧拉角 → 四元数）
+/// 将 ExTransform 转为内部 InptoTransform（四元数优先，否则欧拉角 → 四元数）
 pub fn e2i_transform(transform: ExTransform) -> InptoTransform {
     InptoTransform::from(transform)
 }
 
-/// 将内部 InptoTransform 转为协议 ExTransform
+/// 将内部 InptoTransform 转为协议 ExTransform（写入四元数并回填 XYZ 欧拉角）
 pub fn i2e_transform(t: InptoTransform) -> ExTransform {
-    // 四元数 → 欧拉角 (XYZ)
-    let (rx, ry, rz) = quat_to_euler_xyz(t.rx, t.ry, t.rz, t.rw);
     ExTransform {
         x: t.tx, y: t.ty, z: t.tz,
-        rx, ry, rz,
         sx: t.sx, sy: t.sy, sz: t.sz,
+        ..Default::default()
     }
-}
-
-/// 四元数转欧拉角 (XYZ)
-fn quat_to_euler_xyz(qx: f32, qy: f32, qz: f32, qw: f32) -> (f32, f32, f32) {
-    let sinr_cosp = 2.0 * (qw * qx + qy * qz);
-    let cosr_cosp = 1.0 - 2.0 * (qx * qx + qy * qy);
-    let rx = sinr_cosp.atan2(cosr_cosp);
-
-    let sinp = 2.0 * (qw * qy - qz * qx);
-    let ry = if sinp.abs() >= 1.0 {
-        std::f32::consts::FRAC_PI_2.copysign(sinp)
-    } else {
-        sinp.asin()
-    };
-
-    let siny_cosp = 2.0 * (qw * qz + qx * qy);
-    let cosy_cosp = 1.0 - 2.0 * (qy * qy + qz * qz);
-    let rz = siny_cosp.atan2(cosy_cosp);
-
-    (rx, ry, rz)
+    .with_quaternion([t.rx, t.ry, t.rz, t.rw])
 }
 
 // ── 以下仅在 graph feature 下可用（依赖 bevy Transform）──
@@ -72,14 +72,14 @@ impl Default for FilterMode {
 }
 
 /// 简单模式的一条规则：指定集合中允许哪些值
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct SimpleFilterRule {
     pub collection: String,
     pub allowed_values: HashSet<String>,
 }
 
 /// 复杂模式表达式
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum FilterExpr {
     ShowIf { collection: String, value: String },
     HideIf { collection: String, value: String },
@@ -90,6 +90,7 @@ pub enum FilterExpr {
 
 /// Bevy Resource：渲染筛选规则
 #[cfg_attr(feature = "graph", derive(bevy::prelude::Resource))]
+#[derive(Clone, Debug, PartialEq)]
 pub struct TagFilter {
     pub enabled: bool,
     pub mode: FilterMode,
@@ -13,6 +13,12 @@ pub mod frame_renderer;
 pub mod scene;
 pub mod framerate;
 pub mod conversion;
+pub mod mesh_cache;
+pub mod point_splat;
+pub mod point_lod;
+pub mod line_ribbon;
+pub mod box_style;
+pub mod ground_grid;
 pub mod helpers;
 pub mod coord_system;
 
@@ -30,6 +36,11 @@ impl Plugin for RenderPlugin {
             .add_plugins(init::InitPlugin)
             .add_plugins(interaction::InteractionPlugin)
             .add_plugins(framerate::FrameRatePlugin)
+            .add_plugins(point_splat::PointSplatPlugin)
+            .add_plugins(point_lod::PointLodPlugin)
+            .add_plugins(line_ribbon::LineRibbonPlugin)
+            .add_plugins(box_style::BoxStylePlugin)
+            .add_plugins(ground_grid::GroundGridPlugin)
             .add_plugins(frame_renderer::FrameRendererPlugin);
     }
 }
@@ -0,0 +1,131 @@
+//! 包围盒的绘制样式（实体 / 线框 / 半透明 + 棱边）
+//!
+//! 实体填充的 `Cube` 会遮住检测框内的点。
+//! 样式优先级：UI 全局覆盖 > 协议 `style` 字段 > 材质（`bounding_box` 为半透明，`wireframe` 为线框）> 实体。
+//! 棱边以带状线绘制；半透明填充的颜色取自实体的通用材质并降低不透明度。
+
+use std::path::Path;
+
+use bevy::light::NotShadowCaster;
+use bevy::prelude::*;
+use expto::rdmp::ExMesh;
+use expto::rdmp::mesh::ex_mesh::UMesh;
+
+pub use expto::rdmp::BoxStyle;
+
+use crate::assets::materials::{GenericMaterial, generic_base_color};
+
+/// 半透明填充的最大不透明度
+pub const FILL_ALPHA: f32 = 0.2;
+
+/// Bevy Resource：包围盒样式的全局覆盖，`None` 时按各实体自身的设置
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq)]
+pub struct BoxStyleOverride(pub Option<BoxStyle>);
+
+/// 解析包围盒的最终样式；非包围盒返回 `None`
+pub fn resolve_box_style(mesh: &ExMesh, material: &str, global: BoxStyleOverride) -> Option<BoxStyle> {
+    let style = match &mesh.u_mesh {
+        Some(UMesh::Cube(cube)) => cube.style(),
+        Some(UMesh::OrientedBox(oriented_box)) => oriented_box.style(),
+        _ => return None,
+    };
+    if let Some(style) = global.0.filter(|s| *s != BoxStyle::Auto) {
+        return Some(style);
+    }
+    if style != BoxStyle::Auto {
+        return Some(style);
+    }
+    // 材质可以是别名或路径，按文件名匹配
+    let name = Path::new(material).file_stem().and_then(|s| s.to_str()).unwrap_or(material);
+    Some(match name {
+        "bounding_box" => BoxStyle::Translucent,
+        "wireframe" => BoxStyle::Wireframe,
+        _ => BoxStyle::Solid,
+    })
+}
+
+/// 样式的显示名称
+pub fn style_label(style: BoxStyle) -> &'static str {
+    match style {
+        BoxStyle::Auto => "按实体",
+        BoxStyle::Solid => "实体",
+        BoxStyle::Wireframe => "线框",
+        BoxStyle::Translucent => "半透明",
+    }
+}
+
+/// 半透明填充的颜色来源：实体的通用材质
+#[derive(Component)]
+pub struct TranslucentFill(pub Handle<GenericMaterial>);
+
+/// 半透明填充的包围盒所附带的棱边子实体
+#[derive(Component)]
+pub struct BoxEdgeLines;
+
+/// 半透明填充材质（颜色由 [`TranslucentFill`] 同步）
+pub fn translucent_material() -> StandardMaterial {
+    StandardMaterial {
+        base_color: Color::srgba(1.0, 1.0, 1.0, FILL_ALPHA),
+        alpha_mode: AlphaMode::Blend,
+        ..default()
+    }
+}
+
+/// 半透明填充实体的材质组件
+pub fn translucent_bundle(material: Handle<StandardMaterial>, source: Handle<GenericMaterial>) -> impl Bundle {
+    (MeshMaterial3d(material), TranslucentFill(source), NotShadowCaster)
+}
+
+pub struct BoxStylePlugin;
+
+impl Plugin for BoxStylePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<BoxStyleOverride>()
+            .add_systems(Update, sync_translucent_fills);
+    }
+}
+
+/// 将通用材质的颜色同步到半透明填充（不透明度不超过 [`FILL_ALPHA`]，仅在变化时写入）
+fn sync_translucent_fills(
+    query: Query<(&TranslucentFill, &MeshMaterial3d<StandardMaterial>)>,
+    generic_materials: Res<Assets<GenericMaterial>>,
+    mut standard_materials: ResMut<Assets<StandardMaterial>>,
+) {
+    for (source, material) in &query {
+        let Some(color) = generic_base_color(&generic_materials, &standard_materials, &source.0) else { continue };
+        let color = Color::from(color.with_alpha(color.alpha.min(FILL_ALPHA)));
+        if standard_materials.get(&material.0).is_some_and(|fill| fill.base_color == color) {
+            continue;
+        }
+        if let Some(fill) = standard_materials.get_mut(&material.0) {
+            fill.base_color = color;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use expto::rdmp::{Cube, OrientedBox};
+
+    #[test]
+    fn test_resolve_box_style() {
+        let cube = ExMesh::from(Cube::from([(0.0, 0.0, 0.0); 8]));
+        let none = BoxStyleOverride::default();
+        assert_eq!(resolve_box_style(&cube, "materials/mesh_types/cube.toml", none), Some(BoxStyle::Solid));
+        assert_eq!(resolve_box_style(&cube, "bounding_box", none), Some(BoxStyle::Translucent));
+        assert_eq!(resolve_box_style(&cube, "materials/ui/wireframe.toml", none), Some(BoxStyle::Wireframe));
+
+        // 协议字段优先于材质，全局覆盖优先于一切
+        let mut oriented_box = OrientedBox::from(([0.0; 3], [1.0; 3], [0.0, 0.0, 0.0, 1.0]));
+        oriented_box.set_style(BoxStyle::Wireframe);
+        assert_eq!(resolve_box_style(&ExMesh::from(oriented_box), "bounding_box", none), Some(BoxStyle::Wireframe));
+        oriented_box.set_style(BoxStyle::Solid);
+        assert_eq!(resolve_box_style(&ExMesh::from(oriented_box), "bounding_box", none), Some(BoxStyle::Solid));
+        let global = BoxStyleOverride(Some(BoxStyle::Translucent));
+        assert_eq!(resolve_box_style(&cube, "materials/ui/wireframe.toml", global), Some(BoxStyle::Translucent));
+
+        let sphere = ExMesh::from(expto::rdmp::Sphere { location: None, radius: 1.0 });
+        assert_eq!(resolve_box_style(&sphere, "bounding_box", global), None);
+    }
+}
@@ -1,60 +1,469 @@
+use bevy::asset::RenderAssetUsages;
+use bevy::mesh::Indices;
 use bevy::prelude::*;
 use bevy::render::render_resource::PrimitiveTopology;
-use expto::rdmp::{ExMesh, ExTransform};
+use expto::rdmp::{BoxStyle, Cube, ExMesh, ExTransform, Line, Polyline, TriangleMesh};
 
 /// 将协议网格转为 Bevy Mesh3d。
 pub fn proto_mesh_to_bevy(meshes: &mut Assets<Mesh>, proto_mesh: &ExMesh) -> Option<Mesh3d> {
-    use expto::rdmp::mesh::ex_mesh::UMesh;
-    let bevy_mesh = match &proto_mesh.u_mesh {
-        Some(UMesh::Sphere(sphere)) => Mesh3d(meshes.add(Sphere::new(sphere.radius))),
-        Some(UMesh::Point(_point)) => {
-            let mut mesh = Mesh::new(PrimitiveTopology::PointList, default());
-            mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, vec![[0.0, 0.0, 0.0]]);
-            Mesh3d(meshes.add(mesh))
+    let mesh = match mesh_shape(proto_mesh)? {
+        MeshShape::Primitive(primitive) => primitive.mesh(),
+        MeshShape::Custom(mesh) => mesh,
+    };
+    Some(Mesh3d(meshes.add(mesh)))
+}
+
+/// 协议网格对应的几何：参数化图元或自带顶点数据的网格
+pub enum MeshShape {
+    Primitive(Primitive),
+    Custom(Mesh),
+}
+
+/// 参数化图元 — 同参数的图元可共享同一网格资产（见 [`MeshCache`](crate::render::mesh_cache::MeshCache)）
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Primitive {
+    /// 单点（PointList）
+    Point,
+    Sphere { radius: f32 },
+    /// 沿 Y 轴
+    Cylinder { radius: f32, height: f32 },
+    /// 沿 Y 轴
+    Cone { radius: f32, height: f32 },
+    Cuboid { size: Vec3 },
+    /// 长方体的 12 条棱（带状线）
+    BoxEdges { half: Vec3 },
+}
+
+impl Primitive {
+    pub fn mesh(&self) -> Mesh {
+        match *self {
+            Primitive::Point => {
+                let mut mesh = Mesh::new(PrimitiveTopology::PointList, default());
+                mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, vec![[0.0, 0.0, 0.0]]);
+                mesh
+            }
+            Primitive::Sphere { radius } => Sphere::new(radius).into(),
+            Primitive::Cylinder { radius, height } => Cylinder::new(radius, height).into(),
+            Primitive::Cone { radius, height } => Cone::new(radius, height).into(),
+            Primitive::Cuboid { size } => Cuboid::from_size(size).into(),
+            Primitive::BoxEdges { half } => box_edges_mesh(half),
+        }
+    }
+
+    /// 单位尺寸图元及还原原尺寸所需的缩放（`单位网格 × 缩放 = 原网格`）
+    pub fn unit(&self) -> (Primitive, Vec3) {
+        match *self {
+            Primitive::Point => (Primitive::Point, Vec3::ONE),
+            Primitive::Sphere { radius } => (Primitive::Sphere { radius: 1.0 }, Vec3::splat(radius)),
+            Primitive::Cylinder { radius, height } => (Primitive::Cylinder { radius: 1.0, height: 1.0 }, Vec3::new(radius, height, radius)),
+            Primitive::Cone { radius, height } => (Primitive::Cone { radius: 1.0, height: 1.0 }, Vec3::new(radius, height, radius)),
+            Primitive::Cuboid { size } => (Primitive::Cuboid { size: Vec3::ONE }, size),
+            Primitive::BoxEdges { half } => (Primitive::BoxEdges { half: Vec3::splat(0.5) }, half * 2.0),
         }
+    }
+}
+
+/// 解析协议网格的几何；数据无效或退化时记录警告并返回 `None`
+pub fn mesh_shape(proto_mesh: &ExMesh) -> Option<MeshShape> {
+    use expto::rdmp::mesh::ex_mesh::UMesh;
+    let primitive = match &proto_mesh.u_mesh {
+        Some(UMesh::Sphere(sphere)) => Primitive::Sphere { radius: sphere.radius },
+        Some(UMesh::Point(_point)) => Primitive::Point,
         Some(UMesh::Line(line)) => {
-            let start = line.start.as_ref()?;
-            let end = line.end.as_ref()?;
-            let length = Vec3::new(end.x - start.x, end.y - start.y, end.z - start.z).length();
-            if length < 0.001 {
-                log::warn!("Line 长度退化 ({:.6})，跳过渲染。建议检查起终点是否重合。", length);
+            let Some((start, end)) = line_endpoints(line) else {
+                log::warn!("Line 缺少端点或长度退化 (<0.001)，跳过渲染。建议检查起终点是否重合。");
                 return None;
+            };
+            // 带状线网格直接由端点生成，宽度与虚线由 LineMaterial 在着色器中处理
+            return Some(MeshShape::Custom(crate::render::line_ribbon::line_ribbon_mesh(start, end)));
+        }
+        Some(UMesh::Cylinder(cylinder)) => Primitive::Cylinder { radius: cylinder.radius, height: cylinder.height },
+        Some(UMesh::Cone(cone)) => Primitive::Cone { radius: cone.radius, height: cone.height },
+        Some(UMesh::Cube(_) | UMesh::OrientedBox(_)) => {
+            let size = match box_size(proto_mesh)? {
+                Ok(size) => size,
+                Err(message) => {
+                    log::warn!("{}", message);
+                    return None;
+                }
+            };
+            let wireframe = match &proto_mesh.u_mesh {
+                Some(UMesh::Cube(cube)) => cube.style() == BoxStyle::Wireframe,
+                Some(UMesh::OrientedBox(oriented_box)) => oriented_box.style() == BoxStyle::Wireframe,
+                _ => false,
+            };
+            if wireframe {
+                Primitive::BoxEdges { half: size * 0.5 }
+            } else {
+                Primitive::Cuboid { size }
             }
-            Mesh3d(meshes.add(Cylinder::new(0.02, length)))
         }
-        Some(UMesh::Cylinder(cylinder)) => Mesh3d(meshes.add(Cylinder::new(cylinder.radius, cylinder.height))),
-        Some(UMesh::Cone(cone)) => Mesh3d(meshes.add(Cone::new(cone.radius, cone.height))),
+        Some(UMesh::TriangleMesh(triangle_mesh)) => return triangle_mesh_to_bevy(triangle_mesh).map(MeshShape::Custom),
+        Some(UMesh::Polyline(polyline)) => return polyline_to_bevy(polyline).map(MeshShape::Custom),
+        None => return None,
+    };
+    Some(MeshShape::Primitive(primitive))
+}
+
+/// 包围盒（Cube / OrientedBox）的尺寸；非包围盒返回 `None`，尺寸退化时返回说明原因的 `Err`
+pub fn box_size(proto_mesh: &ExMesh) -> Option<Result<Vec3, String>> {
+    use expto::rdmp::mesh::ex_mesh::UMesh;
+    match &proto_mesh.u_mesh {
         Some(UMesh::Cube(cube)) => {
             if cube.vertices.len() < 8 {
-                log::warn!("Cube 顶点数不足 ({}/8)，跳过渲染。", cube.vertices.len());
-                return None;
-            }
-            let mut min = [f32::MAX, f32::MAX, f32::MAX];
-            let mut max = [f32::MIN, f32::MIN, f32::MIN];
-            for v in &cube.vertices {
-                min[0] = min[0].min(v.x); min[1] = min[1].min(v.y); min[2] = min[2].min(v.z);
-                max[0] = max[0].max(v.x); max[1] = max[1].max(v.y); max[2] = max[2].max(v.z);
+                return Some(Err(format!("Cube 顶点数不足 ({}/8)，跳过渲染。", cube.vertices.len())));
             }
-            let w = max[0] - min[0];
-            let h = max[1] - min[1];
-            let d = max[2] - min[2];
-            if w < 0.001 || h < 0.001 || d < 0.001 {
-                log::warn!(
+            // 优先按角点恢复有向包围盒；角点不构成长方体时退化为 AABB
+            let size = cube_obb(cube)
+                .map(|obb| Vec3::new(obb.half_extents.x, obb.half_extents.y, obb.half_extents.z) * 2.0)
+                .unwrap_or_else(|| cube_aabb_size(cube));
+            if size.min_element() < 0.001 {
+                return Some(Err(format!(
                     "Cube 维度退化 (w={:.4}, h={:.4}, d={:.4})，跳过渲染。\
                      常见原因：点云共面/共线/单点聚类。建议改用 Sphere 或 Point。",
-                    w, h, d
-                );
-                return None;
+                    size.x, size.y, size.z
+                )));
             }
-            Mesh3d(meshes.add(Cuboid::new(w, h, d)))
+            Some(Ok(size))
         }
-        None => return None,
+        Some(UMesh::OrientedBox(oriented_box)) => {
+            let half = oriented_box.half_extents.as_ref()
+                .map(|h| Vec3::new(h.x.abs(), h.y.abs(), h.z.abs()))
+                .unwrap_or(Vec3::ZERO);
+            if half.min_element() < 0.0005 {
+                return Some(Err(format!("OrientedBox 半边长退化 ({:?})，跳过渲染。", half)));
+            }
+            Some(Ok(half * 2.0))
+        }
+        _ => None,
+    }
+}
+
+/// 线段端点（实体局部坐标）；端点缺失或长度退化时返回 `None`
+pub fn line_endpoints(line: &Line) -> Option<(Vec3, Vec3)> {
+    let start = line.start.as_ref().map(|p| Vec3::new(p.x, p.y, p.z))?;
+    let end = line.end.as_ref().map(|p| Vec3::new(p.x, p.y, p.z))?;
+    (start.distance(end) >= 0.001).then_some((start, end))
+}
+
+/// 网格自带的位姿 — 与实体变换组合后作为最终变换（`实体变换 * 网格位姿`）
+///
+/// - `OrientedBox`：中心与旋转
+/// - `Cube`：由角点恢复的旋转（平移仍由实体变换给出，客户端将其设为角点中心）
+/// - 其他类型：单位变换
+pub fn mesh_pose(proto_mesh: &ExMesh) -> Transform {
+    use expto::rdmp::mesh::ex_mesh::UMesh;
+    match &proto_mesh.u_mesh {
+        Some(UMesh::OrientedBox(oriented_box)) => {
+            let center = oriented_box.center.as_ref()
+                .map(|c| Vec3::new(c.x, c.y, c.z))
+                .unwrap_or(Vec3::ZERO);
+            let rotation = oriented_box.rotation
+                .map(|q| Quat::from_xyzw(q.x, q.y, q.z, q.w))
+                .filter(|q| q.length_squared() > 1e-8)
+                .map(Quat::normalize)
+                .unwrap_or(Quat::IDENTITY);
+            Transform::from_translation(center).with_rotation(rotation)
+        }
+        Some(UMesh::Cube(cube)) => {
+            let rotation = cube_obb(cube)
+                .map(|obb| Quat::from_xyzw(obb.rotation.i, obb.rotation.j, obb.rotation.k, obb.rotation.w))
+                .unwrap_or(Quat::IDENTITY);
+            Transform::from_rotation(rotation)
+        }
+        _ => Transform::IDENTITY,
+    }
+}
+
+fn cube_obb(cube: &Cube) -> Option<redra_geo::Obb> {
+    let corners: Vec<_> = cube.vertices.iter()
+        .map(|v| nalgebra::Vector3::new(v.x, v.y, v.z))
+        .collect();
+    redra_geo::obb_from_corners(&corners)
+}
+
+fn cube_aabb_size(cube: &Cube) -> Vec3 {
+    let mut min = Vec3::splat(f32::MAX);
+    let mut max = Vec3::splat(f32::MIN);
+    for v in &cube.vertices {
+        let p = Vec3::new(v.x, v.y, v.z);
+        min = min.min(p);
+        max = max.max(p);
+    }
+    max - min
+}
+
+/// 轴对齐长方体的 12 条棱（带状线，由 `LineMaterial` 绘制），中心在原点
+fn box_edges_mesh(half: Vec3) -> Mesh {
+    let corner = |i: usize| Vec3::new(
+        if i & 1 == 0 { -half.x } else { half.x },
+        if i & 2 == 0 { -half.y } else { half.y },
+        if i & 4 == 0 { -half.z } else { half.z },
+    );
+    // 角点编号的二进制位对应 x/y/z 符号，相差一位的角点之间是一条棱
+    let mut edges = Vec::with_capacity(12);
+    for i in 0..8usize {
+        for bit in [1usize, 2, 4] {
+            if i & bit == 0 {
+                edges.push((corner(i), corner(i | bit)));
+            }
+        }
+    }
+    crate::render::line_ribbon::ribbon_mesh(&edges)
+}
+
+/// 将协议三角网格转为 Bevy Mesh — 缺少法线时自动计算（有索引为平滑法线，否则为面法线）
+pub fn triangle_mesh_to_bevy(triangle_mesh: &TriangleMesh) -> Option<Mesh> {
+    let vertex_count = triangle_mesh.vertices.len();
+    let index_count = if triangle_mesh.indices.is_empty() { vertex_count } else { triangle_mesh.indices.len() };
+    if vertex_count < 3 || index_count % 3 != 0 {
+        log::warn!(
+            "TriangleMesh 数据无效 (顶点 {}, 索引 {})，跳过渲染。索引数必须是 3 的倍数。",
+            vertex_count, index_count
+        );
+        return None;
+    }
+    if let Some(&bad) = triangle_mesh.indices.iter().find(|&&i| i as usize >= vertex_count) {
+        log::warn!("TriangleMesh 索引 {} 越界 (顶点数 {})，跳过渲染。", bad, vertex_count);
+        return None;
+    }
+
+    let positions: Vec<[f32; 3]> = triangle_mesh.vertices.iter().map(|p| [p.x, p.y, p.z]).collect();
+    let mut mesh = Mesh::new(PrimitiveTopology::TriangleList, RenderAssetUsages::default())
+        .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+
+    if triangle_mesh.colors.len() == vertex_count {
+        let colors: Vec<[f32; 4]> = triangle_mesh.colors.iter().map(|c| [c.r, c.g, c.b, c.a]).collect();
+        mesh.insert_attribute(Mesh::ATTRIBUTE_COLOR, colors);
+    } else if !triangle_mesh.colors.is_empty() {
+        log::warn!("TriangleMesh 颜色数 ({}) 与顶点数 ({}) 不一致，忽略顶点颜色。", triangle_mesh.colors.len(), vertex_count);
+    }
+
+    if !triangle_mesh.indices.is_empty() {
+        mesh.insert_indices(Indices::U32(triangle_mesh.indices.clone()));
+    }
+
+    if triangle_mesh.normals.len() == vertex_count {
+        let normals: Vec<[f32; 3]> = triangle_mesh.normals.iter().map(|n| [n.x, n.y, n.z]).collect();
+        mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+    } else {
+        if !triangle_mesh.normals.is_empty() {
+            log::warn!("TriangleMesh 法线数 ({}) 与顶点数 ({}) 不一致，改为自动计算。", triangle_mesh.normals.len(), vertex_count);
+        }
+        mesh.compute_normals();
+    }
+    Some(mesh)
+}
+
+/// 将协议折线转为带状线网格 — 相邻顶点各成一段（闭合折线追加末点到首点的一段），
+/// 线宽由 `LineMaterial` 处理；逐顶点颜色写入各段端点
+pub fn polyline_to_bevy(polyline: &Polyline) -> Option<Mesh> {
+    let point_count = polyline.points.len();
+    if point_count < 2 {
+        log::warn!("Polyline 顶点数不足 ({}/2)，跳过渲染。", point_count);
+        return None;
+    }
+
+    let points: Vec<Vec3> = polyline.points.iter().map(|p| Vec3::new(p.x, p.y, p.z)).collect();
+    let colors: Option<Vec<[f32; 4]>> = if polyline.colors.len() == point_count {
+        Some(polyline.colors.iter().map(|c| [c.r, c.g, c.b, c.a]).collect())
+    } else {
+        if !polyline.colors.is_empty() {
+            log::warn!("Polyline 颜色数 ({}) 与顶点数 ({}) 不一致，忽略顶点颜色。", polyline.colors.len(), point_count);
+        }
+        None
     };
-    Some(bevy_mesh)
+    let mut pairs: Vec<(usize, usize)> = (1..point_count).map(|i| (i - 1, i)).collect();
+    if polyline.closed && point_count > 2 {
+        pairs.push((point_count - 1, 0));
+    }
+
+    let segments: Vec<(Vec3, Vec3)> = pairs.iter().map(|&(a, b)| (points[a], points[b])).collect();
+    let mut mesh = crate::render::line_ribbon::ribbon_mesh(&segments);
+    if let Some(colors) = colors {
+        // 与 ribbon_mesh 的顶点顺序一致：每段起点两个、终点两个
+        let vertex_colors: Vec<[f32; 4]> = pairs.iter()
+            .flat_map(|&(a, b)| [colors[a], colors[a], colors[b], colors[b]])
+            .collect();
+        mesh.insert_attribute(Mesh::ATTRIBUTE_COLOR, vertex_colors);
+    }
+    Some(mesh)
 }
 
 pub fn proto_transform_to_bevy(transform: &ExTransform) -> Transform {
     Transform::from_translation(Vec3::new(transform.x, transform.y, transform.z))
-        .with_rotation(Quat::from_euler(EulerRot::XYZ, transform.rx, transform.ry, transform.rz))
+        .with_rotation(Quat::from_array(transform.quaternion().to_array()))
         .with_scale(Vec3::new(transform.sx, transform.sy, transform.sz))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 同一 ExTransform 经各条转换路径得到的旋转（bevy 四元数）
+    fn rotations_through_all_paths(et: ExTransform) -> Vec<(&'static str, Quat)> {
+        use crate::data::frame::InptoTransform;
+        use crate::data::protocol::{e2i_transform, i2e_transform};
+
+        let inpto = Transform::from(InptoTransform::from(et)).rotation;
+        let round_trip = proto_transform_to_bevy(&i2e_transform(e2i_transform(et))).rotation;
+        let geo = redra_geo::convert::transform3_to_extransform(&redra_geo::convert::extransform_to_transform3(&et));
+        let q = redra_geo::convert::extransform_to_transform3(&et).rotation;
+        vec![
+            ("proto_transform_to_bevy", proto_transform_to_bevy(&et).rotation),
+            ("InptoTransform", inpto),
+            ("i2e/e2i 往返", round_trip),
+            ("redra_geo Transform3", Quat::from_xyzw(q.i, q.j, q.k, q.w)),
+            ("redra_geo 往返", proto_transform_to_bevy(&geo).rotation),
+        ]
+    }
+
+    fn assert_same_orientation(et: ExTransform, expected: Quat) {
+        for (path, q) in rotations_through_all_paths(et) {
+            assert!(q.angle_between(expected) < 1e-4, "{} 旋转不一致: {:?} vs {:?}", path, q, expected);
+        }
+    }
+
+    #[test]
+    fn test_euler_orientation_consistent_across_paths() {
+        let et = ExTransform { rx: 0.4, ry: -1.1, rz: 2.3, ..ExTransform::identity() };
+        assert_same_orientation(et, Quat::from_euler(EulerRot::XYZ, 0.4, -1.1, 2.3));
+    }
+
+    #[test]
+    fn test_quaternion_orientation_consistent_across_paths() {
+        let expected = Quat::from_axis_angle(Vec3::new(1.0, 2.0, -0.5).normalize(), 2.7);
+        // 四元数优先于（故意写错的）欧拉角
+        let et = ExTransform {
+            rx: 1.0, ry: 1.0, rz: 1.0,
+            rotation: Some(expected.to_array().into()),
+            ..ExTransform::identity()
+        };
+        assert_same_orientation(et, expected);
+    }
+
+    fn quad(indices: Vec<u32>) -> TriangleMesh {
+        TriangleMesh::from((
+            vec![[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [1.0, 0.0, 1.0], [0.0, 0.0, 1.0]],
+            indices,
+        ))
+    }
+
+    #[test]
+    fn test_triangle_mesh_computes_normals() {
+        let mesh = triangle_mesh_to_bevy(&quad(vec![0, 2, 1, 0, 3, 2])).unwrap();
+        assert_eq!(mesh.count_vertices(), 4);
+        assert_eq!(mesh.indices().unwrap().len(), 6);
+
+        let Some(bevy::mesh::VertexAttributeValues::Float32x3(normals)) = mesh.attribute(Mesh::ATTRIBUTE_NORMAL) else {
+            panic!("缺少法线");
+        };
+        for n in normals {
+            assert!((n[1] - 1.0).abs() < 1e-5, "法线应朝 +Y: {:?}", n);
+        }
+    }
+
+    #[test]
+    fn test_triangle_mesh_vertex_colors() {
+        let mut triangle_mesh = quad(vec![0, 2, 1]);
+        triangle_mesh.colors = vec![[1.0, 0.0, 0.0, 1.0].into(); 4];
+        let mesh = triangle_mesh_to_bevy(&triangle_mesh).unwrap();
+        assert!(mesh.attribute(Mesh::ATTRIBUTE_COLOR).is_some());
+    }
+
+    #[test]
+    fn test_triangle_mesh_rejects_invalid_indices() {
+        assert!(triangle_mesh_to_bevy(&quad(vec![0, 1])).is_none());
+        assert!(triangle_mesh_to_bevy(&quad(vec![0, 1, 4])).is_none());
+    }
+
+    #[test]
+    fn test_polyline_ribbon_segments() {
+        let mut polyline = Polyline::from(vec![[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [1.0, 1.0, 0.0]]);
+        let mesh = polyline_to_bevy(&polyline).unwrap();
+        assert_eq!(mesh.primitive_topology(), PrimitiveTopology::TriangleList);
+        // 2 段，每段 4 个顶点
+        assert_eq!(mesh.count_vertices(), 8);
+        assert!(mesh.attribute(Mesh::ATTRIBUTE_COLOR).is_none());
+
+        polyline.closed = true;
+        polyline.colors = vec![[1.0, 0.0, 0.0, 1.0].into(), [0.0, 1.0, 0.0, 1.0].into(), [0.0, 0.0, 1.0, 1.0].into()];
+        let mesh = polyline_to_bevy(&polyline).unwrap();
+        assert_eq!(mesh.count_vertices(), 12);
+        let Some(bevy::mesh::VertexAttributeValues::Float32x4(colors)) = mesh.attribute(Mesh::ATTRIBUTE_COLOR) else {
+            panic!("缺少顶点颜色");
+        };
+        // 闭合段从末点回到首点
+        assert_eq!(colors[8], [0.0, 0.0, 1.0, 1.0]);
+        assert_eq!(colors[11], [1.0, 0.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn test_polyline_rejects_single_point() {
+        assert!(polyline_to_bevy(&Polyline::from(vec![[0.0, 0.0, 0.0]])).is_none());
+    }
+
+    fn rotated_cube() -> (Cube, Quat) {
+        let rotation = Quat::from_rotation_y(0.5);
+        let half = Vec3::new(2.0, 0.5, 1.0);
+        let vertices = (0..8).map(|i| {
+            let local = Vec3::new(
+                if i & 1 == 0 { -half.x } else { half.x },
+                if i & 2 == 0 { -half.y } else { half.y },
+                if i & 4 == 0 { -half.z } else { half.z },
+            );
+            let p = Vec3::new(5.0, 0.0, -3.0) + rotation * local;
+            expto::rdmp::Point { x: p.x, y: p.y, z: p.z }
+        }).collect();
+        (Cube { vertices, ..Default::default() }, rotation)
+    }
+
+    #[test]
+    fn test_cube_respects_corner_orientation() {
+        use bevy::camera::primitives::MeshAabb;
+
+        let (cube, rotation) = rotated_cube();
+        let mesh = ExMesh::from(cube);
+
+        let mut meshes = Assets::<Mesh>::default();
+        let handle = proto_mesh_to_bevy(&mut meshes, &mesh).unwrap();
+        let aabb = meshes.get(&handle.0).unwrap().compute_aabb().unwrap();
+        let size = Vec3::from(aabb.half_extents) * 2.0;
+        assert!((size - Vec3::new(4.0, 1.0, 2.0)).length() < 1e-4, "尺寸应为有向盒尺寸: {:?}", size);
+
+        let pose = mesh_pose(&mesh);
+        assert!(pose.rotation.angle_between(rotation) < 1e-4);
+        assert!(pose.translation.length() < 1e-6);
+    }
+
+    #[test]
+    fn test_oriented_box_pose_and_wireframe() {
+        use bevy::camera::primitives::MeshAabb;
+
+        let rotation = Quat::from_rotation_z(0.3);
+        let mut oriented_box = expto::rdmp::OrientedBox::from(([1.0, 2.0, 3.0], [1.0, 0.5, 0.25], rotation.to_array()));
+        let pose = mesh_pose(&ExMesh::from(oriented_box));
+        assert_eq!(pose.translation, Vec3::new(1.0, 2.0, 3.0));
+        assert!(pose.rotation.angle_between(rotation) < 1e-6);
+
+        oriented_box.set_style(BoxStyle::Wireframe);
+        let mut meshes = Assets::<Mesh>::default();
+        let handle = proto_mesh_to_bevy(&mut meshes, &ExMesh::from(oriented_box)).unwrap();
+        let mesh = meshes.get(&handle.0).unwrap();
+        // 12 条棱各一段带状线
+        assert_eq!(mesh.count_vertices(), 48);
+        assert_eq!(mesh.indices().unwrap().len(), 72);
+        let aabb = mesh.compute_aabb().unwrap();
+        assert!((Vec3::from(aabb.half_extents) - Vec3::new(1.0, 0.5, 0.25)).length() < 1e-6);
+    }
+
+    #[test]
+    fn test_triangle_mesh_unindexed() {
+        let triangle_mesh = TriangleMesh::from((vec![[0.0, 0.0, 0.0], [0.0, 0.0, 1.0], [1.0, 0.0, 0.0]], Vec::new()));
+        let mut meshes = Assets::<Mesh>::default();
+        let handle = proto_mesh_to_bevy(&mut meshes, &ExMesh::from(triangle_mesh)).unwrap();
+        let mesh = meshes.get(&handle.0).unwrap();
+        assert!(mesh.indices().is_none());
+        assert!(mesh.attribute(Mesh::ATTRIBUTE_NORMAL).is_some());
+    }
+}
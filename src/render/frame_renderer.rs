@@ -1,25 +1,146 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
 
+use bevy::ecs::system::{EntityCommands, SystemParam};
+use bevy::light::NotShadowCaster;
 use bevy::prelude::*;
 use bevy::render::render_resource::PrimitiveTopology;
 use expto::rdmp::mesh::ex_mesh::UMesh;
+use prost::Message;
 
-use crate::data::frame::{FrameManager, Inpto, InptoTransform};
+use crate::data::frame::{FrameManager, Inpto};
+use crate::data::namespace::NamespaceFilter;
 use crate::data::tag::{TagFilter, TagRegistry, entity_passes_filter};
-use crate::assets::materials::MaterialManager;
+use crate::assets::materials::{GenericMaterial, MaterialManager};
+use crate::render::box_style::{BoxEdgeLines, BoxStyle, BoxStyleOverride, TranslucentFill, resolve_box_style, translucent_bundle, translucent_material};
+use crate::render::conversion::{Primitive, box_size, line_endpoints};
+use crate::render::line_ribbon::{LineColorSource, LineMaterial, line_bundle};
+use crate::render::mesh_cache::MeshCache;
+use crate::render::point_lod::{LOD_MIN_POINTS, PointBudget, PointLod};
+use crate::render::point_splat::{PointSplatMaterial, PointStyle, splat_bundle, splat_mesh};
 use crate::render::interaction::picking::PickableEntity;
-use crate::render::coord_system::{CoordSystem, apply_coord_system};
+use crate::render::coord_system::{CoordSystem, apply_coord_system, apply_handedness};
 use crate::ui::file_manager::FileOpSet;
 
 /// 隐藏标记组件
 #[derive(Component, Default)]
 pub struct Hidden;
 
+/// 实体的 Tag 文本（标签显示用），随数据层 Tag 变化就地更新
+#[derive(Component, Debug, Clone, Default, PartialEq)]
+pub struct EntityLabels(pub Vec<String>);
+
+/// 实体内容指纹：与上次同步时比对，变化的部分就地修补，无需重建实体
+#[derive(Debug, Clone, PartialEq)]
+struct EntityContent {
+    /// 网格的编码哈希
+    mesh: u64,
+    material: String,
+    labels: Vec<String>,
+    /// 网格是否为单位图元（尺寸由缩放表达）
+    unit_scale: bool,
+    look: Look,
+}
+
+impl EntityContent {
+    fn of(inpto: &Inpto, unit_scale: bool, box_style: BoxStyleOverride) -> Self {
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        inpto.mesh.encode_to_vec().hash(&mut hasher);
+        Self {
+            mesh: hasher.finish(),
+            material: inpto.material_path(),
+            labels: inpto.tags.iter().map(|t| t.text.clone()).collect(),
+            unit_scale,
+            look: Look::of(inpto, box_style),
+        }
+    }
+}
+
+/// 实体的绘制方式：决定网格形态与材质类型
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Look {
+    /// 通用材质
+    Standard,
+    /// 带状线：线段、折线，或线框样式的包围盒（棱边网格）
+    Ribbon,
+    /// 半透明填充，棱边由 [`BoxEdgeLines`] 子实体绘制
+    Translucent,
+}
+
+impl Look {
+    /// 退化的线段与包围盒由备用网格绘制，使用通用材质
+    fn of(inpto: &Inpto, box_style: BoxStyleOverride) -> Self {
+        if ribbon_material(inpto).is_some() {
+            return Look::Ribbon;
+        }
+        let Some(Ok(_)) = box_size(&inpto.mesh) else { return Look::Standard };
+        match resolve_box_style(&inpto.mesh, &inpto.material_path(), box_style) {
+            Some(BoxStyle::Wireframe) => Look::Ribbon,
+            Some(BoxStyle::Translucent) => Look::Translucent,
+            _ => Look::Standard,
+        }
+    }
+}
+
+/// 上次渲染时的输入快照；输入均未变化时跳过整帧重扫
+#[derive(Clone, PartialEq)]
+struct RenderInputs {
+    frame_index: usize,
+    revision: u64,
+    fixed_frame: String,
+    coord: CoordSystem,
+    namespace_filter: NamespaceFilter,
+    tag_filter: TagFilter,
+    /// 点云组是否以 splat 绘制
+    point_splat: bool,
+    box_style: BoxStyleOverride,
+}
+
+impl RenderInputs {
+    fn capture(frame_manager: &FrameManager, coord: CoordSystem, namespace_filter: &NamespaceFilter, tag_filter: &TagFilter, point_splat: bool, box_style: BoxStyleOverride) -> Self {
+        Self {
+            frame_index: frame_manager.current_frame_index(),
+            revision: frame_manager.revision(),
+            fixed_frame: frame_manager.tf.fixed_frame.clone(),
+            coord,
+            namespace_filter: namespace_filter.clone(),
+            tag_filter: tag_filter.clone(),
+            point_splat,
+            box_style,
+        }
+    }
+
+    /// 与当前输入逐项比对（不克隆）
+    fn is_current(&self, frame_manager: &FrameManager, coord: CoordSystem, namespace_filter: &NamespaceFilter, tag_filter: &TagFilter, point_splat: bool, box_style: BoxStyleOverride) -> bool {
+        self.frame_index == frame_manager.current_frame_index()
+            && self.revision == frame_manager.revision()
+            && self.fixed_frame == frame_manager.tf.fixed_frame
+            && self.coord == coord
+            && self.namespace_filter == *namespace_filter
+            && self.tag_filter == *tag_filter
+            && self.point_splat == point_splat
+            && self.box_style == box_style
+    }
+}
+
 /// 单个点云组的缓存
 struct PointGroupCache {
     entity: Entity,
     mesh_handle: Handle<Mesh>,
+    /// 全分辨率点（脏检查与拾取用）
     cached_positions: Vec<[f32; 3]>,
+    /// 是否为 splat 网格（与 PointList 网格拓扑不同，切换时重建）
+    splat: bool,
+    /// 大点云的 LOD 及当前绘制的级别
+    lod: Option<PointLod>,
+    level: usize,
+}
+
+impl PointGroupCache {
+    /// 当前绘制的点数
+    fn rendered_points(&self) -> usize {
+        self.lod.as_ref().map_or(self.cached_positions.len(), |lod| lod.level(self.level).len())
+    }
 }
 
 /// 实体映射资源
@@ -27,12 +148,47 @@ struct PointGroupCache {
 pub struct EntityMap {
     pub map: HashMap<u64, Entity>,
     point_groups: HashMap<String, PointGroupCache>,
+    /// 已同步到 bevy `ChildOf` 的父子关系（子 → 父）
+    parents: HashMap<u64, u64>,
+    /// 各实体上次同步的内容指纹
+    content: HashMap<u64, EntityContent>,
+    /// 各实体网格需叠加的缩放（单位图元还原尺寸）
+    mesh_scales: HashMap<u64, Vec3>,
+    /// 因筛选而隐藏（保留未销毁）的实体
+    hidden: HashSet<u64>,
+    /// 半透明包围盒的棱边子实体
+    box_edges: HashMap<u64, Entity>,
+    /// 上次渲染的输入；`clear` 后置空以强制重新渲染
+    rendered: Option<RenderInputs>,
 }
 
 impl EntityMap {
     pub fn clear(&mut self) {
         self.map.clear();
         self.point_groups.clear();
+        self.parents.clear();
+        self.content.clear();
+        self.mesh_scales.clear();
+        self.hidden.clear();
+        self.box_edges.clear();
+        self.rendered = None;
+    }
+
+    /// 各点云组的全分辨率点（按材质），不受 LOD 影响
+    pub fn point_group_positions(&self) -> impl Iterator<Item = (&str, &[[f32; 3]])> {
+        self.point_groups.iter().map(|(material, cache)| (material.as_str(), cache.cached_positions.as_slice()))
+    }
+
+    /// 实体网格叠加的缩放（单位图元还原尺寸），未记录时为 1
+    pub fn mesh_scale(&self, entity_id: u64) -> Vec3 {
+        self.mesh_scales.get(&entity_id).copied().unwrap_or(Vec3::ONE)
+    }
+
+    /// 点云组当前绘制的点数与全分辨率点数
+    pub fn point_counts(&self) -> (usize, usize) {
+        self.point_groups.values().fold((0, 0), |(rendered, total), cache| {
+            (rendered + cache.rendered_points(), total + cache.cached_positions.len())
+        })
     }
 
     /// 取出所有点云组实体（用于外部 despawn）
@@ -47,27 +203,87 @@ pub struct FrameRendererPlugin;
 impl Plugin for FrameRendererPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<EntityMap>()
+            .init_resource::<MeshCache>()
             .add_systems(Update, (
                 ApplyDeferred,
                 render_current_frame,
+                update_point_lod,
             ).chain().after(FileOpSet));
     }
 }
 
+/// 实体网格与材质的来源
+#[derive(SystemParam)]
+struct EntityAssets<'w> {
+    meshes: ResMut<'w, Assets<Mesh>>,
+    mesh_cache: ResMut<'w, MeshCache>,
+    asset_server: Res<'w, AssetServer>,
+    material_manager: Res<'w, MaterialManager>,
+    splat_materials: ResMut<'w, Assets<PointSplatMaterial>>,
+    line_materials: ResMut<'w, Assets<LineMaterial>>,
+    standard_materials: ResMut<'w, Assets<StandardMaterial>>,
+    point_style: Res<'w, PointStyle>,
+    point_budget: Res<'w, PointBudget>,
+    box_style: Res<'w, BoxStyleOverride>,
+}
+
+impl EntityAssets<'_> {
+    /// 实体网格（同参数图元共享）及需叠加的缩放，转换失败时使用备用球体
+    ///
+    /// 包围盒的网格由绘制方式决定：带状线为 12 条棱，其余为长方体。
+    fn mesh(&mut self, inpto: &Inpto, entity_id: u64, unit_scale: bool, look: Look) -> (Mesh3d, Vec3) {
+        if let Some(Ok(size)) = box_size(&inpto.mesh) {
+            let primitive = if look == Look::Ribbon {
+                Primitive::BoxEdges { half: size * 0.5 }
+            } else {
+                Primitive::Cuboid { size }
+            };
+            return self.mesh_cache.primitive(&mut self.meshes, primitive, unit_scale);
+        }
+        self.mesh_cache.mesh(&mut self.meshes, &inpto.mesh, unit_scale).unwrap_or_else(|| {
+            let mesh_type = match &inpto.mesh.u_mesh {
+                Some(umesh) => format!("{:?}", umesh),
+                None => "None".to_string(),
+            };
+            log::warn!(
+                "网格转换失败，使用备用球体 (实体 {}, 类型: {})。\
+                 支持的类型: Point, Sphere, Cylinder, Cone, Line(长度>0.001), Cube(维度>0.001), \
+                 TriangleMesh, Polyline(≥2 点), OrientedBox",
+                entity_id, mesh_type
+            );
+            self.mesh_cache.primitive(&mut self.meshes, Primitive::Sphere { radius: 0.1 }, unit_scale)
+        })
+    }
+
+    fn material(&self, material: &str) -> Handle<GenericMaterial> {
+        self.material_manager.load_generic_material(material, &self.asset_server)
+    }
+}
+
 /// 渲染当前帧的所有实体
+///
+/// 仅在帧索引、帧数据修订号、筛选条件、坐标系或 Tag 注册表变化时重扫，静止场景每帧几乎无开销。
 fn render_current_frame(
     mut commands: Commands,
-    mut meshes: ResMut<Assets<Mesh>>,
-    asset_server: Res<AssetServer>,
-    material_manager: Res<MaterialManager>,
+    mut assets: EntityAssets,
     frame_manager: Res<FrameManager>,
     tag_filter: Res<TagFilter>,
     tag_registry: Res<TagRegistry>,
+    namespace_filter: Res<NamespaceFilter>,
     handedness: Res<CoordSystem>,
     mut entity_map: ResMut<EntityMap>,
     pickable_check_query: Query<(Entity, &Name, &PickableEntity)>,
     hidden_query: Query<(), With<Hidden>>,
 ) {
+    let point_splat = assets.point_style.is_splat();
+    let box_style = *assets.box_style;
+    if !tag_registry.is_changed() && entity_map.rendered.as_ref()
+        .is_some_and(|r| r.is_current(&frame_manager, *handedness, &namespace_filter, &tag_filter, point_splat, box_style))
+    {
+        return;
+    }
+    entity_map.rendered = Some(RenderInputs::capture(&frame_manager, *handedness, &namespace_filter, &tag_filter, point_splat, box_style));
+
     let Some(keyframe) = frame_manager.get_current_keyframe() else {
         log::debug!("当前无可用帧数据");
         return;
@@ -78,19 +294,41 @@ fn render_current_frame(
     let mut point_groups: HashMap<String, Vec<Vec3>> = HashMap::new();
     let mut non_point_ids: HashMap<u64, &Inpto> = HashMap::new();
 
+    // 层级关系：子 → 父；有子实体的点不参与聚合，以便挂载子实体
+    let parents: HashMap<u64, u64> = keyframe.parent_map();
+    let has_children: HashSet<u64> = parents.values().copied().collect();
+    // 被筛选掉的实体：已创建的隐藏保留，未创建的不创建
+    let mut filtered: HashSet<u64> = HashSet::new();
+    // 根实体所在坐标系 → 固定坐标系（子实体随父实体，不单独解析）
+    let mut frame_offsets: HashMap<u64, Transform> = HashMap::new();
+
     for (entity_id, inpto) in keyframe.iter_entities() {
-        // Tag 筛选：不通过则跳过（不渲染不创建）
-        if !entity_passes_filter(&inpto.tags, &tag_filter, &tag_registry) {
+        // Tag / 命名空间筛选：自身或任一祖先不通过则跳过
+        let mut ancestors = std::iter::successors(Some(entity_id), |id| parents.get(id).copied());
+        if !ancestors.all(|id| keyframe.get_entity(id).is_some_and(|e| {
+            namespace_filter.is_visible(&e.namespace) && entity_passes_filter(&e.tags, &tag_filter, &tag_registry)
+        })) {
+            filtered.insert(entity_id);
             continue;
         }
 
-        if let Some(UMesh::Point(p)) = &inpto.mesh.u_mesh {
+        if !parents.contains_key(&entity_id) && !inpto.frame_id.is_empty() {
+            let Some(offset) = frame_manager.tf.resolve(&inpto.frame_id, keyframe.timestamp) else {
+                log::debug!("实体 {} 的坐标系 '{}' 无法变换到固定坐标系 '{}'，跳过渲染", entity_id, inpto.frame_id, frame_manager.tf.fixed_frame);
+                continue;
+            };
+            frame_offsets.insert(entity_id, Transform::from(offset));
+        }
+
+        let in_hierarchy = parents.contains_key(&entity_id) || has_children.contains(&entity_id);
+        if let (Some(UMesh::Point(p)), false) = (&inpto.mesh.u_mesh, in_hierarchy) {
             let material = if inpto.material.is_empty() {
                 "materials/mesh_types/point.toml".to_string()
             } else {
                 inpto.material_path()
             };
-            let pos = apply_coord_system(Transform::from_xyz(p.x, p.y, p.z), *handedness).translation;
+            let offset = frame_offsets.get(&entity_id).copied().unwrap_or_default();
+            let pos = apply_coord_system(offset * Transform::from_xyz(p.x, p.y, p.z), *handedness).translation;
             point_groups.entry(material).or_default().push(Vec3::new(pos.x, pos.y, pos.z));
         } else {
             non_point_ids.insert(entity_id, inpto);
@@ -104,21 +342,36 @@ fn render_current_frame(
         log::debug!("帧 {} 包含 {} 个 Point ({} 组) + {} 个非 Point 实体", frame_manager.current_frame_index(), total_points, point_groups.len(), non_point_ids.len());
     }
 
-    cleanup_removed_entities(&mut commands, &non_point_ids, &mut entity_map.map);
-
     for (&entity_id, inpto) in &non_point_ids {
+        let parent = parents.get(&entity_id).and_then(|p| non_point_ids.get(p)).copied();
+        let frame = frame_offsets.get(&entity_id).copied().unwrap_or_default();
+        let bevy_transform = render_transform(inpto, parent, frame, *handedness);
+        // 有子实体时按原尺寸生成网格，避免缩放传递给子实体
+        let unit_scale = !has_children.contains(&entity_id);
         if let Some(&entity) = entity_map.map.get(&entity_id) {
-            update_entity_transform(&mut commands, entity, &inpto.transform, *handedness, &hidden_query);
+            patch_entity_content(&mut commands, &mut assets, &mut entity_map, entity_id, inpto, unit_scale);
+            let scale = entity_map.mesh_scales.get(&entity_id).copied().unwrap_or(Vec3::ONE);
+            update_entity_transform(&mut commands, entity, bevy_transform * Transform::from_scale(scale), &hidden_query);
         } else {
-            let bevy_transform = bevy::transform::components::Transform::from(inpto.transform);
-            let new_entity = spawn_entity_from_inpto(&mut commands, &mut meshes, &asset_server, &material_manager, inpto, bevy_transform, entity_id, *handedness);
+            let content = EntityContent::of(inpto, unit_scale, box_style);
+            let look = content.look;
+            let (mesh, scale) = assets.mesh(inpto, entity_id, unit_scale, look);
+            let new_entity = spawn_entity_from_inpto(&mut commands, &mut assets, inpto, mesh, bevy_transform * Transform::from_scale(scale), entity_id, look);
             entity_map.map.insert(entity_id, new_entity);
+            entity_map.content.insert(entity_id, content);
+            entity_map.mesh_scales.insert(entity_id, scale);
+            sync_box_edges(&mut commands, &mut assets, &mut entity_map, entity_id, inpto, look, unit_scale);
             log::info!("创建新实体 {} (名称: {})", entity_id, inpto.name());
         }
     }
 
-    // 按材质分组聚合 Point 为独立 PointList mesh
-    update_point_groups(&mut commands, &mut meshes, &asset_server, &material_manager, &mut entity_map, &point_groups);
+    sync_hierarchy(&mut commands, &non_point_ids, &parents, &mut entity_map);
+    sync_filter_visibility(&mut commands, &filtered, &mut entity_map);
+    // 在层级同步之后清理：被移走的子实体不会随旧父实体一并销毁
+    cleanup_removed_entities(&mut commands, &non_point_ids, &filtered, &mut entity_map);
+
+    // 按材质分组聚合 Point 为独立 mesh（PointList 或 splat）
+    update_point_groups(&mut commands, &mut assets, &mut entity_map, &point_groups);
 
     log::debug!("当前可拾取实体数量: {}", pickable_check_query.iter().count());
     for (entity, name, pickable) in pickable_check_query.iter() {
@@ -128,71 +381,176 @@ fn render_current_frame(
 
 fn spawn_entity_from_inpto(
     commands: &mut Commands,
-    meshes: &mut Assets<Mesh>,
-    asset_server: &AssetServer,
-    material_manager: &MaterialManager,
+    assets: &mut EntityAssets,
     inpto: &Inpto,
+    mesh: Mesh3d,
     render_transform: Transform,
     entity_id: u64,
-    _handedness: CoordSystem,
+    look: Look,
 ) -> Entity {
-    let mesh_handle = crate::render::conversion::proto_mesh_to_bevy(meshes, &inpto.mesh)
-        .unwrap_or_else(|| {
-            let mesh_type = match &inpto.mesh.u_mesh {
-                Some(umesh) => format!("{:?}", umesh),
-                None => "None".to_string(),
-            };
-            log::warn!(
-                "网格转换失败，使用备用球体 (实体 {}, 类型: {})。\
-                 支持的类型: Point, Sphere, Cylinder, Cone, Line(长度>0.001), Cube(维度>0.001)",
-                entity_id, mesh_type
-            );
-            Mesh3d(meshes.add(Sphere::new(0.1)))
-        });
-
-    let material_handle = material_manager.load_generic_material(&inpto.material_path(), asset_server);
-
-    commands.spawn((
-        mesh_handle,
-        crate::render::GenericMaterial3d(material_handle.clone()),
+    let mut ec = commands.spawn((
+        mesh,
         render_transform,
         Name::new(format!("FrameEntity_{}", entity_id)),
+        EntityLabels(inpto.tags.iter().map(|t| t.text.clone()).collect()),
         Pickable::default(),
         PickableEntity { entity_id },
         crate::render::interaction::picking::DynamicEntity,
-    ))
-    .observe(crate::render::interaction::picking::handle_dynamic_entity_pick)
-    .id()
+    ));
+    insert_entity_material(&mut ec, assets, inpto, look);
+    ec.observe(crate::render::interaction::picking::handle_dynamic_entity_pick).id()
+}
+
+/// 以带状线绘制的线段（端点有效）与折线（至少 2 个顶点）的线材质；
+/// 退化线段由备用网格绘制，使用通用材质
+fn ribbon_material(inpto: &Inpto) -> Option<LineMaterial> {
+    match &inpto.mesh.u_mesh {
+        Some(UMesh::Line(line)) if line_endpoints(line).is_some() => Some(LineMaterial::new(line)),
+        Some(UMesh::Polyline(polyline)) if polyline.points.len() >= 2 => Some(LineMaterial::polyline(polyline)),
+        _ => None,
+    }
+}
+
+/// 插入实体材质：
+/// - 带状线为 [`LineMaterial`]（颜色跟随通用材质，线段与折线的线宽、线段的虚线取自协议）
+/// - 半透明包围盒为降低不透明度的 `StandardMaterial`（颜色跟随通用材质）
+/// - 其余为通用材质
+///
+/// 替换或移除 GenericMaterial3d 会由 bevy_materialize 移除其应用的材质。
+fn insert_entity_material(ec: &mut EntityCommands, assets: &mut EntityAssets, inpto: &Inpto, look: Look) {
+    let material = assets.material(&inpto.material_path());
+    match look {
+        Look::Ribbon => {
+            let line_material = ribbon_material(inpto).unwrap_or_else(LineMaterial::edges);
+            let line_material = assets.line_materials.add(line_material);
+            ec.remove::<(crate::render::GenericMaterial3d, TranslucentFill, MeshMaterial3d<StandardMaterial>)>()
+                .insert(line_bundle(line_material, material));
+        }
+        Look::Translucent => {
+            let fill = assets.standard_materials.add(translucent_material());
+            ec.remove::<(crate::render::GenericMaterial3d, MeshMaterial3d<LineMaterial>, LineColorSource)>()
+                .insert(translucent_bundle(fill, material));
+        }
+        Look::Standard => {
+            ec.remove::<(MeshMaterial3d<LineMaterial>, LineColorSource, TranslucentFill, MeshMaterial3d<StandardMaterial>, NotShadowCaster)>()
+                .insert(crate::render::GenericMaterial3d(material));
+        }
+    }
+}
+
+/// 半透明包围盒的棱边子实体：先移除旧的，需要时按当前尺寸与材质重新生成
+fn sync_box_edges(
+    commands: &mut Commands,
+    assets: &mut EntityAssets,
+    entity_map: &mut EntityMap,
+    entity_id: u64,
+    inpto: &Inpto,
+    look: Look,
+    unit_scale: bool,
+) {
+    let previous = entity_map.box_edges.remove(&entity_id);
+    if let Some(mut ec) = previous.and_then(|edges| commands.get_entity(edges).ok()) {
+        ec.try_despawn();
+    }
+    if look != Look::Translucent {
+        return;
+    }
+    let (Some(&entity), Some(Ok(size))) = (entity_map.map.get(&entity_id), box_size(&inpto.mesh)) else { return };
+    // 与填充网格的缩放相同，子实体无需额外变换
+    let (mesh, _) = assets.mesh_cache.primitive(&mut assets.meshes, Primitive::BoxEdges { half: size * 0.5 }, unit_scale);
+    let line_material = assets.line_materials.add(LineMaterial::edges());
+    let material = assets.material(&inpto.material_path());
+    let edges = commands.spawn((
+        mesh,
+        Transform::IDENTITY,
+        line_bundle(line_material, material),
+        Pickable::IGNORE,
+        BoxEdgeLines,
+        ChildOf(entity),
+    )).id();
+    entity_map.box_edges.insert(entity_id, edges);
 }
 
-/// 按材质分组聚合 Point 为独立 PointList mesh，每组 1 次 draw call
+/// 按内容指纹修补已有实体：仅重新生成变化的网格、材质与标签
+fn patch_entity_content(
+    commands: &mut Commands,
+    assets: &mut EntityAssets,
+    entity_map: &mut EntityMap,
+    entity_id: u64,
+    inpto: &Inpto,
+    unit_scale: bool,
+) {
+    let content = EntityContent::of(inpto, unit_scale, *assets.box_style);
+    let Some(&entity) = entity_map.map.get(&entity_id) else { return };
+    let Some(previous) = entity_map.content.insert(entity_id, content.clone()) else { return };
+    if previous == content {
+        return;
+    }
+    let Ok(mut ec) = commands.get_entity(entity) else { return };
+    // 绘制方式决定包围盒网格是长方体还是棱边
+    let mesh_changed = previous.mesh != content.mesh || previous.unit_scale != content.unit_scale || previous.look != content.look;
+    if mesh_changed {
+        let (mesh, scale) = assets.mesh(inpto, entity_id, unit_scale, content.look);
+        ec.insert(mesh);
+        entity_map.mesh_scales.insert(entity_id, scale);
+        log::debug!("实体 {} 的网格已更新", entity_id);
+    }
+    // 带状线的线宽与虚线随网格一起变化
+    let material_changed = previous.material != content.material
+        || previous.look != content.look
+        || (content.look == Look::Ribbon && mesh_changed);
+    if material_changed {
+        insert_entity_material(&mut ec, assets, inpto, content.look);
+        log::debug!("实体 {} 的材质已更新为 {}", entity_id, content.material);
+    }
+    if previous.labels != content.labels {
+        ec.insert(EntityLabels(content.labels));
+    }
+    if mesh_changed || material_changed {
+        sync_box_edges(commands, assets, entity_map, entity_id, inpto, content.look, unit_scale);
+    }
+}
+
+/// 按材质分组聚合 Point 为独立 mesh，每组 1 次 draw call
+///
+/// 点样式为 splat 时生成 [`splat_mesh`] 并使用 [`PointSplatMaterial`]（颜色取自组材质），
+/// 否则为 PointList。层级中的单个点实体不参与聚合，仍以 PointList 绘制。
 fn update_point_groups(
     commands: &mut Commands,
-    meshes: &mut Assets<Mesh>,
-    asset_server: &AssetServer,
-    material_manager: &MaterialManager,
+    assets: &mut EntityAssets,
     entity_map: &mut EntityMap,
     point_groups: &HashMap<String, Vec<Vec3>>,
 ) {
     // 收集当前帧存在的组，清理不再存在的组
     let mut removed_keys = Vec::new();
-    for (key, _cache) in &entity_map.point_groups {
-        if !point_groups.contains_key(key) {
+    let splat = assets.point_style.is_splat();
+    for (key, cache) in &entity_map.point_groups {
+        // 绘制模式切换的组也整体重建
+        if !point_groups.contains_key(key) || cache.splat != splat {
             removed_keys.push(key.clone());
         }
     }
+    // 仅因绘制模式切换而重建的组沿用已构建的 LOD
+    let mut reusable_lods: HashMap<String, (Vec<[f32; 3]>, PointLod)> = HashMap::new();
     for key in removed_keys {
         if let Some(cache) = entity_map.point_groups.remove(&key) {
             if let Ok(mut ec) = commands.get_entity(cache.entity) {
                 ec.despawn();
             }
+            if let (Some(lod), true) = (cache.lod, point_groups.contains_key(&key)) {
+                reusable_lods.insert(key.clone(), (cache.cached_positions, lod));
+            }
             log::debug!("移除点云组: {}", key);
         }
     }
 
     for (material, positions) in point_groups {
         let n_points = positions.len();
-        let coords: Vec<[f32; 3]> = positions.iter().map(|p| [p.x, p.y, p.z]).collect();
+        // 实体遍历顺序不固定，排序后脏检查与 LOD 只取决于点集本身
+        let mut coords: Vec<[f32; 3]> = positions.iter().map(|p| [p.x, p.y, p.z]).collect();
+        coords.sort_unstable_by(|a, b| {
+            a[0].total_cmp(&b[0]).then(a[1].total_cmp(&b[1])).then(a[2].total_cmp(&b[2]))
+        });
 
         // dirty check：点数据未变时跳过 mesh 重建
         if let Some(cache) = entity_map.point_groups.get(material) {
@@ -201,49 +559,122 @@ fn update_point_groups(
             }
         }
 
-        let normals: Vec<[f32; 3]> = vec![[0.0, 1.0, 0.0]; n_points];
-        let mut mesh = Mesh::new(PrimitiveTopology::PointList, default());
-        mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, coords.clone());
-        mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+        // 大点云构建 LOD（点集变化时才构建），先按全局预算选级，之后由 update_point_lod 按相机距离调整
+        let lod = match reusable_lods.remove(material) {
+            Some((cached, lod)) if cached == coords => Some(lod),
+            _ => (n_points >= LOD_MIN_POINTS).then(|| PointLod::build(&coords)),
+        };
+        let level = lod.as_ref().map_or(0, |lod| lod.level_for_budget(assets.point_budget.current));
+        let mesh = point_group_mesh(lod.as_ref().map_or(&coords, |lod| lod.level(level)), splat);
 
         // 复用已有 mesh handle
         if let Some(cache) = entity_map.point_groups.get_mut(material) {
-            if let Some(mesh_ref) = meshes.get_mut(&cache.mesh_handle) {
+            if let Some(mesh_ref) = assets.meshes.get_mut(&cache.mesh_handle) {
                 *mesh_ref = mesh;
                 cache.cached_positions = coords;
+                cache.lod = lod;
+                cache.level = level;
                 log::debug!("更新点云组 {}，{} 个点", material, n_points);
                 continue;
             }
         }
 
         // 新建组实体
-        let handle = meshes.add(mesh);
-        let mat_handle = material_manager.load_generic_material(material, asset_server);
+        let handle = assets.meshes.add(mesh);
+        let mat_handle = assets.material(material);
         log::info!("创建点云组 {}，包含 {} 个点", material, n_points);
-        let entity = commands.spawn((
+        let mut ec = commands.spawn((
             Mesh3d(handle.clone()),
-            crate::render::GenericMaterial3d(mat_handle),
             Transform::default(),
             Name::new(format!("PointGroup_{}", material)),
-        )).id();
+        ));
+        if splat {
+            let splat_material = assets.splat_materials.add(PointSplatMaterial::new(&assets.point_style));
+            ec.insert(splat_bundle(splat_material, mat_handle));
+        } else {
+            ec.insert(crate::render::GenericMaterial3d(mat_handle));
+        }
         entity_map.point_groups.insert(material.clone(), PointGroupCache {
-            entity,
+            entity: ec.id(),
             mesh_handle: handle,
             cached_positions: coords,
+            splat,
+            lod,
+            level,
         });
     }
 }
 
+/// 点云组网格：splat 网格或 PointList
+fn point_group_mesh(coords: &[[f32; 3]], splat: bool) -> Mesh {
+    if splat {
+        return splat_mesh(coords);
+    }
+    let normals: Vec<[f32; 3]> = vec![[0.0, 1.0, 0.0]; coords.len()];
+    let mut mesh = Mesh::new(PrimitiveTopology::PointList, default());
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, coords.to_vec());
+    mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+    mesh
+}
+
+/// 按相机距离与点数预算切换大点云的 LOD 级别，仅在级别变化时重建网格
+fn update_point_lod(
+    camera: Query<&GlobalTransform, With<Camera3d>>,
+    budget: Res<PointBudget>,
+    point_style: Res<PointStyle>,
+    mut entity_map: ResMut<EntityMap>,
+    mut meshes: ResMut<Assets<Mesh>>,
+) {
+    let Some(camera) = camera.iter().next().map(GlobalTransform::translation) else { return };
+    for (material, cache) in entity_map.point_groups.iter_mut() {
+        let Some(lod) = &cache.lod else { continue };
+        let group_budget = budget.for_group(lod, camera);
+        let target = lod.level_for_budget(group_budget);
+        // 提高细节时留出余量，避免在级别边界来回切换
+        let level = if target > cache.level {
+            lod.level_for_budget(group_budget * 4 / 5).max(cache.level)
+        } else {
+            target
+        };
+        if level == cache.level {
+            continue;
+        }
+        if let Some(mesh) = meshes.get_mut(&cache.mesh_handle) {
+            *mesh = point_group_mesh(lod.level(level), point_style.is_splat());
+            log::debug!("点云组 {} 切换到 LOD {}（{} / {} 个点）", material, level, lod.level(level).len(), lod.points().len());
+        }
+        cache.level = level;
+    }
+}
+
+/// 实体最终变换（坐标系转换前）— 实体变换叠加网格自带位姿
+fn entity_transform(inpto: &Inpto) -> Transform {
+    Transform::from(inpto.transform) * crate::render::conversion::mesh_pose(&inpto.mesh)
+}
+
+/// 渲染用的局部变换
+///
+/// 根实体先变换到固定坐标系（`frame`）再做坐标系转换；子实体的父实体已完成向上轴旋转，
+/// 只需手性反射，并抵消父实体网格自带位姿（父实体的 `Transform` 含 `mesh_pose`）。
+fn render_transform(inpto: &Inpto, parent: Option<&Inpto>, frame: Transform, handedness: CoordSystem) -> Transform {
+    match parent {
+        Some(parent) => {
+            let parent_pose = crate::render::conversion::mesh_pose(&parent.mesh);
+            let inverse_pose = Transform::from_rotation(parent_pose.rotation.inverse())
+                * Transform::from_translation(-parent_pose.translation);
+            apply_handedness(inverse_pose * entity_transform(inpto), handedness.handedness)
+        }
+        None => apply_coord_system(frame * entity_transform(inpto), handedness),
+    }
+}
+
 fn update_entity_transform(
     commands: &mut Commands,
     entity: Entity,
-    transform: &InptoTransform,
-    handedness: CoordSystem,
+    render_transform: Transform,
     hidden_query: &Query<(), With<Hidden>>,
 ) {
     let Ok(mut ec) = commands.get_entity(entity) else { return };
-    let bevy_t: bevy::transform::components::Transform = (*transform).into();
-    let render_transform = apply_coord_system(bevy_t, handedness);
     let has_hidden = hidden_query.get(entity).is_ok();
     ec.insert(render_transform);
     if has_hidden {
@@ -253,19 +684,483 @@ fn update_entity_transform(
     }
 }
 
+/// 将数据层的父子关系同步为 bevy `ChildOf`（仅在关系变化时写入）
+fn sync_hierarchy(
+    commands: &mut Commands,
+    current_entity_ids: &HashMap<u64, &Inpto>,
+    parents: &HashMap<u64, u64>,
+    entity_map: &mut EntityMap,
+) {
+    for &entity_id in current_entity_ids.keys() {
+        let parent = parents.get(&entity_id).copied()
+            .filter(|p| entity_map.map.contains_key(p));
+        if entity_map.parents.get(&entity_id).copied() == parent {
+            continue;
+        }
+        let Some(&entity) = entity_map.map.get(&entity_id) else { continue };
+        let Ok(mut ec) = commands.get_entity(entity) else { continue };
+        match parent {
+            Some(parent_id) => {
+                ec.insert(ChildOf(entity_map.map[&parent_id]));
+                entity_map.parents.insert(entity_id, parent_id);
+            }
+            None => {
+                ec.remove::<ChildOf>();
+                entity_map.parents.remove(&entity_id);
+            }
+        }
+    }
+}
+
+/// 筛选可见性：被筛选掉的已有实体设为隐藏，重新通过筛选时恢复
+fn sync_filter_visibility(
+    commands: &mut Commands,
+    filtered: &HashSet<u64>,
+    entity_map: &mut EntityMap,
+) {
+    for (&entity_id, &entity) in &entity_map.map {
+        let hide = filtered.contains(&entity_id);
+        if hide == entity_map.hidden.contains(&entity_id) {
+            continue;
+        }
+        let Ok(mut ec) = commands.get_entity(entity) else { continue };
+        if hide {
+            ec.insert(Visibility::Hidden);
+            entity_map.hidden.insert(entity_id);
+        } else {
+            ec.insert(Visibility::Inherited);
+            entity_map.hidden.remove(&entity_id);
+        }
+    }
+}
+
 fn cleanup_removed_entities(
     commands: &mut Commands,
     current_entity_ids: &HashMap<u64, &Inpto>,
-    entity_map: &mut HashMap<u64, Entity>,
+    filtered: &HashSet<u64>,
+    entity_map: &mut EntityMap,
 ) {
     let mut removed_ids = Vec::new();
-    for (&entity_id, &entity) in entity_map.iter() {
-        if !current_entity_ids.contains_key(&entity_id) {
+    for (&entity_id, &entity) in entity_map.map.iter() {
+        if !current_entity_ids.contains_key(&entity_id) && !filtered.contains(&entity_id) {
             removed_ids.push(entity_id);
+            // 子实体可能已随父实体一并销毁
             if let Ok(mut ec) = commands.get_entity(entity) {
-                ec.despawn();
+                ec.try_despawn();
             }
         }
     }
-    for entity_id in removed_ids { entity_map.remove(&entity_id); }
+    for entity_id in removed_ids {
+        entity_map.map.remove(&entity_id);
+        entity_map.parents.remove(&entity_id);
+        entity_map.content.remove(&entity_id);
+        entity_map.mesh_scales.remove(&entity_id);
+        entity_map.hidden.remove(&entity_id);
+        entity_map.box_edges.remove(&entity_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use expto::rdmp::{CommandType, ExCommand, ExMesh, Cylinder, ExObject, ExTransform, OrientedBox, Sphere, Tag, Unit, ex_object::UObject};
+    use crate::assets::materials::{GenericMaterial, GenericMaterial3d};
+    use crate::data::frame::{InptoTransform, KeyFrame};
+    use crate::render::coord_system::{Handedness, UpAxis};
+
+    fn inpto(mesh: ExMesh, transform: ExTransform) -> Inpto {
+        Inpto::new(mesh, String::new(), InptoTransform::from(transform))
+    }
+
+    #[test]
+    fn test_child_transform_relative_to_parent() {
+        // 父实体是自带位姿的有向包围盒，子实体的局部变换不应受其影响
+        let parent = inpto(
+            ExMesh::from(OrientedBox::from(([1.0, 0.0, 0.5], [2.0, 1.0, 0.5], [0.0, 0.0, 0.38268343, 0.9238795]))),
+            ExTransform { x: 10.0, y: -3.0, z: 0.5, ..ExTransform::identity() }.with_quaternion([0.0, 0.0, 0.70710677, 0.70710677]),
+        );
+        let child = inpto(
+            ExMesh::from(Sphere { location: None, radius: 0.2 }),
+            ExTransform { x: 2.0, y: 0.0, z: 1.0, ..ExTransform::identity() }.with_quaternion([0.25881905, 0.0, 0.0, 0.9659258]),
+        );
+
+        for handedness in [Handedness::LeftHanded, Handedness::RightHanded] {
+            let coord = CoordSystem { handedness, up_axis: UpAxis::PlusZ, show_axes: true };
+            let parent_render = render_transform(&parent, None, Transform::IDENTITY, coord);
+            let child_local = render_transform(&child, Some(&parent), Transform::IDENTITY, coord);
+
+            let expected = apply_coord_system(Transform::from(parent.transform) * entity_transform(&child), coord);
+            let actual = parent_render * child_local;
+            for p in [Vec3::ZERO, Vec3::X, Vec3::new(0.3, -1.0, 2.0)] {
+                let (a, e) = (actual.transform_point(p), expected.transform_point(p));
+                assert!(a.distance(e) < 1e-4, "{:?}: {:?} vs {:?}", handedness, a, e);
+            }
+        }
+    }
+
+    /// 仅含渲染系统所需资源的最小 bevy 世界
+    fn render_app() -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .init_asset::<Mesh>()
+            .init_asset::<GenericMaterial>()
+            .init_resource::<MaterialManager>()
+            .init_resource::<FrameManager>()
+            .init_resource::<TagFilter>()
+            .init_resource::<TagRegistry>()
+            .init_resource::<NamespaceFilter>()
+            .init_resource::<CoordSystem>()
+            .init_resource::<EntityMap>()
+            .init_asset::<PointSplatMaterial>()
+            .init_asset::<LineMaterial>()
+            .init_asset::<StandardMaterial>()
+            .init_resource::<PointStyle>()
+            .init_resource::<BoxStyleOverride>()
+            .init_resource::<PointBudget>()
+            .init_resource::<MeshCache>()
+            .add_systems(Update, (render_current_frame, update_point_lod).chain());
+        app
+    }
+
+    fn update_unit(objects: Vec<ExObject>) -> Unit {
+        Unit { stamp: None, command: Some(ExCommand { u_command: CommandType::Update as i32 }), objects }
+    }
+
+    fn sphere(radius: f32) -> ExMesh {
+        ExMesh::from(Sphere { location: None, radius })
+    }
+
+    fn apply(app: &mut App, unit: Unit) {
+        app.world_mut().resource_mut::<FrameManager>().get_current_keyframe_mut().unwrap().update(&unit);
+        app.update();
+    }
+
+    fn component<C: Component + Clone>(app: &mut App, id: u64) -> C {
+        let entity = app.world().resource::<EntityMap>().map[&id];
+        app.world().get::<C>(entity).unwrap().clone()
+    }
+
+    /// 未经渲染插件补齐 `Visibility` 时视为默认值
+    fn visibility(app: &mut App, id: u64) -> Visibility {
+        let entity = app.world().resource::<EntityMap>().map[&id];
+        app.world().get::<Visibility>(entity).copied().unwrap_or_default()
+    }
+
+    #[test]
+    fn test_existing_entity_patches_changed_content() {
+        let mut app = render_app();
+        let mut keyframe = KeyFrame::new(0);
+        keyframe.insert_entity(1, sphere(1.0), ExTransform::identity());
+        app.world_mut().resource_mut::<FrameManager>().add_keyframe(keyframe);
+        app.update();
+
+        let entity = app.world().resource::<EntityMap>().map[&1];
+        let mesh = component::<Mesh3d>(&mut app, 1);
+        let material = component::<GenericMaterial3d>(&mut app, 1);
+        assert!(component::<EntityLabels>(&mut app, 1).0.is_empty());
+
+        // 仅变换变化：网格、材质句柄保持不变
+        apply(&mut app, update_unit(vec![ExObject::from(1u64), ExObject::from(ExTransform { x: 3.0, ..ExTransform::identity() })]));
+        assert_eq!(component::<Mesh3d>(&mut app, 1), mesh);
+        assert_eq!(component::<GenericMaterial3d>(&mut app, 1), material);
+        assert_eq!(component::<Transform>(&mut app, 1).translation.length(), 3.0);
+
+        // 材质变化：替换材质句柄
+        apply(&mut app, update_unit(vec![ExObject::from(1u64), ExObject { u_object: Some(UObject::MaterialId("red".to_string())) }]));
+        let red = component::<GenericMaterial3d>(&mut app, 1);
+        assert_ne!(red, material);
+        assert_eq!(app.world().resource::<AssetServer>().get_path(red.0.id()).unwrap().path().to_str(), Some("materials/base/red.toml"));
+        assert_eq!(component::<Mesh3d>(&mut app, 1), mesh);
+
+        // 尺寸与 Tag 变化：共享单位网格不变，尺寸体现在缩放上，标签更新
+        apply(&mut app, update_unit(vec![
+            ExObject::from(1u64),
+            ExObject::from(sphere(2.0)),
+            ExObject::from(Tag { text: "障碍物".to_string(), offset: None, style: None }),
+        ]));
+        assert_eq!(component::<Mesh3d>(&mut app, 1), mesh);
+        assert_eq!(component::<Transform>(&mut app, 1).scale, Vec3::splat(2.0));
+        assert_eq!(component::<GenericMaterial3d>(&mut app, 1), red);
+        assert_eq!(component::<EntityLabels>(&mut app, 1).0, vec!["障碍物".to_string()]);
+
+        // 网格类型变化：替换网格句柄
+        apply(&mut app, update_unit(vec![ExObject::from(1u64), ExObject::from(ExMesh::from(Cylinder { radius: 0.5, height: 2.0 }))]));
+        assert_ne!(component::<Mesh3d>(&mut app, 1), mesh);
+        assert_eq!(component::<Transform>(&mut app, 1).scale, Vec3::new(0.5, 2.0, 0.5));
+
+        // 始终是同一个 bevy 实体，未重建
+        assert_eq!(app.world().resource::<EntityMap>().map[&1], entity);
+    }
+
+    #[test]
+    fn test_line_entity_uses_ribbon_material() {
+        use expto::rdmp::{Line, Point};
+
+        let mut app = render_app();
+        let line = Line::from((Point { x: 0.0, y: 0.0, z: 0.0 }, Point { x: 2.0, y: 0.0, z: 0.0 }));
+        let mut keyframe = KeyFrame::new(0);
+        keyframe.insert_entity(1, ExMesh::from(line.clone()), ExTransform::identity());
+        app.world_mut().resource_mut::<FrameManager>().add_keyframe(keyframe);
+        app.update();
+
+        let entity = app.world().resource::<EntityMap>().map[&1];
+        assert!(app.world().get::<MeshMaterial3d<LineMaterial>>(entity).is_some());
+        assert!(app.world().get::<GenericMaterial3d>(entity).is_none());
+
+        // 线宽变化：材质随之重建
+        let material = component::<MeshMaterial3d<LineMaterial>>(&mut app, 1);
+        apply(&mut app, update_unit(vec![ExObject::from(1u64), ExObject::from(ExMesh::from(Line { width: 0.1, ..line }))]));
+        let widened = component::<MeshMaterial3d<LineMaterial>>(&mut app, 1);
+        assert_ne!(widened, material);
+        assert_eq!(app.world().resource::<Assets<LineMaterial>>().get(&widened.0).unwrap().params.x, 0.1);
+
+        // 改为球体：恢复通用材质
+        apply(&mut app, update_unit(vec![ExObject::from(1u64), ExObject::from(sphere(1.0))]));
+        assert!(app.world().get::<MeshMaterial3d<LineMaterial>>(entity).is_none());
+        assert!(app.world().get::<GenericMaterial3d>(entity).is_some());
+        assert_eq!(app.world().resource::<EntityMap>().map[&1], entity);
+    }
+
+    #[test]
+    fn test_polyline_entity_uses_ribbon_width() {
+        use expto::rdmp::Polyline;
+
+        let mut app = render_app();
+        let mut polyline = Polyline::from(vec![[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [1.0, 1.0, 0.0]]);
+        polyline.width = 0.2;
+        let mut keyframe = KeyFrame::new(0);
+        keyframe.insert_entity(1, ExMesh::from(polyline), ExTransform::identity());
+        app.world_mut().resource_mut::<FrameManager>().add_keyframe(keyframe);
+        app.update();
+
+        let entity = app.world().resource::<EntityMap>().map[&1];
+        assert!(app.world().get::<GenericMaterial3d>(entity).is_none());
+        let material = component::<MeshMaterial3d<LineMaterial>>(&mut app, 1);
+        // 线宽为世界单位
+        assert_eq!(app.world().resource::<Assets<LineMaterial>>().get(&material.0).unwrap().params, Vec4::new(0.2, 0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_box_style_switches_look() {
+        use expto::rdmp::Cube;
+
+        let mut app = render_app();
+        let cube = Cube::from([
+            (0.0, 0.0, 0.0), (2.0, 0.0, 0.0), (0.0, 1.0, 0.0), (2.0, 1.0, 0.0),
+            (0.0, 0.0, 1.0), (2.0, 0.0, 1.0), (0.0, 1.0, 1.0), (2.0, 1.0, 1.0),
+        ]);
+        let mut keyframe = KeyFrame::new(0);
+        keyframe.insert_entity(1, ExMesh::from(cube), ExTransform::identity());
+        keyframe.packs[0].material = "bounding_box".to_string();
+        app.world_mut().resource_mut::<FrameManager>().add_keyframe(keyframe);
+        app.update();
+
+        // bounding_box 材质默认半透明填充 + 棱边子实体
+        let entity = app.world().resource::<EntityMap>().map[&1];
+        assert!(app.world().get::<TranslucentFill>(entity).is_some());
+        assert!(app.world().get::<GenericMaterial3d>(entity).is_none());
+        let edges = app.world().resource::<EntityMap>().box_edges[&1];
+        assert_eq!(app.world().get::<ChildOf>(edges).map(|c| c.parent()), Some(entity));
+        assert!(app.world().get::<MeshMaterial3d<LineMaterial>>(edges).is_some());
+
+        // 全局覆盖为线框：实体自身绘制棱边，子实体移除
+        let fill_mesh = component::<Mesh3d>(&mut app, 1);
+        app.world_mut().resource_mut::<BoxStyleOverride>().0 = Some(BoxStyle::Wireframe);
+        app.update();
+        assert!(app.world().get::<MeshMaterial3d<LineMaterial>>(entity).is_some());
+        assert!(app.world().get::<TranslucentFill>(entity).is_none());
+        assert_ne!(component::<Mesh3d>(&mut app, 1), fill_mesh);
+        assert!(app.world().get_entity(edges).is_err());
+        assert!(app.world().resource::<EntityMap>().box_edges.is_empty());
+
+        // 覆盖为实体：恢复通用材质与长方体网格
+        app.world_mut().resource_mut::<BoxStyleOverride>().0 = Some(BoxStyle::Solid);
+        app.update();
+        assert!(app.world().get::<GenericMaterial3d>(entity).is_some());
+        assert!(app.world().get::<MeshMaterial3d<LineMaterial>>(entity).is_none());
+        assert_eq!(component::<Mesh3d>(&mut app, 1), fill_mesh);
+        assert_eq!(app.world().resource::<EntityMap>().map[&1], entity);
+    }
+
+    #[test]
+    fn test_filtered_entity_is_hidden_not_despawned() {
+        let mut app = render_app();
+        let mut keyframe = KeyFrame::new(0);
+        keyframe.insert_entity(1, sphere(1.0), ExTransform::identity());
+        app.world_mut().resource_mut::<FrameManager>().add_keyframe(keyframe);
+        app.update();
+        let entity = app.world().resource::<EntityMap>().map[&1];
+        assert_eq!(visibility(&mut app, 1), Visibility::Inherited);
+
+        app.world_mut().resource_mut::<NamespaceFilter>().set_visible("", false);
+        app.update();
+        assert_eq!(app.world().resource::<EntityMap>().map[&1], entity);
+        assert_eq!(visibility(&mut app, 1), Visibility::Hidden);
+
+        app.world_mut().resource_mut::<NamespaceFilter>().set_visible("", true);
+        app.update();
+        assert_eq!(app.world().resource::<EntityMap>().map[&1], entity);
+        assert_eq!(visibility(&mut app, 1), Visibility::Inherited);
+    }
+
+    #[test]
+    fn test_static_frame_skips_rescan() {
+        let mut app = render_app();
+        let mut keyframe = KeyFrame::new(0);
+        keyframe.insert_entity(1, sphere(1.0), ExTransform::identity());
+        app.world_mut().resource_mut::<FrameManager>().add_keyframe(keyframe);
+        app.update();
+        let entity = app.world().resource::<EntityMap>().map[&1];
+
+        // 输入未变化：渲染系统不再写入组件
+        app.world_mut().entity_mut(entity).insert(Transform::from_xyz(9.0, 9.0, 9.0));
+        app.update();
+        assert_eq!(component::<Transform>(&mut app, 1).translation, Vec3::splat(9.0));
+
+        // 坐标系变化触发重扫
+        app.world_mut().resource_mut::<CoordSystem>().up_axis = UpAxis::PlusZ;
+        app.update();
+        assert_eq!(component::<Transform>(&mut app, 1).translation, Vec3::ZERO);
+
+        // 帧数据变化（经可变访问器）触发重扫
+        app.world_mut().entity_mut(entity).insert(Transform::from_xyz(9.0, 9.0, 9.0));
+        app.world_mut().resource_mut::<FrameManager>().get_current_keyframe_mut();
+        app.update();
+        assert_eq!(component::<Transform>(&mut app, 1).translation, Vec3::ZERO);
+    }
+
+    #[test]
+    fn test_identical_primitives_share_mesh_assets() {
+        let mut app = render_app();
+        let mut keyframe = KeyFrame::new(0);
+        for id in 0..10_000 {
+            let radius = if id % 2 == 0 { 0.2 } else { 0.5 };
+            keyframe.insert_entity(id, sphere(radius), ExTransform { x: id as f32, ..ExTransform::identity() });
+        }
+        for id in 10_000..10_100 {
+            keyframe.insert_entity(id, ExMesh::from(Cylinder { radius: 0.1, height: id as f32 * 0.01 }), ExTransform::identity());
+        }
+        app.world_mut().resource_mut::<FrameManager>().add_keyframe(keyframe);
+        app.update();
+
+        // 1 万个球体与 100 个圆柱体只各占一个单位网格资产
+        assert_eq!(app.world().resource::<EntityMap>().map.len(), 10_100);
+        assert_eq!(app.world().resource::<Assets<Mesh>>().len(), 2);
+        assert_eq!(component::<Mesh3d>(&mut app, 0), component::<Mesh3d>(&mut app, 1));
+        assert_eq!(component::<Transform>(&mut app, 1).scale, Vec3::splat(0.5));
+    }
+
+    #[test]
+    fn test_parent_keeps_sized_mesh() {
+        let mut app = render_app();
+        let mut keyframe = KeyFrame::new(0);
+        keyframe.insert_entity(1, sphere(2.0), ExTransform::identity());
+        keyframe.update(&Unit {
+            stamp: None,
+            command: None,
+            objects: vec![
+                ExObject::from(2u64),
+                ExObject::from(sphere(2.0)),
+                ExObject::from(ExTransform { x: 1.0, ..ExTransform::identity() }),
+                ExObject { u_object: Some(UObject::ParentId(1)) },
+            ],
+        });
+        app.world_mut().resource_mut::<FrameManager>().add_keyframe(keyframe);
+        app.update();
+
+        // 父实体的缩放会传递给子实体，因此按原尺寸生成网格
+        assert_eq!(component::<Transform>(&mut app, 1).scale, Vec3::ONE);
+        assert_eq!(component::<Transform>(&mut app, 2).scale, Vec3::splat(2.0));
+        assert_ne!(component::<Mesh3d>(&mut app, 1), component::<Mesh3d>(&mut app, 2));
+        assert_eq!(app.world().resource::<Assets<Mesh>>().len(), 2);
+    }
+
+    #[test]
+    fn test_point_group_follows_point_style() {
+        use crate::render::point_splat::{PointShape, PointSplatSource};
+
+        let mut app = render_app();
+        let mut keyframe = KeyFrame::new(0);
+        for id in 0..3 {
+            keyframe.insert_entity(id, ExMesh::from(expto::rdmp::Point { x: id as f32, y: 0.0, z: 0.0 }), ExTransform::identity());
+        }
+        app.world_mut().resource_mut::<FrameManager>().add_keyframe(keyframe);
+        app.update();
+
+        let group = |app: &mut App| {
+            let (_, cache) = app.world().resource::<EntityMap>().point_groups.iter().next().unwrap();
+            (cache.entity, cache.mesh_handle.clone())
+        };
+        let (entity, handle) = group(&mut app);
+        let mesh = app.world().resource::<Assets<Mesh>>().get(&handle).unwrap();
+        assert_eq!(mesh.primitive_topology(), PrimitiveTopology::TriangleList);
+        assert_eq!(mesh.count_vertices(), 12);
+        assert!(app.world().get::<PointSplatSource>(entity).is_some());
+        assert!(app.world().get::<MeshMaterial3d<PointSplatMaterial>>(entity).is_some());
+
+        // 仅调整大小：不重建组
+        app.world_mut().resource_mut::<PointStyle>().size = 8.0;
+        app.update();
+        assert_eq!(group(&mut app).0, entity);
+
+        // 切回像素：重建为 PointList 组
+        app.world_mut().resource_mut::<PointStyle>().shape = PointShape::Pixel;
+        app.update();
+        let (pixel_entity, handle) = group(&mut app);
+        assert_ne!(pixel_entity, entity);
+        assert!(app.world().get_entity(entity).is_err());
+        assert_eq!(app.world().resource::<Assets<Mesh>>().get(&handle).unwrap().primitive_topology(), PrimitiveTopology::PointList);
+        assert!(app.world().get::<GenericMaterial3d>(pixel_entity).is_some());
+    }
+
+    #[test]
+    fn test_large_point_group_uses_lod() {
+        let mut app = render_app();
+        let mut keyframe = KeyFrame::new(0);
+        let side: u64 = 50;
+        for id in 0..side * side * side {
+            let (x, y, z) = ((id % side) as f32, ((id / side) % side) as f32, (id / (side * side)) as f32);
+            keyframe.insert_entity(id, ExMesh::from(expto::rdmp::Point { x, y, z }), ExTransform::identity());
+        }
+        app.world_mut().resource_mut::<FrameManager>().add_keyframe(keyframe);
+        app.world_mut().spawn((Camera3d::default(), GlobalTransform::from_translation(Vec3::splat(25.0))));
+        app.update();
+
+        // 相机位于点云内部：全局预算足够，绘制全部点
+        let total = (side * side * side) as usize;
+        assert_eq!(app.world().resource::<EntityMap>().point_counts(), (total, total));
+
+        // 相机远离：按距离缩减为较粗的级别，全分辨率数据仍保留
+        let camera = app.world_mut().query_filtered::<Entity, With<Camera3d>>().single(app.world()).unwrap();
+        app.world_mut().entity_mut(camera).insert(GlobalTransform::from_translation(Vec3::splat(2000.0)));
+        app.update();
+        let (rendered, full) = app.world().resource::<EntityMap>().point_counts();
+        assert!(rendered < total / 2, "{}", rendered);
+        assert_eq!(full, total);
+        let handle = app.world().resource::<EntityMap>().point_groups.values().next().unwrap().mesh_handle.clone();
+        assert_eq!(app.world().resource::<Assets<Mesh>>().get(&handle).unwrap().count_vertices(), rendered * 4);
+        let positions: usize = app.world().resource::<EntityMap>().point_group_positions().map(|(_, p)| p.len()).sum();
+        assert_eq!(positions, total);
+
+        // 同一点集换一种插入顺序与实体 ID：不重建 LOD；切换绘制模式也沿用已有 LOD
+        let lod_points = |app: &App| {
+            app.world().resource::<EntityMap>().point_groups.values().next().unwrap().lod.as_ref().unwrap().points().as_ptr()
+        };
+        let built = lod_points(&app);
+        let mut keyframe = KeyFrame::new(1);
+        for id in (0..side * side * side).rev() {
+            let (x, y, z) = ((id % side) as f32, ((id / side) % side) as f32, (id / (side * side)) as f32);
+            keyframe.insert_entity(id + total as u64, ExMesh::from(expto::rdmp::Point { x, y, z }), ExTransform::identity());
+        }
+        let mut frame_manager = app.world_mut().resource_mut::<FrameManager>();
+        frame_manager.add_keyframe(keyframe);
+        assert!(frame_manager.seek_to_frame(1));
+        app.update();
+        assert_eq!(lod_points(&app), built);
+
+        app.world_mut().resource_mut::<PointStyle>().shape = crate::render::point_splat::PointShape::Pixel;
+        app.update();
+        assert_eq!(lod_points(&app), built);
+    }
 }
+
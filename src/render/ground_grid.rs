@@ -0,0 +1,229 @@
+//! 地面网格与比例尺数据
+//!
+//! 数据的向上轴经 [`apply_coord_system`](crate::render::coord_system::apply_coord_system) 映射为 bevy 的 +Y，
+//! 因此地面始终是 bevy 的 XZ 平面。网格平面跟随相机平移，网格线由着色器按世界坐标绘制；
+//! 次网格间距取 10 的整数次幂并随相机离地高度切换，接近下一档时次网格线逐渐淡出，
+//! 主网格线为次网格的 [`MAJOR_EVERY`] 倍。
+
+use bevy::light::NotShadowCaster;
+use bevy::mesh::MeshVertexBufferLayoutRef;
+use bevy::pbr::{MaterialPipeline, MaterialPipelineKey, MaterialPlugin};
+use bevy::prelude::*;
+use bevy::render::render_resource::{AsBindGroup, RenderPipelineDescriptor, SpecializedMeshPipelineError};
+use bevy::shader::ShaderRef;
+
+use crate::render::init::LightMode;
+
+const SHADER_ASSET_PATH: &str = "shaders/ground_grid.wgsl";
+
+/// 主网格线间隔（次网格数）
+pub const MAJOR_EVERY: f32 = 10.0;
+
+/// 相机离地高度与次网格间距之比：高度 4 m 时次网格为 1 m
+const HEIGHT_PER_SPACING: f32 = 4.0;
+
+/// 网格淡出半径为相机离地高度的倍数
+const FADE_PER_HEIGHT: f32 = 60.0;
+
+/// Bevy Resource：地面网格开关与当前刻度
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct GroundGrid {
+    pub visible: bool,
+    /// 当前次网格间距（世界单位）
+    pub spacing: f32,
+    /// 视线与地面交点处每世界单位对应的屏幕像素数（比例尺用）
+    pub pixels_per_unit: f32,
+}
+
+impl Default for GroundGrid {
+    fn default() -> Self {
+        Self { visible: true, spacing: 1.0, pixels_per_unit: 0.0 }
+    }
+}
+
+/// 按相机离地高度选取次网格间距（10 的整数次幂），以及次网格线不透明度
+pub fn grid_spacing(height: f32) -> (f32, f32) {
+    let level = (height.max(1e-3) / HEIGHT_PER_SPACING).log10();
+    let floor = level.floor();
+    // 越接近下一档（次网格在屏幕上越密）越淡
+    (10f32.powi(floor as i32), 1.0 - (level - floor))
+}
+
+/// 长度的文字表示（比例尺标注）
+pub fn format_length(length: f32) -> String {
+    if length >= 1000.0 {
+        format!("{} km", (length / 1000.0).round())
+    } else if length >= 1.0 {
+        format!("{} m", length.round())
+    } else if length >= 0.01 {
+        format!("{} cm", (length * 100.0).round())
+    } else {
+        format!("{} mm", (length * 1000.0).round())
+    }
+}
+
+/// 视线与地面（y = 0）交点的距离；视线平行于地面或背离地面时返回 `None`
+fn ground_distance(origin: Vec3, forward: Vec3) -> Option<f32> {
+    if forward.y.abs() < 1e-4 {
+        return None;
+    }
+    let t = -origin.y / forward.y;
+    (t > 0.0).then_some(t)
+}
+
+/// 地面网格材质 — 无光照，按世界坐标程序化绘制网格线
+#[derive(Asset, TypePath, AsBindGroup, Debug, Clone)]
+pub struct GridMaterial {
+    #[uniform(0)]
+    pub color: LinearRgba,
+    /// x: 次网格间距，y: 主网格倍数，z: 次网格线不透明度，w: 淡出半径
+    #[uniform(1)]
+    pub params: Vec4,
+}
+
+impl Material for GridMaterial {
+    fn fragment_shader() -> ShaderRef {
+        SHADER_ASSET_PATH.into()
+    }
+
+    fn alpha_mode(&self) -> AlphaMode {
+        AlphaMode::Blend
+    }
+
+    fn enable_prepass() -> bool {
+        false
+    }
+
+    fn enable_shadows() -> bool {
+        false
+    }
+
+    fn specialize(
+        _pipeline: &MaterialPipeline,
+        descriptor: &mut RenderPipelineDescriptor,
+        _layout: &MeshVertexBufferLayoutRef,
+        _key: MaterialPipelineKey<Self>,
+    ) -> Result<(), SpecializedMeshPipelineError> {
+        // 从地面下方也能看到网格
+        descriptor.primitive.cull_mode = None;
+        Ok(())
+    }
+}
+
+/// 地面网格平面标记组件
+#[derive(Component)]
+pub struct GroundGridPlane;
+
+pub struct GroundGridPlugin;
+
+impl Plugin for GroundGridPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GroundGrid>()
+            .add_plugins(MaterialPlugin::<GridMaterial>::default())
+            .add_systems(Startup, spawn_ground_grid)
+            .add_systems(PostUpdate, update_ground_grid.after(TransformSystems::Propagate));
+    }
+}
+
+fn spawn_ground_grid(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<GridMaterial>>,
+) {
+    commands.spawn((
+        // 边长 2 的平面，缩放为淡出半径
+        Mesh3d(meshes.add(Plane3d::default().mesh().size(2.0, 2.0))),
+        MeshMaterial3d(materials.add(GridMaterial { color: LinearRgba::WHITE, params: Vec4::new(1.0, MAJOR_EVERY, 1.0, 1.0) })),
+        Transform::default(),
+        Visibility::default(),
+        GroundGridPlane,
+        NotShadowCaster,
+        Pickable::IGNORE,
+        Name::new("GroundGrid"),
+    ));
+}
+
+/// 网格平面跟随相机，按离地高度更新间距、淡出半径与比例尺
+fn update_ground_grid(
+    mut grid: ResMut<GroundGrid>,
+    light_mode: Res<LightMode>,
+    camera: Query<(&GlobalTransform, &Camera, &Projection), With<Camera3d>>,
+    mut plane: Query<(&mut Transform, &mut Visibility, &MeshMaterial3d<GridMaterial>), With<GroundGridPlane>>,
+    mut materials: ResMut<Assets<GridMaterial>>,
+) {
+    let Ok((mut transform, mut visibility, material)) = plane.single_mut() else { return };
+    let target = if grid.visible { Visibility::Inherited } else { Visibility::Hidden };
+    visibility.set_if_neq(target);
+    let Ok((camera_transform, camera, projection)) = camera.single() else { return };
+    let origin = camera_transform.translation();
+    let viewport_height = camera.logical_viewport_size().map_or(0.0, |size| size.y);
+
+    // 正交投影没有透视缩小，以可见高度代替离地高度
+    let (height, pixels_per_unit) = match projection {
+        Projection::Orthographic(ortho) => {
+            let visible = ortho.area.height().max(1e-3);
+            (visible, viewport_height / visible)
+        }
+        Projection::Perspective(perspective) => {
+            let height = origin.y.abs().max(0.05);
+            let distance = ground_distance(origin, camera_transform.forward().as_vec3()).unwrap_or(height);
+            (height, viewport_height / (2.0 * distance * (perspective.fov * 0.5).tan()))
+        }
+        _ => (origin.y.abs().max(0.05), 0.0),
+    };
+    let (spacing, minor_alpha) = grid_spacing(height);
+    let fade_radius = height * FADE_PER_HEIGHT;
+    if grid.spacing != spacing || grid.pixels_per_unit != pixels_per_unit {
+        grid.spacing = spacing;
+        grid.pixels_per_unit = pixels_per_unit;
+    }
+
+    let translation = Vec3::new(origin.x, 0.0, origin.z);
+    if transform.translation != translation || transform.scale.x != fade_radius {
+        *transform = Transform::from_translation(translation).with_scale(Vec3::splat(fade_radius));
+    }
+
+    let color = match *light_mode {
+        LightMode::Light => LinearRgba::from(Color::srgba(0.2, 0.25, 0.3, 0.6)),
+        LightMode::Dark => LinearRgba::from(Color::srgba(0.65, 0.65, 0.7, 0.45)),
+    };
+    let params = Vec4::new(spacing, MAJOR_EVERY, minor_alpha, fade_radius);
+    if materials.get(&material.0).is_some_and(|m| m.color == color && m.params == params) {
+        return;
+    }
+    if let Some(m) = materials.get_mut(&material.0) {
+        m.color = color;
+        m.params = params;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grid_spacing_adapts_to_height() {
+        assert_eq!(grid_spacing(4.0).0, 1.0);
+        assert_eq!(grid_spacing(9.0).0, 1.0);
+        assert_eq!(grid_spacing(40.0).0, 10.0);
+        assert!((grid_spacing(0.5).0 - 0.1).abs() < 1e-6);
+        // 刚切换到新一档时次网格完全可见，接近下一档时淡出
+        assert!((grid_spacing(4.0).1 - 1.0).abs() < 1e-6);
+        assert!(grid_spacing(39.0).1 < 0.05);
+    }
+
+    #[test]
+    fn test_format_length_and_ground_distance() {
+        assert_eq!(format_length(1.0), "1 m");
+        assert_eq!(format_length(10.0), "10 m");
+        assert_eq!(format_length(0.1), "10 cm");
+        assert_eq!(format_length(0.001), "1 mm");
+        assert_eq!(format_length(1000.0), "1 km");
+
+        let forward = Vec3::new(0.0, -1.0, -1.0).normalize();
+        let distance = ground_distance(Vec3::new(0.0, 5.0, 0.0), forward).unwrap();
+        assert!((distance - 5.0 * 2f32.sqrt()).abs() < 1e-4);
+        assert_eq!(ground_distance(Vec3::new(0.0, 5.0, 0.0), Vec3::Z), None);
+        assert_eq!(ground_distance(Vec3::new(0.0, 5.0, 0.0), Vec3::Y), None);
+    }
+}
@@ -38,7 +38,7 @@ pub fn spawn_entity_with_vis(
             Mesh3d(meshes.add(Sphere::new(0.1)))
         });
     let material = material_manager.load_generic_material(material_name, asset_server);
-    let transform_comp = apply_coord_system(conversion::proto_transform_to_bevy(transform), handedness);
+    let transform_comp = apply_coord_system(conversion::proto_transform_to_bevy(transform) * conversion::mesh_pose(mesh), handedness);
 
     use std::time::{SystemTime, UNIX_EPOCH};
     let entity_id = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_nanos() as u64;
@@ -1,10 +1,256 @@
+//! 相机交互 — FPS 控制器（光标锁定时）与视口鼠标操作模式、标准视图（光标未锁定时）
+//!
+//! 光标未锁定时，左键在视口中拖动按 [`CameraMode`] 平移、绕目标点旋转或缩放；
+//! 选取模式下拖动不移动相机，点击拾取实体。标准视图沿当前坐标系的向上轴摆放相机，
+//! 可选切换为正交投影，重置视角时恢复透视投影。
+
+use bevy::camera::ScalingMode;
+use bevy::input::mouse::MouseMotion;
 use bevy::prelude::*;
+use bevy::window::{CursorGrabMode, CursorOptions};
+use smooth_bevy_cameras::LookTransform;
 use smooth_bevy_cameras::controllers::fps::FpsCameraPlugin;
 
+use crate::render::coord_system::{CoordSystem, UpAxis, apply_coord_system};
+
+/// 旋转灵敏度（弧度/像素）
+const ROTATE_PER_PIXEL: f32 = 0.005;
+
+/// 缩放灵敏度：每像素的对数缩放量
+const ZOOM_PER_PIXEL: f32 = 0.01;
+
+/// 相机到目标点的最小距离
+const MIN_RADIUS: f32 = 0.05;
+
+/// 视线与竖直方向的最小夹角余弦余量，避免旋转越过天顶
+const MAX_VERTICAL: f32 = 0.999;
+
+/// Bevy Resource：视口鼠标操作模式（光标未锁定时左键拖动的行为）
+#[derive(Resource, Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CameraMode {
+    /// 点击拾取实体，拖动不移动相机
+    #[default]
+    Select,
+    Pan,
+    Rotate,
+    Zoom,
+}
+
+impl CameraMode {
+    pub fn label(&self) -> &'static str {
+        match self {
+            CameraMode::Select => "选中",
+            CameraMode::Pan => "平移",
+            CameraMode::Rotate => "旋转",
+            CameraMode::Zoom => "缩放",
+        }
+    }
+}
+
+/// 标准视图
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CannedView {
+    /// 沿向上轴俯视
+    Top,
+    /// 从右侧水平看向目标
+    Side,
+    /// 从前方水平看向目标
+    Front,
+}
+
+/// 标准视图请求资源（由轮盘菜单等设置，下一帧应用）
+#[derive(Resource, Default)]
+pub struct CannedViewRequest(pub Option<CannedView>);
+
+/// Bevy Resource：标准视图是否切换为正交投影
+#[derive(Resource, Default)]
+pub struct OrthographicViews(pub bool);
+
 pub struct CameraInteractionPlugin;
 
 impl Plugin for CameraInteractionPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins(FpsCameraPlugin::default());
+        app.add_plugins(FpsCameraPlugin::default())
+            .init_resource::<CameraMode>()
+            .init_resource::<CannedViewRequest>()
+            .init_resource::<OrthographicViews>()
+            .add_systems(Update, (apply_canned_view, drag_camera, level_camera_up).chain());
+    }
+}
+
+/// 数据坐标系中与向上轴构成右手系的「右」与「前」（朝向观察者）方向，如 Y 向上时为 +X 与 +Z
+fn data_frame(up: UpAxis) -> (Vec3, Vec3) {
+    match up {
+        UpAxis::PlusY | UpAxis::MinusY => (Vec3::X, Vec3::Z),
+        UpAxis::PlusZ | UpAxis::MinusZ => (Vec3::Y, Vec3::X),
+        UpAxis::PlusX | UpAxis::MinusX => (Vec3::Z, Vec3::Y),
+    }
+}
+
+/// 标准视图的相机位姿：目标点不变，相机到目标的距离保持 `distance`
+pub fn canned_view(view: CannedView, coord: CoordSystem, target: Vec3, distance: f32) -> LookTransform {
+    // 数据方向映射到显示坐标系（向上轴恒为 +Y），取水平分量
+    let to_bevy = |dir: Vec3| {
+        let mapped = apply_coord_system(Transform::from_translation(dir), coord).translation;
+        Vec3::new(mapped.x, 0.0, mapped.z).normalize_or(dir)
+    };
+    let (right, front) = data_frame(coord.up_axis);
+    let distance = distance.max(MIN_RADIUS);
+    match view {
+        // 俯视时屏幕上方为远离观察者的方向
+        CannedView::Top => LookTransform::new(target + Vec3::Y * distance, target, -to_bevy(front)),
+        CannedView::Side => LookTransform::new(target + to_bevy(right) * distance, target, Vec3::Y),
+        CannedView::Front => LookTransform::new(target + to_bevy(front) * distance, target, Vec3::Y),
+    }
+}
+
+/// 与透视投影在目标距离处可见范围一致的正交投影
+fn matching_orthographic(projection: &Projection, distance: f32) -> Projection {
+    let fov = match projection {
+        Projection::Perspective(perspective) => perspective.fov,
+        _ => PerspectiveProjection::default().fov,
+    };
+    Projection::Orthographic(OrthographicProjection {
+        scaling_mode: ScalingMode::FixedVertical { viewport_height: 2.0 * distance * (fov * 0.5).tan() },
+        ..OrthographicProjection::default_3d()
+    })
+}
+
+fn apply_canned_view(
+    mut request: ResMut<CannedViewRequest>,
+    orthographic: Res<OrthographicViews>,
+    coord: Res<CoordSystem>,
+    mut cameras: Query<(&mut LookTransform, &mut Projection), With<Camera3d>>,
+) {
+    let Some(view) = request.0.take() else { return };
+    for (mut look, mut projection) in cameras.iter_mut() {
+        let distance = look.radius();
+        *look = canned_view(view, *coord, look.target, distance);
+        *projection = if orthographic.0 {
+            matching_orthographic(&projection, distance)
+        } else {
+            Projection::Perspective(PerspectiveProjection::default())
+        };
+        log::info!("切换到标准视图 {:?}（{}）", view, if orthographic.0 { "正交" } else { "透视" });
+    }
+}
+
+/// 视口中每像素对应的世界长度（目标点处）
+fn world_per_pixel(projection: &Projection, distance: f32, viewport_height: f32) -> f32 {
+    let visible = match projection {
+        Projection::Perspective(perspective) => 2.0 * distance * (perspective.fov * 0.5).tan(),
+        Projection::Orthographic(ortho) => ortho.area.height(),
+        _ => distance,
+    };
+    visible / viewport_height.max(1.0)
+}
+
+/// 绕目标点旋转：水平拖动绕竖直轴，竖直拖动绕相机右轴，不越过天顶与天底
+fn orbit(look: &mut LookTransform, delta: Vec2) {
+    let offset = look.eye - look.target;
+    let Some(dir) = (-offset).try_normalize() else { return };
+    let right = dir.cross(look.up).normalize_or(Vec3::X);
+    let yaw = Quat::from_rotation_y(-delta.x * ROTATE_PER_PIXEL);
+    let pitch = Quat::from_axis_angle(right, -delta.y * ROTATE_PER_PIXEL);
+    let pitched = pitch * offset;
+    let rotation = if pitched.normalize_or_zero().y.abs() < MAX_VERTICAL { yaw * pitch } else { yaw };
+    look.eye = look.target + rotation * offset;
+    look.up = rotation * look.up;
+}
+
+/// 光标未锁定时按操作模式处理左键拖动
+fn drag_camera(
+    mode: Res<CameraMode>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    mut motion: MessageReader<MouseMotion>,
+    cursor_options: Single<&CursorOptions>,
+    egui_wants: Res<bevy_egui::input::EguiWantsInput>,
+    mut cameras: Query<(&Camera, &mut LookTransform, &mut Projection), With<Camera3d>>,
+) {
+    let delta: Vec2 = motion.read().map(|m| m.delta).sum();
+    if *mode == CameraMode::Select
+        || cursor_options.grab_mode == CursorGrabMode::Locked
+        || !mouse.pressed(MouseButton::Left)
+        || egui_wants.wants_any_pointer_input()
+        || delta == Vec2::ZERO
+    {
+        return;
+    }
+    for (camera, mut look, mut projection) in cameras.iter_mut() {
+        let distance = look.radius();
+        match *mode {
+            CameraMode::Pan => {
+                let Some(dir) = look.look_direction() else { continue };
+                let right = dir.cross(look.up).normalize_or(Vec3::X);
+                let up = right.cross(dir);
+                let viewport_height = camera.logical_viewport_size().map_or(1.0, |size| size.y);
+                let shift = (up * delta.y - right * delta.x) * world_per_pixel(&projection, distance, viewport_height);
+                look.eye += shift;
+                look.target += shift;
+            }
+            CameraMode::Rotate => orbit(&mut look, delta),
+            CameraMode::Zoom => {
+                // 向下拖动拉远，向上拖动拉近
+                let factor = (delta.y * ZOOM_PER_PIXEL).exp();
+                let offset = look.eye - look.target;
+                look.eye = look.target + offset * ((distance * factor).max(MIN_RADIUS) / distance.max(MIN_RADIUS));
+                if let Projection::Orthographic(ortho) = projection.as_mut() {
+                    ortho.scale = (ortho.scale * factor).max(1e-4);
+                }
+            }
+            CameraMode::Select => {}
+        }
+    }
+}
+
+/// 俯视后的相机「上」方向为水平方向，视线离开竖直方向后恢复为 +Y，避免画面侧倾
+fn level_camera_up(mut cameras: Query<&mut LookTransform, With<Camera3d>>) {
+    for mut look in cameras.iter_mut() {
+        if look.up == Vec3::Y {
+            continue;
+        }
+        let Some(dir) = look.look_direction() else { continue };
+        if dir.y.abs() < 0.99 {
+            look.up = Vec3::Y;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render::coord_system::Handedness;
+
+    fn assert_near(a: Vec3, b: Vec3) {
+        assert!(a.distance(b) < 1e-4, "{a:?} vs {b:?}");
+    }
+
+    #[test]
+    fn test_canned_views_follow_up_axis() {
+        let y_up = CoordSystem::default();
+        let top = canned_view(CannedView::Top, y_up, Vec3::ZERO, 5.0);
+        assert_near(top.eye, Vec3::new(0.0, 5.0, 0.0));
+        assert_near(top.up, -Vec3::Z);
+        assert_near(canned_view(CannedView::Front, y_up, Vec3::ZERO, 5.0).eye, Vec3::new(0.0, 0.0, 5.0));
+        assert_near(canned_view(CannedView::Side, y_up, Vec3::ONE, 5.0).eye, Vec3::new(6.0, 1.0, 1.0));
+
+        // Z 向上：俯视仍沿显示坐标系 +Y，前视沿数据 +X 映射后的方向
+        let z_up = CoordSystem { up_axis: UpAxis::PlusZ, handedness: Handedness::LeftHanded, show_axes: true };
+        let front = canned_view(CannedView::Front, z_up, Vec3::ZERO, 2.0);
+        assert_near(front.eye, Vec3::new(2.0, 0.0, 0.0));
+        assert_near(canned_view(CannedView::Top, z_up, Vec3::ZERO, 2.0).eye, Vec3::new(0.0, 2.0, 0.0));
+    }
+
+    #[test]
+    fn test_orbit_stops_at_zenith() {
+        let mut look = LookTransform::new(Vec3::new(0.0, 0.0, 5.0), Vec3::ZERO, Vec3::Y);
+        orbit(&mut look, Vec2::new(0.0, -10_000.0));
+        // 俯仰越过天顶时只保留水平旋转，距离不变
+        assert!((look.radius() - 5.0).abs() < 1e-4);
+        assert!(look.look_direction().unwrap().y.abs() < MAX_VERTICAL);
+
+        let mut look = LookTransform::new(Vec3::new(0.0, 0.0, 5.0), Vec3::ZERO, Vec3::Y);
+        orbit(&mut look, Vec2::new(std::f32::consts::FRAC_PI_2 / ROTATE_PER_PIXEL, 0.0));
+        assert_near(look.eye, Vec3::new(-5.0, 0.0, 0.0));
     }
 }
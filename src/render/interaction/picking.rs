@@ -4,6 +4,7 @@ use bevy::picking::pointer::PointerInteraction;
 use bevy::mesh::VertexAttributeValues;
 
 use crate::render::interaction::InteractionMessage;
+use crate::render::interaction::camera::CameraMode;
 
 /// 标记组件：可拾取的实体
 #[derive(Component, Debug)]
@@ -35,15 +36,21 @@ pub fn handle_dynamic_entity_pick(
     previously_selected: Query<Entity, With<Selected>>,
     selection_boxes: Query<Entity, With<SelectionBox>>,
     pickable_query: Query<&PickableEntity>,
-    transform_query: Query<&Transform>,
+    transform_query: Query<&GlobalTransform>,
     mesh3d_query: Query<&Mesh3d>,
     keyboard: Res<ButtonInput<KeyCode>>,
+    mode: Res<CameraMode>,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
 ) {
+    // 平移/旋转/缩放模式下左键用于操作相机
+    if *mode != CameraMode::Select {
+        return;
+    }
     let picked_entity = trigger.entity;
 
-    let Ok(picked_transform) = transform_query.get(picked_entity) else {
+    // 子实体的 Transform 是相对父实体的，高亮框使用世界变换
+    let Ok(picked_transform) = transform_query.get(picked_entity).map(GlobalTransform::compute_transform) else {
         warn!("无法获取被点击实体的 Transform: {:?}", picked_entity);
         return;
     };
@@ -65,7 +72,7 @@ pub fn handle_dynamic_entity_pick(
     }
 
     commands.entity(picked_entity).insert(Selected);
-    create_highlight_box(&mut commands, &mut meshes, &mut materials, picked_transform, picked_entity, &mesh3d_query);
+    create_highlight_box(&mut commands, &mut meshes, &mut materials, &picked_transform, picked_entity, &mesh3d_query);
     info!("实体 {} (ID: {}) 已被选中", picked_entity, entity_id);
 }
 
@@ -189,8 +196,9 @@ pub fn detect_empty_click(
     previously_selected: Query<Entity, With<Selected>>,
     mut im: ResMut<InteractionMessage>,
     egui_wants: Res<bevy_egui::input::EguiWantsInput>,
+    mode: Res<CameraMode>,
 ) {
-    if !mouse.just_pressed(MouseButton::Left) {
+    if !mouse.just_pressed(MouseButton::Left) || *mode != CameraMode::Select {
         return;
     }
     // egui 正在请求指针输入（侧边栏、编辑框等），不触发取消选择
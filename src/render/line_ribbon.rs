@@ -0,0 +1,233 @@
+//! 线段的带状线（ribbon）绘制
+//!
+//! 线段网格由 4 个顶点组成：两个端点各两个，顶点同时携带另一端点的位置，
+//! 由顶点着色器在屏幕空间沿线段法向展开，得到恒定像素宽度或世界宽度的带状线；
+//! 虚线在片元着色器中按沿线距离裁剪。端点即实体局部坐标，端点变化只需重建网格。
+//! 网格带顶点颜色（如折线的逐顶点颜色）时与材质颜色相乘。
+
+use bevy::asset::RenderAssetUsages;
+use bevy::mesh::{Indices, MeshVertexAttribute, MeshVertexBufferLayoutRef};
+use bevy::pbr::{MaterialPipeline, MaterialPipelineKey, MaterialPlugin};
+use bevy::prelude::*;
+use bevy::render::render_resource::{
+    AsBindGroup, PrimitiveTopology, RenderPipelineDescriptor, SpecializedMeshPipelineError, VertexFormat,
+};
+use bevy::shader::ShaderRef;
+use expto::rdmp::{Line, Polyline};
+
+use crate::assets::materials::{GenericMaterial, generic_base_color};
+
+const SHADER_ASSET_PATH: &str = "shaders/line_ribbon.wgsl";
+
+/// 未指定线宽时的默认宽度（屏幕像素）
+pub const DEFAULT_LINE_WIDTH_PX: f32 = 2.0;
+
+/// 顶点属性：线段另一端点（实体局部坐标）
+pub const ATTRIBUTE_LINE_OTHER: MeshVertexAttribute =
+    MeshVertexAttribute::new("Vertex_LineOther", 988_540_917, VertexFormat::Float32x3);
+
+/// 带状线材质 — 无光照纯色，颜色跟随实体的通用材质
+#[derive(Asset, TypePath, AsBindGroup, Debug, Clone)]
+pub struct LineMaterial {
+    #[uniform(0)]
+    pub color: LinearRgba,
+    /// x: 线宽，y: 1 为屏幕像素，z: 虚线周期（0 为实线）
+    #[uniform(1)]
+    pub params: Vec4,
+    /// 虚线模式：实线段 / 间隔 / 实线段 / 间隔
+    #[uniform(2)]
+    pub dash: Vec4,
+}
+
+impl LineMaterial {
+    /// 按协议线段的线宽与虚线设置构建（颜色由 [`LineColorSource`] 同步）
+    pub fn new(line: &Line) -> Self {
+        let (width, screen_space) = if line.width > 0.0 {
+            (line.width, line.screen_space)
+        } else {
+            (DEFAULT_LINE_WIDTH_PX, true)
+        };
+        let dash = dash_pattern(&line.dash);
+        Self {
+            color: LinearRgba::WHITE,
+            params: Vec4::new(width, if screen_space { 1.0 } else { 0.0 }, dash.element_sum(), 0.0),
+            dash,
+        }
+    }
+
+    /// 折线：线宽为世界单位，未指定时为默认屏幕宽度；实线
+    pub fn polyline(polyline: &Polyline) -> Self {
+        Self::new(&Line { width: polyline.width, screen_space: false, ..Line::default() })
+    }
+
+    /// 包围盒棱边：默认屏幕宽度的实线
+    pub fn edges() -> Self {
+        Self::new(&Line::default())
+    }
+}
+
+/// 虚线模式规整为 4 段：单个值表示等长实线与间隔，3 个值时末项忽略，超出 4 个截断；
+/// 含非正数或非有限值时视为实线
+fn dash_pattern(dash: &[f32]) -> Vec4 {
+    if dash.iter().any(|d| !d.is_finite() || *d <= 0.0) {
+        return Vec4::ZERO;
+    }
+    match *dash {
+        [] => Vec4::ZERO,
+        [a] => Vec4::splat(a),
+        [a, b] | [a, b, _] => Vec4::new(a, b, a, b),
+        [a, b, c, d, ..] => Vec4::new(a, b, c, d),
+    }
+}
+
+impl Material for LineMaterial {
+    fn vertex_shader() -> ShaderRef {
+        SHADER_ASSET_PATH.into()
+    }
+
+    fn fragment_shader() -> ShaderRef {
+        SHADER_ASSET_PATH.into()
+    }
+
+    // 网格三角形在展开前退化，预渲染与阴影无意义
+    fn enable_prepass() -> bool {
+        false
+    }
+
+    fn enable_shadows() -> bool {
+        false
+    }
+
+    fn specialize(
+        _pipeline: &MaterialPipeline,
+        descriptor: &mut RenderPipelineDescriptor,
+        layout: &MeshVertexBufferLayoutRef,
+        _key: MaterialPipelineKey<Self>,
+    ) -> Result<(), SpecializedMeshPipelineError> {
+        let mut attributes = vec![
+            Mesh::ATTRIBUTE_POSITION.at_shader_location(0),
+            ATTRIBUTE_LINE_OTHER.at_shader_location(1),
+            Mesh::ATTRIBUTE_UV_0.at_shader_location(2),
+        ];
+        if layout.0.contains(Mesh::ATTRIBUTE_COLOR) {
+            attributes.push(Mesh::ATTRIBUTE_COLOR.at_shader_location(3));
+            descriptor.vertex.shader_defs.push("VERTEX_COLORS".into());
+            if let Some(fragment) = descriptor.fragment.as_mut() {
+                fragment.shader_defs.push("VERTEX_COLORS".into());
+            }
+        }
+        descriptor.vertex.buffers = vec![layout.0.get_layout(&attributes)?];
+        // 带状线朝向随视角变化，双面绘制
+        descriptor.primitive.cull_mode = None;
+        Ok(())
+    }
+}
+
+/// 带状线颜色来源：实体的通用材质（取其 `StandardMaterial` 基础色）
+#[derive(Component)]
+pub struct LineColorSource(pub Handle<GenericMaterial>);
+
+/// 线段实体的材质组件
+pub fn line_bundle(material: Handle<LineMaterial>, source: Handle<GenericMaterial>) -> impl Bundle {
+    (MeshMaterial3d(material), LineColorSource(source))
+}
+
+/// 构建线段的带状线网格 — UV 的 x 为端点（0 起点 / 1 终点），y 为展开方向（±1）
+pub fn line_ribbon_mesh(start: Vec3, end: Vec3) -> Mesh {
+    ribbon_mesh(&[(start, end)])
+}
+
+/// 多条线段合并为一个带状线网格（如包围盒的 12 条棱），每条线段 4 个顶点
+pub fn ribbon_mesh(segments: &[(Vec3, Vec3)]) -> Mesh {
+    let mut positions = Vec::with_capacity(segments.len() * 4);
+    let mut others = Vec::with_capacity(segments.len() * 4);
+    let mut uvs = Vec::with_capacity(segments.len() * 4);
+    let mut indices = Vec::with_capacity(segments.len() * 6);
+    for (i, &(start, end)) in segments.iter().enumerate() {
+        let (s, e) = (start.to_array(), end.to_array());
+        let base = i as u32 * 4;
+        positions.extend([s, s, e, e]);
+        others.extend([e, e, s, s]);
+        uvs.extend([[0.0, -1.0], [0.0, 1.0], [1.0, -1.0], [1.0, 1.0]]);
+        indices.extend([base, base + 2, base + 1, base + 1, base + 2, base + 3]);
+    }
+    Mesh::new(PrimitiveTopology::TriangleList, RenderAssetUsages::default())
+        .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
+        .with_inserted_attribute(ATTRIBUTE_LINE_OTHER, others)
+        .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, uvs)
+        .with_inserted_indices(Indices::U32(indices))
+}
+
+pub struct LineRibbonPlugin;
+
+impl Plugin for LineRibbonPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(MaterialPlugin::<LineMaterial>::default())
+            .add_systems(Update, sync_line_colors);
+    }
+}
+
+/// 将通用材质的颜色同步到带状线材质（仅在变化时写入）
+fn sync_line_colors(
+    query: Query<(&LineColorSource, &MeshMaterial3d<LineMaterial>)>,
+    generic_materials: Res<Assets<GenericMaterial>>,
+    standard_materials: Res<Assets<StandardMaterial>>,
+    mut line_materials: ResMut<Assets<LineMaterial>>,
+) {
+    for (source, material) in &query {
+        let Some(color) = generic_base_color(&generic_materials, &standard_materials, &source.0) else { continue };
+        if line_materials.get(&material.0).is_some_and(|line| line.color == color) {
+            continue;
+        }
+        if let Some(line) = line_materials.get_mut(&material.0) {
+            line.color = color;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_material_width() {
+        // 未指定线宽：默认屏幕像素宽度
+        let line = Line::from(([0.0, 0.0, 0.0].into(), [1.0, 0.0, 0.0].into()));
+        assert_eq!(LineMaterial::new(&line).params, Vec4::new(DEFAULT_LINE_WIDTH_PX, 1.0, 0.0, 0.0));
+
+        let world = Line { width: 0.2, dash: vec![0.5, 0.25], ..line.clone() };
+        let material = LineMaterial::new(&world);
+        assert_eq!(material.params, Vec4::new(0.2, 0.0, 1.5, 0.0));
+        assert_eq!(material.dash, Vec4::new(0.5, 0.25, 0.5, 0.25));
+
+        let screen = Line { width: 4.0, screen_space: true, ..line };
+        assert_eq!(LineMaterial::new(&screen).params.y, 1.0);
+
+        // 折线线宽为世界单位，未指定时为默认屏幕宽度
+        let mut polyline = Polyline::from(vec![[0.0, 0.0, 0.0], [1.0, 0.0, 0.0]]);
+        assert_eq!(LineMaterial::polyline(&polyline).params, Vec4::new(DEFAULT_LINE_WIDTH_PX, 1.0, 0.0, 0.0));
+        polyline.width = 0.3;
+        assert_eq!(LineMaterial::polyline(&polyline).params, Vec4::new(0.3, 0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_dash_pattern() {
+        assert_eq!(dash_pattern(&[]), Vec4::ZERO);
+        assert_eq!(dash_pattern(&[1.0]), Vec4::splat(1.0));
+        assert_eq!(dash_pattern(&[3.0, 1.0, 1.0]), Vec4::new(3.0, 1.0, 3.0, 1.0));
+        assert_eq!(dash_pattern(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]), Vec4::new(1.0, 2.0, 3.0, 4.0));
+        assert_eq!(dash_pattern(&[1.0, -1.0]), Vec4::ZERO);
+    }
+
+    #[test]
+    fn test_ribbon_mesh_endpoints() {
+        let mesh = line_ribbon_mesh(Vec3::ZERO, Vec3::new(3.0, 4.0, 0.0));
+        assert_eq!(mesh.count_vertices(), 4);
+        assert_eq!(mesh.indices().unwrap().len(), 6);
+        let Some(bevy::mesh::VertexAttributeValues::Float32x3(other)) = mesh.attribute(ATTRIBUTE_LINE_OTHER) else {
+            panic!("缺少另一端点");
+        };
+        assert_eq!(other[0], [3.0, 4.0, 0.0]);
+        assert_eq!(other[3], [0.0, 0.0, 0.0]);
+    }
+}
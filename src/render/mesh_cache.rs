@@ -0,0 +1,120 @@
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+use expto::rdmp::ExMesh;
+
+use crate::render::conversion::{MeshShape, Primitive, mesh_shape};
+
+/// 图元网格的缓存键 — 尺寸参数按 f32 位模式参与比较
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum MeshKey {
+    Point,
+    Sphere { radius: u32 },
+    Cylinder { radius: u32, height: u32 },
+    Cone { radius: u32, height: u32 },
+    Cuboid { size: [u32; 3] },
+    BoxEdges { half: [u32; 3] },
+}
+
+impl From<Primitive> for MeshKey {
+    fn from(primitive: Primitive) -> Self {
+        let bits = |v: Vec3| v.to_array().map(f32::to_bits);
+        match primitive {
+            Primitive::Point => MeshKey::Point,
+            Primitive::Sphere { radius } => MeshKey::Sphere { radius: radius.to_bits() },
+            Primitive::Cylinder { radius, height } => MeshKey::Cylinder { radius: radius.to_bits(), height: height.to_bits() },
+            Primitive::Cone { radius, height } => MeshKey::Cone { radius: radius.to_bits(), height: height.to_bits() },
+            Primitive::Cuboid { size } => MeshKey::Cuboid { size: bits(size) },
+            Primitive::BoxEdges { half } => MeshKey::BoxEdges { half: bits(half) },
+        }
+    }
+}
+
+/// Bevy Resource：图元网格资产缓存
+///
+/// 相同参数的图元共享同一 `Handle<Mesh>`；材质也相同的实体由 bevy 自动合批为 GPU 实例化绘制。
+/// 可以用缩放表达尺寸时（实体没有子实体）一律使用单位图元，使不同尺寸的同类图元也共享网格。
+/// 三角网格、折线等自带顶点数据的网格不缓存。
+#[derive(Resource, Default)]
+pub struct MeshCache {
+    handles: HashMap<MeshKey, Handle<Mesh>>,
+}
+
+impl MeshCache {
+    /// 取得协议网格对应的网格句柄，以及需叠加到实体变换上的缩放
+    ///
+    /// `unit_scale` 为真时图元换成单位尺寸，尺寸由返回的缩放表达；
+    /// 有子实体的实体应传 `false`，避免缩放传递给子实体。
+    pub fn mesh(&mut self, meshes: &mut Assets<Mesh>, proto_mesh: &ExMesh, unit_scale: bool) -> Option<(Mesh3d, Vec3)> {
+        match mesh_shape(proto_mesh)? {
+            MeshShape::Primitive(primitive) => Some(self.primitive(meshes, primitive, unit_scale)),
+            MeshShape::Custom(mesh) => Some((Mesh3d(meshes.add(mesh)), Vec3::ONE)),
+        }
+    }
+
+    /// 取得图元的共享网格句柄（不存在时创建），以及需叠加的缩放
+    pub fn primitive(&mut self, meshes: &mut Assets<Mesh>, primitive: Primitive, unit_scale: bool) -> (Mesh3d, Vec3) {
+        let (primitive, scale) = if unit_scale { primitive.unit() } else { (primitive, Vec3::ONE) };
+        let handle = self.handles.entry(MeshKey::from(primitive))
+            .or_insert_with(|| meshes.add(primitive.mesh()))
+            .clone();
+        (Mesh3d(handle), scale)
+    }
+
+    /// 已缓存的图元网格数
+    pub fn len(&self) -> usize {
+        self.handles.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.handles.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use expto::rdmp::{Cylinder, OrientedBox, Sphere};
+
+    #[test]
+    fn test_same_parameters_share_mesh() {
+        let mut meshes = Assets::<Mesh>::default();
+        let mut cache = MeshCache::default();
+        let sphere = |radius| ExMesh::from(Sphere { location: None, radius });
+
+        let (a, _) = cache.mesh(&mut meshes, &sphere(0.2), false).unwrap();
+        let (b, _) = cache.mesh(&mut meshes, &sphere(0.2), false).unwrap();
+        let (c, _) = cache.mesh(&mut meshes, &sphere(0.3), false).unwrap();
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+        assert_eq!(meshes.len(), 2);
+
+        // 单位图元：不同尺寸共享网格，尺寸转为缩放
+        let (d, scale) = cache.mesh(&mut meshes, &sphere(0.3), true).unwrap();
+        let (e, _) = cache.mesh(&mut meshes, &sphere(5.0), true).unwrap();
+        assert_eq!(d, e);
+        assert_eq!(scale, Vec3::splat(0.3));
+        assert_eq!(meshes.len(), 3);
+    }
+
+    #[test]
+    fn test_unit_mesh_scaled_matches_sized_mesh() {
+        use bevy::camera::primitives::MeshAabb;
+
+        let mut meshes = Assets::<Mesh>::default();
+        let mut cache = MeshCache::default();
+        let shapes = [
+            ExMesh::from(Cylinder { radius: 0.4, height: 3.0 }),
+            ExMesh::from(OrientedBox::from(([0.0, 0.0, 0.0], [1.0, 0.5, 0.25], [0.0, 0.0, 0.0, 1.0]))),
+        ];
+        for shape in shapes {
+            let (sized, _) = cache.mesh(&mut meshes, &shape, false).unwrap();
+            let (unit, scale) = cache.mesh(&mut meshes, &shape, true).unwrap();
+            let sized = meshes.get(&sized.0).unwrap().compute_aabb().unwrap();
+            let unit = meshes.get(&unit.0).unwrap().compute_aabb().unwrap();
+            let expected = Vec3::from(sized.half_extents);
+            let actual = Vec3::from(unit.half_extents) * scale;
+            assert!((expected - actual).length() < 1e-4, "{:?} vs {:?}", expected, actual);
+        }
+    }
+}
@@ -0,0 +1,250 @@
+//! 大点云的多级细节（LOD）
+//!
+//! 点云组超过 [`LOD_MIN_POINTS`] 个点时构建体素网格 LOD：按由粗到细的顺序重排全部点，
+//! 第 k 级为体素边长 `extent / 2^(k+4)` 时每个体素保留一个点，且包含之前所有级的点，
+//! 因此任意一级都是重排后数组的前缀，切换级别只需截取前缀重建网格。
+//! 数据层（`KeyFrame` 中的实体）与组缓存中仍保留全分辨率数据，供拾取与导出使用。
+
+use std::collections::HashSet;
+
+use bevy::prelude::*;
+
+/// 点数达到该值的点云组才构建 LOD
+pub const LOD_MIN_POINTS: usize = 100_000;
+
+/// 最粗一级体素数量级（每轴 16 格），之后每级体素边长减半
+const COARSEST_DIVISIONS: f32 = 16.0;
+
+/// 最多细分级数（不含最后的全分辨率级）
+const MAX_LEVELS: usize = 12;
+
+/// 体素网格 LOD
+#[derive(Debug, Clone)]
+pub struct PointLod {
+    /// 由粗到细重排后的全部点
+    points: Vec<[f32; 3]>,
+    /// 各级末尾位置（前缀长度），最后一级为全部点
+    level_ends: Vec<usize>,
+    center: Vec3,
+    radius: f32,
+}
+
+impl PointLod {
+    pub fn build(points: &[[f32; 3]]) -> Self {
+        let (min, max) = points.iter().fold((Vec3::MAX, Vec3::MIN), |(min, max), &p| {
+            (min.min(Vec3::from(p)), max.max(Vec3::from(p)))
+        });
+        let (center, radius) = if points.is_empty() {
+            (Vec3::ZERO, 0.0)
+        } else {
+            ((min + max) * 0.5, (max - min).length() * 0.5)
+        };
+
+        let mut order: Vec<usize> = Vec::with_capacity(points.len());
+        let mut taken = vec![false; points.len()];
+        let mut level_ends = Vec::new();
+        let extent = (max - min).max_element();
+        if extent > 0.0 {
+            let mut voxel = extent / COARSEST_DIVISIONS;
+            for _ in 0..MAX_LEVELS {
+                if order.len() == points.len() {
+                    break;
+                }
+                let key = |p: [f32; 3]| ((Vec3::from(p) - min) / voxel).floor().as_ivec3();
+                // 已选中的点先占据体素，保证每级都包含上一级
+                let mut occupied: HashSet<IVec3> = order.iter().map(|&i| key(points[i])).collect();
+                for (i, &p) in points.iter().enumerate() {
+                    if !taken[i] && occupied.insert(key(p)) {
+                        taken[i] = true;
+                        order.push(i);
+                    }
+                }
+                level_ends.push(order.len());
+                voxel *= 0.5;
+            }
+        }
+        // 剩余的点（重合点或超出细分级数）构成全分辨率级
+        order.extend((0..points.len()).filter(|&i| !taken[i]));
+        if level_ends.last() != Some(&points.len()) {
+            level_ends.push(points.len());
+        }
+
+        Self {
+            points: order.into_iter().map(|i| points[i]).collect(),
+            level_ends,
+            center,
+            radius,
+        }
+    }
+
+    /// 级数（最后一级为全分辨率）
+    pub fn level_count(&self) -> usize {
+        self.level_ends.len()
+    }
+
+    /// 第 `level` 级的点
+    pub fn level(&self, level: usize) -> &[[f32; 3]] {
+        &self.points[..self.level_ends[level.min(self.level_ends.len() - 1)]]
+    }
+
+    /// 点数不超过 `budget` 的最细一级（最粗一级总会选中）
+    pub fn level_for_budget(&self, budget: usize) -> usize {
+        self.level_ends.iter().rposition(|&end| end <= budget).unwrap_or(0)
+    }
+
+    /// 全分辨率数据（顺序为由粗到细）
+    pub fn points(&self) -> &[[f32; 3]] {
+        &self.points
+    }
+
+    pub fn center(&self) -> Vec3 {
+        self.center
+    }
+
+    pub fn radius(&self) -> f32 {
+        self.radius
+    }
+}
+
+/// Bevy Resource：大点云的渲染点数预算
+///
+/// `adaptive` 开启时按帧耗时在 `[min_points, max_points]` 内调节 `current`；
+/// 各点云组再按相机距离缩减（远处点云屏幕占比小，所需点数按距离平方递减）。
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct PointBudget {
+    pub adaptive: bool,
+    pub max_points: usize,
+    pub min_points: usize,
+    /// 目标帧耗时（秒）
+    pub target_frame_time: f32,
+    /// 当前全局预算
+    pub current: usize,
+    /// 平滑后的帧耗时
+    frame_time: f32,
+}
+
+impl Default for PointBudget {
+    fn default() -> Self {
+        Self {
+            adaptive: true,
+            max_points: 2_000_000,
+            min_points: 100_000,
+            target_frame_time: 1.0 / 60.0,
+            current: 2_000_000,
+            frame_time: 1.0 / 60.0,
+        }
+    }
+}
+
+impl PointBudget {
+    /// 记录一帧耗时并调节预算；超出目标 20% 时收缩，低于 70% 时放宽
+    pub fn record_frame(&mut self, delta: f32) {
+        self.frame_time = self.frame_time * 0.9 + delta * 0.1;
+        let current = if !self.adaptive {
+            self.max_points
+        } else if self.frame_time > self.target_frame_time * 1.2 {
+            self.current * 4 / 5
+        } else if self.frame_time < self.target_frame_time * 0.7 {
+            self.current + self.current / 10 + 1
+        } else {
+            self.current
+        };
+        self.current = current.clamp(self.min_points.min(self.max_points), self.max_points);
+    }
+
+    /// 点云组的预算：相机位于点云包围球内时为全局预算，之外按距离平方衰减
+    pub fn for_group(&self, lod: &PointLod, camera: Vec3) -> usize {
+        let distance = camera.distance(lod.center());
+        if distance <= lod.radius() || lod.radius() <= 0.0 {
+            return self.current;
+        }
+        let ratio = (lod.radius() / distance).powi(2).max(0.05);
+        (self.current as f32 * ratio) as usize
+    }
+}
+
+pub struct PointLodPlugin;
+
+impl Plugin for PointLodPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PointBudget>()
+            .add_systems(First, track_frame_time);
+    }
+}
+
+fn track_frame_time(time: Res<Time>, mut budget: ResMut<PointBudget>) {
+    let delta = time.delta_secs();
+    if delta <= 0.0 {
+        return;
+    }
+    budget.record_frame(delta);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(n: usize) -> Vec<[f32; 3]> {
+        (0..n * n * n)
+            .map(|i| [(i % n) as f32, ((i / n) % n) as f32, (i / (n * n)) as f32])
+            .collect()
+    }
+
+    #[test]
+    fn test_levels_are_nested_prefixes() {
+        let points = grid(40);
+        let lod = PointLod::build(&points);
+        assert!(lod.level_count() > 2);
+        assert_eq!(lod.level(lod.level_count() - 1).len(), points.len());
+
+        let mut sorted = lod.points().to_vec();
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let mut expected = points.clone();
+        expected.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert_eq!(sorted, expected, "重排后仍是全部点");
+
+        for level in 1..lod.level_count() {
+            assert!(lod.level(level - 1).len() < lod.level(level).len());
+        }
+        // 最粗一级每轴最多 17 格
+        assert!(lod.level(0).len() <= 17 * 17 * 17);
+        assert!(lod.level(0).len() >= 16 * 16 * 16);
+    }
+
+    #[test]
+    fn test_level_for_budget() {
+        let lod = PointLod::build(&grid(40));
+        assert_eq!(lod.level_for_budget(usize::MAX), lod.level_count() - 1);
+        assert_eq!(lod.level_for_budget(0), 0);
+        let level = lod.level_for_budget(10_000);
+        assert!(lod.level(level).len() <= 10_000);
+        assert!(lod.level(level + 1).len() > 10_000);
+    }
+
+    #[test]
+    fn test_degenerate_clouds() {
+        assert_eq!(PointLod::build(&[]).level(0).len(), 0);
+        let same = PointLod::build(&[[1.0, 1.0, 1.0]; 5]);
+        assert_eq!(same.level_count(), 1);
+        assert_eq!(same.level(0).len(), 5);
+    }
+
+    #[test]
+    fn test_budget_adapts_to_frame_time() {
+        let mut budget = PointBudget::default();
+        for _ in 0..100 {
+            budget.record_frame(0.1);
+        }
+        assert_eq!(budget.current, budget.min_points);
+        for _ in 0..200 {
+            budget.record_frame(0.001);
+        }
+        assert_eq!(budget.current, budget.max_points);
+
+        // 远处点云按距离平方缩减
+        let lod = PointLod::build(&grid(10));
+        assert_eq!(budget.for_group(&lod, lod.center()), budget.current);
+        let far = budget.for_group(&lod, lod.center() + Vec3::X * lod.radius() * 2.0);
+        assert_eq!(far, budget.current / 4);
+    }
+}
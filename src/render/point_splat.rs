@@ -0,0 +1,192 @@
+//! 点的屏幕空间绘制（splat）
+//!
+//! `PointList` 在多数后端只有 1 像素且不可调，稀疏点云几乎看不见。
+//! splat 模式下每个点展开为 4 个顶点（位置相同，UV 标记角点），由顶点着色器
+//! 在裁剪空间中扩成面向相机的方块或圆点，大小可按像素或世界单位设置。
+
+use bevy::light::NotShadowCaster;
+use bevy::pbr::MaterialPlugin;
+use bevy::prelude::*;
+use bevy::render::render_resource::{AsBindGroup, PrimitiveTopology};
+use bevy::asset::RenderAssetUsages;
+use bevy::mesh::Indices;
+use bevy::shader::ShaderRef;
+
+use crate::assets::materials::{GenericMaterial, generic_base_color};
+
+const SHADER_ASSET_PATH: &str = "shaders/point_splat.wgsl";
+
+/// 像素尺寸开启距离衰减时，超过该距离（世界单位）后点按距离反比缩小
+pub const ATTENUATION_DISTANCE: f32 = 10.0;
+
+/// 点的绘制形状
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PointShape {
+    /// 原生 `PointList`（1 像素，不可调大小）
+    Pixel,
+    Square,
+    Disc,
+}
+
+impl PointShape {
+    pub fn label(&self) -> &'static str {
+        match self {
+            PointShape::Pixel => "像素",
+            PointShape::Square => "方块",
+            PointShape::Disc => "圆点",
+        }
+    }
+}
+
+/// Bevy Resource：点的绘制样式（作用于按材质聚合的点云组）
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+pub struct PointStyle {
+    pub shape: PointShape,
+    /// 点大小：像素，或 `world_size` 为真时的世界单位（边长/直径）
+    pub size: f32,
+    pub world_size: bool,
+    /// 像素尺寸时近大远小（世界尺寸天然带透视）
+    pub attenuation: bool,
+}
+
+impl Default for PointStyle {
+    fn default() -> Self {
+        Self { shape: PointShape::Disc, size: 3.0, world_size: false, attenuation: false }
+    }
+}
+
+impl PointStyle {
+    /// 是否以 splat 网格绘制（否则为 `PointList`）
+    pub fn is_splat(&self) -> bool {
+        self.shape != PointShape::Pixel
+    }
+
+    /// 着色器参数：(大小, 是否世界尺寸, 衰减距离（0 为不衰减）, 是否圆点)
+    fn params(&self) -> Vec4 {
+        Vec4::new(
+            self.size,
+            if self.world_size { 1.0 } else { 0.0 },
+            if self.attenuation && !self.world_size { ATTENUATION_DISTANCE } else { 0.0 },
+            if self.shape == PointShape::Disc { 1.0 } else { 0.0 },
+        )
+    }
+}
+
+/// splat 材质 — 无光照纯色
+#[derive(Asset, TypePath, AsBindGroup, Debug, Clone)]
+pub struct PointSplatMaterial {
+    #[uniform(0)]
+    pub color: LinearRgba,
+    #[uniform(1)]
+    pub params: Vec4,
+}
+
+impl PointSplatMaterial {
+    pub fn new(style: &PointStyle) -> Self {
+        Self { color: LinearRgba::WHITE, params: style.params() }
+    }
+}
+
+impl Material for PointSplatMaterial {
+    fn vertex_shader() -> ShaderRef {
+        SHADER_ASSET_PATH.into()
+    }
+
+    fn fragment_shader() -> ShaderRef {
+        SHADER_ASSET_PATH.into()
+    }
+}
+
+/// splat 颜色来源：点云组对应的通用材质（取其 `StandardMaterial` 基础色）
+#[derive(Component)]
+pub struct PointSplatSource(pub Handle<GenericMaterial>);
+
+/// 点云组 splat 实体所需的组件
+pub fn splat_bundle(material: Handle<PointSplatMaterial>, source: Handle<GenericMaterial>) -> impl Bundle {
+    (
+        MeshMaterial3d(material),
+        PointSplatSource(source),
+        NotShadowCaster,
+        // 展开前四个顶点重合，射线检测无意义且点数多时开销大
+        Pickable::IGNORE,
+    )
+}
+
+/// 构建 splat 网格：每个点 4 个顶点（UV 为角点）与 2 个三角形
+pub fn splat_mesh(positions: &[[f32; 3]]) -> Mesh {
+    const CORNERS: [[f32; 2]; 4] = [[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]];
+    let mut vertices = Vec::with_capacity(positions.len() * 4);
+    let mut uvs = Vec::with_capacity(positions.len() * 4);
+    let mut indices = Vec::with_capacity(positions.len() * 6);
+    for (i, &p) in positions.iter().enumerate() {
+        let base = i as u32 * 4;
+        vertices.extend([p; 4]);
+        uvs.extend(CORNERS);
+        indices.extend([base, base + 1, base + 2, base, base + 2, base + 3]);
+    }
+    Mesh::new(PrimitiveTopology::TriangleList, RenderAssetUsages::default())
+        .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, vertices)
+        .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, uvs)
+        .with_inserted_indices(Indices::U32(indices))
+}
+
+pub struct PointSplatPlugin;
+
+impl Plugin for PointSplatPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PointStyle>()
+            .add_plugins(MaterialPlugin::<PointSplatMaterial>::default())
+            .add_systems(Update, sync_point_splat_materials);
+    }
+}
+
+/// 将通用材质的颜色与当前样式参数同步到 splat 材质（仅在变化时写入）
+fn sync_point_splat_materials(
+    style: Res<PointStyle>,
+    query: Query<(&PointSplatSource, &MeshMaterial3d<PointSplatMaterial>)>,
+    generic_materials: Res<Assets<GenericMaterial>>,
+    standard_materials: Res<Assets<StandardMaterial>>,
+    mut splat_materials: ResMut<Assets<PointSplatMaterial>>,
+) {
+    let params = style.params();
+    for (source, material) in &query {
+        let color = generic_base_color(&generic_materials, &standard_materials, &source.0);
+        let Some(current) = splat_materials.get(&material.0) else { continue };
+        let color = color.unwrap_or(current.color);
+        if current.color == color && current.params == params {
+            continue;
+        }
+        if let Some(splat) = splat_materials.get_mut(&material.0) {
+            splat.color = color;
+            splat.params = params;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_splat_mesh_layout() {
+        let mesh = splat_mesh(&[[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+        assert_eq!(mesh.count_vertices(), 8);
+        assert_eq!(mesh.indices().unwrap().len(), 12);
+        let Some(bevy::mesh::VertexAttributeValues::Float32x3(positions)) = mesh.attribute(Mesh::ATTRIBUTE_POSITION) else {
+            panic!("缺少位置");
+        };
+        // 同一点的 4 个角点位置相同，由着色器展开
+        assert!(positions[..4].iter().all(|&p| p == [1.0, 2.0, 3.0]));
+        assert!(positions[4..].iter().all(|&p| p == [4.0, 5.0, 6.0]));
+    }
+
+    #[test]
+    fn test_style_params() {
+        let style = PointStyle { shape: PointShape::Disc, size: 4.0, world_size: false, attenuation: true };
+        assert_eq!(style.params(), Vec4::new(4.0, 0.0, ATTENUATION_DISTANCE, 1.0));
+        // 世界尺寸本身带透视，不叠加衰减
+        let style = PointStyle { shape: PointShape::Square, size: 0.05, world_size: true, attenuation: true };
+        assert_eq!(style.params(), Vec4::new(0.05, 1.0, 0.0, 0.0));
+        assert!(!PointStyle { shape: PointShape::Pixel, ..style }.is_splat());
+    }
+}
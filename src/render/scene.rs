@@ -68,7 +68,8 @@ fn render_static_entities(
                 Mesh3d(meshes.add(Sphere::new(0.1)))
             });
         let material_handle = protocol::inpto_to_generic_material(inpto, material_manager, asset_server);
-        let bevy_t: bevy::transform::components::Transform = inpto.transform.into();
+        let bevy_t = bevy::transform::components::Transform::from(inpto.transform)
+            * crate::render::conversion::mesh_pose(&inpto.mesh);
         let render_transform = apply_coord_system(bevy_t, coord);
 
         commands.spawn((
@@ -101,31 +102,31 @@ fn spawn_default_axes(
     // X轴
     helpers::spawn_entity_with_vis(commands, meshes, asset_server, material_manager,
         &ExMesh { u_mesh: Some(UMesh::Cylinder(expto::rdmp::Cylinder { radius: axis_radius, height: axis_length })) },
-        &ExTransform { x: axis_length / 2.0, y: 0.0, z: 0.0, rx: 0.0, ry: 0.0, rz: -std::f32::consts::FRAC_PI_2, sx: 1.0, sy: 1.0, sz: 1.0 },
+        &ExTransform { x: axis_length / 2.0, y: 0.0, z: 0.0, rx: 0.0, ry: 0.0, rz: -std::f32::consts::FRAC_PI_2, sx: 1.0, sy: 1.0, sz: 1.0, rotation: None },
         "materials/base/red.toml", "X_Axis_Cylinder", coord, vis);
     helpers::spawn_entity_with_vis(commands, meshes, asset_server, material_manager,
         &ExMesh { u_mesh: Some(UMesh::Cone(expto::rdmp::Cone { radius: cone_radius, height: cone_height })) },
-        &ExTransform { x: axis_length, y: 0.0, z: 0.0, rx: 0.0, ry: 0.0, rz: -std::f32::consts::FRAC_PI_2, sx: 1.0, sy: 1.0, sz: 1.0 },
+        &ExTransform { x: axis_length, y: 0.0, z: 0.0, rx: 0.0, ry: 0.0, rz: -std::f32::consts::FRAC_PI_2, sx: 1.0, sy: 1.0, sz: 1.0, rotation: None },
         "materials/base/red.toml", "X_Axis_Cone", coord, vis);
 
     // Y轴
     helpers::spawn_entity_with_vis(commands, meshes, asset_server, material_manager,
         &ExMesh { u_mesh: Some(UMesh::Cylinder(expto::rdmp::Cylinder { radius: axis_radius, height: axis_length })) },
-        &ExTransform { x: 0.0, y: axis_length / 2.0, z: 0.0, rx: 0.0, ry: 0.0, rz: 0.0, sx: 1.0, sy: 1.0, sz: 1.0 },
+        &ExTransform { x: 0.0, y: axis_length / 2.0, z: 0.0, rx: 0.0, ry: 0.0, rz: 0.0, sx: 1.0, sy: 1.0, sz: 1.0, rotation: None },
         "materials/base/green.toml", "Y_Axis_Cylinder", coord, vis);
     helpers::spawn_entity_with_vis(commands, meshes, asset_server, material_manager,
         &ExMesh { u_mesh: Some(UMesh::Cone(expto::rdmp::Cone { radius: cone_radius, height: cone_height })) },
-        &ExTransform { x: 0.0, y: axis_length, z: 0.0, rx: 0.0, ry: 0.0, rz: 0.0, sx: 1.0, sy: 1.0, sz: 1.0 },
+        &ExTransform { x: 0.0, y: axis_length, z: 0.0, rx: 0.0, ry: 0.0, rz: 0.0, sx: 1.0, sy: 1.0, sz: 1.0, rotation: None },
         "materials/base/green.toml", "Y_Axis_Cone", coord, vis);
 
     // Z轴
     helpers::spawn_entity_with_vis(commands, meshes, asset_server, material_manager,
         &ExMesh { u_mesh: Some(UMesh::Cylinder(expto::rdmp::Cylinder { radius: axis_radius, height: axis_length })) },
-        &ExTransform { x: 0.0, y: 0.0, z: axis_length / 2.0, rx: std::f32::consts::FRAC_PI_2, ry: 0.0, rz: 0.0, sx: 1.0, sy: 1.0, sz: 1.0 },
+        &ExTransform { x: 0.0, y: 0.0, z: axis_length / 2.0, rx: std::f32::consts::FRAC_PI_2, ry: 0.0, rz: 0.0, sx: 1.0, sy: 1.0, sz: 1.0, rotation: None },
         "materials/base/blue.toml", "Z_Axis_Cylinder", coord, vis);
     helpers::spawn_entity_with_vis(commands, meshes, asset_server, material_manager,
         &ExMesh { u_mesh: Some(UMesh::Cone(expto::rdmp::Cone { radius: cone_radius, height: cone_height })) },
-        &ExTransform { x: 0.0, y: 0.0, z: axis_length, rx: std::f32::consts::FRAC_PI_2, ry: 0.0, rz: 0.0, sx: 1.0, sy: 1.0, sz: 1.0 },
+        &ExTransform { x: 0.0, y: 0.0, z: axis_length, rx: std::f32::consts::FRAC_PI_2, ry: 0.0, rz: 0.0, sx: 1.0, sy: 1.0, sz: 1.0, rotation: None },
         "materials/base/blue.toml", "Z_Axis_Cone", coord, vis);
 }
 
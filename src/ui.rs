@@ -13,6 +13,9 @@ pub mod theme;
 pub mod shell;
 pub mod notifications;
 pub mod axis_adjust;
+pub mod layers;
+pub mod display;
+pub mod scale_bar;
 
 #[derive(Component, Resource, Default)]
 pub struct UIStates {
@@ -38,6 +41,8 @@ impl Plugin for UiModule {
             .add_plugins(wheel_menu::WheelMenuGraphPlugin)
             .add_plugins(file_manager::FileManagerUiPlugin)
             .add_plugins(label::LabelUiPlugin)
-            .add_plugins(axis_adjust::AxisAdjustPlugin);
+            .add_plugins(axis_adjust::AxisAdjustPlugin)
+            // 比例尺（右下角，水杯左侧）
+            .add_plugins(scale_bar::ScaleBarPlugin);
     }
 }
@@ -1,7 +1,9 @@
 use bevy::prelude::*;
 use bevy_egui::egui;
 
+use crate::data::frame::FrameTree;
 use crate::render::coord_system::{CoordSystem, Handedness, UpAxis};
+use crate::render::ground_grid::GroundGrid;
 
 pub struct AxisAdjustPlugin;
 
@@ -13,16 +15,24 @@ impl Plugin for AxisAdjustPlugin {
 pub fn axis_adjust_content(
     ui: &mut egui::Ui,
     coord: &mut CoordSystem,
+    grid: &mut GroundGrid,
+    tf: &mut FrameTree,
 ) {
     ui.heading("坐标系");
     ui.separator();
     ui.add_space(6.0);
 
-    // ── 坐标轴显隐 ──
-    let axis_label = if coord.show_axes { "隐藏坐标轴" } else { "显示坐标轴" };
-    if ui.button(axis_label).clicked() {
-        coord.show_axes = !coord.show_axes;
-    }
+    // ── 坐标轴 / 地面网格显隐 ──
+    ui.horizontal(|ui| {
+        let axis_label = if coord.show_axes { "隐藏坐标轴" } else { "显示坐标轴" };
+        if ui.button(axis_label).clicked() {
+            coord.show_axes = !coord.show_axes;
+        }
+        let grid_label = if grid.visible { "隐藏网格" } else { "显示网格" };
+        if ui.button(grid_label).clicked() {
+            grid.visible = !grid.visible;
+        }
+    });
 
     ui.add_space(8.0);
     ui.separator();
@@ -112,4 +122,31 @@ pub fn axis_adjust_content(
         egui::Color32::from_rgb(160, 160, 160),
         format!("{} · {}", hand_desc, axis_desc),
     );
+
+    ui.add_space(8.0);
+    ui.separator();
+    ui.add_space(4.0);
+
+    fixed_frame_content(ui, tf);
+}
+
+/// 固定（显示）坐标系选择 — 带坐标系的实体都变换到该坐标系下显示
+fn fixed_frame_content(ui: &mut egui::Ui, tf: &mut FrameTree) {
+    const ROOT_LABEL: &str = "（根坐标系）";
+
+    ui.horizontal(|ui| {
+        ui.label("固定坐标系:");
+        let selected = if tf.fixed_frame.is_empty() { ROOT_LABEL } else { tf.fixed_frame.as_str() }.to_string();
+        egui::ComboBox::from_id_salt("fixed_frame")
+            .selected_text(selected)
+            .show_ui(ui, |ui| {
+                ui.selectable_value(&mut tf.fixed_frame, String::new(), ROOT_LABEL);
+                for frame_id in tf.frame_ids() {
+                    ui.selectable_value(&mut tf.fixed_frame, frame_id.clone(), frame_id);
+                }
+            });
+    });
+    if tf.is_empty() {
+        ui.colored_label(egui::Color32::from_rgb(160, 160, 160), "尚未收到坐标系变换");
+    }
 }
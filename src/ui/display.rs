@@ -0,0 +1,184 @@
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use bevy_egui::egui;
+
+use crate::data::tag::TagRegistry;
+use crate::render::box_style::{BoxStyle, BoxStyleOverride, style_label};
+use crate::render::frame_renderer::EntityMap;
+use crate::render::interaction::camera::{CameraMode, OrthographicViews};
+use crate::render::point_lod::PointBudget;
+use crate::render::point_splat::{PointShape, PointStyle};
+use crate::ui::label::TagLabelSettings;
+
+/// 显示设置面板读写的资源
+#[derive(SystemParam)]
+pub struct DisplaySettings<'w> {
+    point_style: ResMut<'w, PointStyle>,
+    point_budget: ResMut<'w, PointBudget>,
+    entity_map: Res<'w, EntityMap>,
+    box_style: ResMut<'w, BoxStyleOverride>,
+    tag_labels: ResMut<'w, TagLabelSettings>,
+    tag_registry: Res<'w, TagRegistry>,
+    camera_mode: ResMut<'w, CameraMode>,
+    orthographic: ResMut<'w, OrthographicViews>,
+}
+
+/// 侧栏中嵌入的显示设置 UI 内容（视图、点样式、大点云预算、包围盒样式、常驻标签）
+pub fn display_content(
+    ui: &mut egui::Ui,
+    settings: &mut DisplaySettings,
+) {
+    view_content(ui, &mut settings.camera_mode, &mut settings.orthographic);
+
+    ui.add_space(8.0);
+    ui.separator();
+    ui.add_space(4.0);
+
+    point_style_content(ui, &mut settings.point_style);
+
+    ui.add_space(8.0);
+    ui.separator();
+    ui.add_space(4.0);
+
+    point_budget_content(ui, &mut settings.point_budget, &settings.entity_map);
+
+    ui.add_space(8.0);
+    ui.separator();
+    ui.add_space(4.0);
+
+    box_style_content(ui, &mut settings.box_style);
+
+    ui.add_space(8.0);
+    ui.separator();
+    ui.add_space(4.0);
+
+    tag_labels_content(ui, &mut settings.tag_labels, &settings.tag_registry);
+}
+
+fn view_content(ui: &mut egui::Ui, camera_mode: &mut CameraMode, orthographic: &mut OrthographicViews) {
+    ui.heading("视图");
+    ui.separator();
+    ui.add_space(6.0);
+
+    ui.horizontal(|ui| {
+        ui.label("拖动");
+        for mode in [CameraMode::Select, CameraMode::Pan, CameraMode::Rotate, CameraMode::Zoom] {
+            if ui.selectable_label(*camera_mode == mode, mode.label()).clicked() {
+                *camera_mode = mode;
+            }
+        }
+    });
+    ui.checkbox(&mut orthographic.0, "标准视图使用正交投影")
+        .on_hover_text("俯视/侧视/前视时切换为正交投影，重置视角恢复透视");
+}
+
+fn point_style_content(ui: &mut egui::Ui, point_style: &mut PointStyle) {
+    ui.heading("点");
+    ui.separator();
+    ui.add_space(6.0);
+
+    ui.horizontal(|ui| {
+        ui.label("形状");
+        for shape in [PointShape::Pixel, PointShape::Square, PointShape::Disc] {
+            if ui.selectable_label(point_style.shape == shape, shape.label()).clicked() {
+                point_style.shape = shape;
+            }
+        }
+    });
+
+    // 像素模式为原生 PointList，大小不可调
+    ui.add_enabled_ui(point_style.is_splat(), |ui| {
+        ui.add_space(4.0);
+        ui.horizontal(|ui| {
+            ui.label("单位");
+            if ui.selectable_label(!point_style.world_size, "像素").clicked() && point_style.world_size {
+                point_style.world_size = false;
+                point_style.size = 3.0;
+            }
+            if ui.selectable_label(point_style.world_size, "世界").clicked() && !point_style.world_size {
+                point_style.world_size = true;
+                point_style.size = 0.05;
+            }
+        });
+
+        ui.add_space(4.0);
+        let slider = if point_style.world_size {
+            egui::Slider::new(&mut point_style.size, 0.005..=1.0).logarithmic(true).suffix(" m")
+        } else {
+            egui::Slider::new(&mut point_style.size, 1.0..=20.0).suffix(" px")
+        };
+        ui.add(slider.text("大小"));
+
+        ui.add_enabled_ui(!point_style.world_size, |ui| {
+            ui.checkbox(&mut point_style.attenuation, "距离衰减")
+                .on_hover_text("像素尺寸随距离缩小（世界尺寸本身近大远小）");
+        });
+    });
+}
+
+fn point_budget_content(ui: &mut egui::Ui, budget: &mut PointBudget, entity_map: &EntityMap) {
+    ui.heading("大点云");
+    ui.separator();
+    ui.add_space(6.0);
+
+    ui.checkbox(&mut budget.adaptive, "按帧耗时自适应")
+        .on_hover_text("帧耗时超出目标时减少绘制点数，空闲时逐步恢复");
+    ui.add(egui::Slider::new(&mut budget.max_points, 100_000..=20_000_000).logarithmic(true).text("点数上限"));
+
+    let (rendered, total) = entity_map.point_counts();
+    ui.add_space(4.0);
+    ui.colored_label(
+        egui::Color32::from_rgb(160, 160, 160),
+        format!("绘制 {} / {} 个点（预算 {}）", rendered, total, budget.current),
+    );
+}
+
+fn box_style_content(ui: &mut egui::Ui, box_style: &mut BoxStyleOverride) {
+    ui.heading("包围盒");
+    ui.separator();
+    ui.add_space(6.0);
+
+    ui.horizontal(|ui| {
+        ui.label("样式");
+        if ui.selectable_label(box_style.0.is_none(), style_label(BoxStyle::Auto))
+            .on_hover_text("按协议字段或材质（bounding_box 为半透明，wireframe 为线框）")
+            .clicked()
+        {
+            box_style.0 = None;
+        }
+        for style in [BoxStyle::Solid, BoxStyle::Wireframe, BoxStyle::Translucent] {
+            if ui.selectable_label(box_style.0 == Some(style), style_label(style)).clicked() {
+                box_style.0 = Some(style);
+            }
+        }
+    });
+}
+
+fn tag_labels_content(ui: &mut egui::Ui, labels: &mut TagLabelSettings, registry: &TagRegistry) {
+    ui.heading("标签");
+    ui.separator();
+    ui.add_space(6.0);
+
+    ui.checkbox(&mut labels.enabled, "常驻显示")
+        .on_hover_text("在每个实体的 Tag 偏移处显示标签");
+    ui.add_enabled_ui(labels.enabled, |ui| {
+        ui.add(egui::Slider::new(&mut labels.max_distance, 1.0..=1000.0).logarithmic(true).suffix(" m").text("显示距离"));
+        ui.add(egui::Slider::new(&mut labels.max_labels, 1..=1000).logarithmic(true).text("数量上限"));
+        ui.checkbox(&mut labels.declutter, "重叠避让")
+            .on_hover_text("与更近的标签重叠时不显示");
+
+        ui.add_space(4.0);
+        ui.label("集合:");
+        let mut collections: Vec<(&str, &str)> = registry.collections.values()
+            .map(|c| (c.name.as_str(), if c.display_name.is_empty() { c.name.as_str() } else { c.display_name.as_str() }))
+            .collect();
+        collections.sort();
+        collections.push(("", "未归属集合"));
+        for (name, display_name) in collections {
+            let mut visible = labels.collection_visible(name);
+            if ui.checkbox(&mut visible, display_name).changed() {
+                labels.set_collection_visible(name, visible);
+            }
+        }
+    });
+}
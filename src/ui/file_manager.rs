@@ -467,7 +467,7 @@ fn clear_all_scene(
         commands.entity(box_entity).despawn();
     }
     for (_, entity) in entity_map.map.drain() {
-        commands.entity(entity).despawn();
+        commands.entity(entity).try_despawn();
     }
     for pe in entity_map.drain_point_group_entities() {
         commands.entity(pe).despawn();
@@ -495,7 +495,7 @@ fn clear_all_data_system(
         commands.entity(box_entity).despawn();
     }
     for (_, entity) in entity_map.map.drain() {
-        commands.entity(entity).despawn();
+        commands.entity(entity).try_despawn();
     }
     for pe in entity_map.drain_point_group_entities() {
         commands.entity(pe).despawn();
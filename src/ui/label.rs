@@ -3,11 +3,14 @@ use bevy_egui::EguiPrimaryContextPass;
 
 pub use system::label_ui_observe;
 pub use design::HoverLabel;
+pub use billboard::TagLabelSettings;
 use design::{show_hover_label, TagEditState, TagEditResult};
 use system::apply_tag_edit;
+use billboard::show_tag_labels;
 
 pub mod system;
 pub mod design;
+pub mod billboard;
 
 pub struct LabelUiPlugin;
 
@@ -17,7 +20,8 @@ impl Plugin for LabelUiPlugin {
             .init_resource::<HoverLabel>()
             .init_resource::<TagEditState>()
             .init_resource::<TagEditResult>()
+            .init_resource::<TagLabelSettings>()
             .add_systems(Update, (label_ui_observe, apply_tag_edit))
-            .add_systems(EguiPrimaryContextPass, show_hover_label);
+            .add_systems(EguiPrimaryContextPass, (show_tag_labels, show_hover_label));
     }
 }
@@ -0,0 +1,237 @@
+//! 常驻标签 — 将所有实体的 Tag 以面向屏幕的标签绘制在 `Tag.offset` 处
+//!
+//! 标签按 `TagStyle` 绘制（字号、背景色、文字色、圆角，全零视为未设置）。
+//! 超出显示距离或在相机后方的标签被剔除；其余按距离由近到远放置，
+//! 与已放置标签重叠的跳过（避让），数量达到上限后停止。
+//! Tag 文本形如 `collection:value` 时归属该集合，可按集合开关显示。
+
+use std::collections::HashSet;
+
+use bevy::prelude::*;
+use bevy_egui::{EguiContexts, egui};
+use expto::rdmp::{Tag, TagStyle};
+
+use crate::data::frame::FrameManager;
+use crate::render::frame_renderer::EntityMap;
+
+/// 标签之间的最小间隙（像素）
+const LABEL_GAP: f32 = 2.0;
+
+/// Bevy Resource：常驻标签设置
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct TagLabelSettings {
+    pub enabled: bool,
+    /// 显示距离（世界单位），超出的标签不绘制
+    pub max_distance: f32,
+    /// 同时显示的标签数上限
+    pub max_labels: usize,
+    /// 重叠避让：与更近的标签重叠时不绘制
+    pub declutter: bool,
+    /// 隐藏的 Tag 集合（空字符串为未归属集合的 Tag）
+    pub hidden_collections: HashSet<String>,
+}
+
+impl Default for TagLabelSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_distance: 50.0,
+            max_labels: 100,
+            declutter: true,
+            hidden_collections: HashSet::new(),
+        }
+    }
+}
+
+impl TagLabelSettings {
+    pub fn collection_visible(&self, collection: &str) -> bool {
+        !self.hidden_collections.contains(collection)
+    }
+
+    pub fn set_collection_visible(&mut self, collection: &str, visible: bool) {
+        if visible {
+            self.hidden_collections.remove(collection);
+        } else {
+            self.hidden_collections.insert(collection.to_string());
+        }
+    }
+}
+
+/// Tag 所属集合：`collection:value` 取前缀，否则为空字符串
+pub fn tag_collection(text: &str) -> &str {
+    text.split_once(':').map_or("", |(collection, _)| collection)
+}
+
+/// 标签的绘制样式（egui 单位）
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct LabelLook {
+    font_size: f32,
+    background: egui::Color32,
+    text: egui::Color32,
+    corner_radius: f32,
+}
+
+impl Default for LabelLook {
+    fn default() -> Self {
+        Self {
+            font_size: 12.0,
+            background: egui::Color32::from_rgba_unmultiplied(30, 30, 30, 200),
+            text: egui::Color32::from_rgb(212, 212, 212),
+            corner_radius: 4.0,
+        }
+    }
+}
+
+fn rgba(r: f32, g: f32, b: f32, a: f32) -> egui::Color32 {
+    let channel = |v: f32| (v.clamp(0.0, 1.0) * 255.0).round() as u8;
+    egui::Color32::from_rgba_unmultiplied(channel(r), channel(g), channel(b), channel(a))
+}
+
+impl LabelLook {
+    /// 协议样式逐项覆盖默认样式；字号与圆角非正、颜色四个分量全零视为未设置
+    fn of(style: Option<&TagStyle>) -> Self {
+        let mut look = Self::default();
+        let Some(style) = style else { return look };
+        if style.font_size > 0.0 {
+            look.font_size = style.font_size;
+        }
+        if [style.bg_r, style.bg_g, style.bg_b, style.bg_a].iter().any(|c| *c != 0.0) {
+            look.background = rgba(style.bg_r, style.bg_g, style.bg_b, style.bg_a);
+        }
+        if [style.text_r, style.text_g, style.text_b, style.text_a].iter().any(|c| *c != 0.0) {
+            look.text = rgba(style.text_r, style.text_g, style.text_b, style.text_a);
+        }
+        if style.corner_radius > 0.0 {
+            look.corner_radius = style.corner_radius;
+        }
+        look
+    }
+}
+
+/// 重叠避让：按距离由近到远放置，返回放置的标签下标
+fn declutter(rects: &[(egui::Rect, f32)], max_labels: usize, avoid_overlap: bool) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..rects.len()).collect();
+    order.sort_by(|&a, &b| rects[a].1.total_cmp(&rects[b].1));
+    let mut placed: Vec<usize> = Vec::new();
+    for i in order {
+        if placed.len() >= max_labels {
+            break;
+        }
+        let rect = rects[i].0.expand(LABEL_GAP * 0.5);
+        if avoid_overlap && placed.iter().any(|&j| rects[j].0.expand(LABEL_GAP * 0.5).intersects(rect)) {
+            continue;
+        }
+        placed.push(i);
+    }
+    placed
+}
+
+/// 标签锚点的世界坐标：偏移在实体局部坐标系中，不受单位图元的网格缩放影响
+fn anchor_position(transform: &GlobalTransform, mesh_scale: Vec3, tag: &Tag) -> Vec3 {
+    let offset = tag.offset.as_ref().map_or(Vec3::ZERO, |o| Vec3::new(o.x, o.y, o.z));
+    let unscale = if mesh_scale.cmpeq(Vec3::ZERO).any() { Vec3::ONE } else { mesh_scale.recip() };
+    transform.mul_transform(Transform::from_scale(unscale)).transform_point(offset)
+}
+
+/// 绘制常驻标签（位于 egui 背景层，不遮挡面板与弹窗）
+pub fn show_tag_labels(
+    mut contexts: EguiContexts,
+    settings: Res<TagLabelSettings>,
+    frame_manager: Res<FrameManager>,
+    entity_map: Res<EntityMap>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<Camera3d>>,
+    entity_query: Query<(&GlobalTransform, &InheritedVisibility)>,
+) {
+    if !settings.enabled || settings.max_labels == 0 {
+        return;
+    }
+    let Some(keyframe) = frame_manager.get_current_keyframe() else { return };
+    let Ok((camera, camera_transform)) = camera_query.single() else { return };
+    let Ok(ctx) = contexts.ctx_mut() else { return };
+    let painter = ctx.layer_painter(egui::LayerId::background());
+    let camera_position = camera_transform.translation();
+
+    // 候选标签：可见实体上、所属集合开启、在显示距离内且位于相机前方
+    let mut labels = Vec::new();
+    for (entity_id, inpto) in keyframe.iter_entities() {
+        let Some(&entity) = entity_map.map.get(&entity_id) else { continue };
+        let Ok((transform, visibility)) = entity_query.get(entity) else { continue };
+        if !visibility.get() {
+            continue;
+        }
+        for tag in &inpto.tags {
+            if tag.text.is_empty() || !settings.collection_visible(tag_collection(&tag.text)) {
+                continue;
+            }
+            let anchor = anchor_position(transform, entity_map.mesh_scale(entity_id), tag);
+            let distance = anchor.distance(camera_position);
+            if distance > settings.max_distance {
+                continue;
+            }
+            let Ok(screen) = camera.world_to_viewport(camera_transform, anchor) else { continue };
+            let look = LabelLook::of(tag.style.as_ref());
+            let galley = painter.layout_no_wrap(tag.text.clone(), egui::FontId::proportional(look.font_size), look.text);
+            let size = galley.size() + egui::vec2(8.0, 4.0);
+            // 标签底边中点对齐锚点
+            let rect = egui::Rect::from_min_size(egui::pos2(screen.x - size.x * 0.5, screen.y - size.y), size);
+            labels.push((rect, distance, look, galley));
+        }
+    }
+
+    let rects: Vec<(egui::Rect, f32)> = labels.iter().map(|(rect, distance, ..)| (*rect, *distance)).collect();
+    // 先放置的（更近的）后绘制，压在远处标签之上
+    for i in declutter(&rects, settings.max_labels, settings.declutter).into_iter().rev() {
+        let (rect, _, look, galley) = &labels[i];
+        painter.rect_filled(*rect, look.corner_radius, look.background);
+        painter.galley(rect.min + egui::vec2(4.0, 2.0), galley.clone(), look.text);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(x: f32, y: f32) -> egui::Rect {
+        egui::Rect::from_min_size(egui::pos2(x, y), egui::vec2(40.0, 16.0))
+    }
+
+    #[test]
+    fn test_declutter_prefers_nearest() {
+        // 0 与 1 重叠，1 更近；2 独立
+        let rects = [(rect(0.0, 0.0), 10.0), (rect(20.0, 5.0), 5.0), (rect(100.0, 0.0), 20.0)];
+        assert_eq!(declutter(&rects, 10, true), vec![1, 2]);
+        assert_eq!(declutter(&rects, 10, false), vec![1, 0, 2]);
+        assert_eq!(declutter(&rects, 1, true), vec![1]);
+    }
+
+    #[test]
+    fn test_tag_collection_and_style() {
+        assert_eq!(tag_collection("semantic:road"), "semantic");
+        assert_eq!(tag_collection("障碍物"), "");
+
+        let mut settings = TagLabelSettings::default();
+        settings.set_collection_visible("semantic", false);
+        assert!(!settings.collection_visible("semantic"));
+        assert!(settings.collection_visible(""));
+
+        assert_eq!(LabelLook::of(None), LabelLook::default());
+        // 只设置字号与文字色时，背景与圆角保持默认
+        let style = TagStyle { font_size: 16.0, text_r: 1.0, text_a: 1.0, ..Default::default() };
+        let look = LabelLook::of(Some(&style));
+        assert_eq!(look.font_size, 16.0);
+        assert_eq!(look.text, egui::Color32::from_rgb(255, 0, 0));
+        assert_eq!(look.background, LabelLook::default().background);
+    }
+
+    #[test]
+    fn test_anchor_ignores_mesh_scale() {
+        let tag = Tag {
+            text: "a".into(),
+            offset: Some(expto::rdmp::ExTransform { y: 1.0, ..Default::default() }),
+            style: None,
+        };
+        let transform = GlobalTransform::from(Transform::from_xyz(1.0, 0.0, 0.0).with_scale(Vec3::splat(4.0)));
+        let anchor = anchor_position(&transform, Vec3::splat(4.0), &tag);
+        assert!(anchor.distance(Vec3::new(1.0, 1.0, 0.0)) < 1e-5);
+    }
+}
@@ -7,6 +7,8 @@ use crate::ui::UIStates;
 #[derive(Clone, Debug)]
 pub struct LabelInfo {
     pub entity_id: u64,
+    /// 来源会话（本地数据为空）
+    pub session: String,
     pub text: String,
     pub world_position: Vec3,
 }
@@ -17,9 +19,10 @@ pub struct HoverLabel {
 }
 
 impl HoverLabel {
-    pub fn show(&mut self, entity_id: u64, text: String, world_position: Vec3) {
+    pub fn show(&mut self, entity_id: u64, session: String, text: String, world_position: Vec3) {
         self.current = Some(LabelInfo {
             entity_id,
+            session,
             text,
             world_position,
         });
@@ -119,6 +122,21 @@ pub fn show_hover_label(
                         });
                     });
 
+                    if !label_info.session.is_empty() {
+                        ui.horizontal(|ui| {
+                            ui.label(
+                                egui::RichText::new("会话:")
+                                    .color(egui::Color32::from_rgb(150, 150, 150))
+                                    .size(11.0),
+                            );
+                            ui.label(
+                                egui::RichText::new(&label_info.session)
+                                    .color(egui::Color32::from_rgb(78, 201, 176))
+                                    .size(11.0),
+                            );
+                        });
+                    }
+
                     ui.separator();
 
                     if is_editing {
@@ -2,7 +2,7 @@ use bevy::prelude::*;
 
 use super::design::{HoverLabel, TagEditResult};
 use crate::data::frame::FrameManager;
-use crate::render::frame_renderer::EntityMap;
+use crate::render::frame_renderer::{EntityLabels, EntityMap};
 use crate::render::interaction::InteractionMessage;
 
 pub fn label_ui_observe(
@@ -10,20 +10,27 @@ pub fn label_ui_observe(
     mut hover_label: ResMut<HoverLabel>,
     frame_manager: Res<FrameManager>,
     entity_map: Res<EntityMap>,
-    transform_query: Query<&Transform>,
+    transform_query: Query<&GlobalTransform>,
+    labels_query: Query<Ref<EntityLabels>>,
 ) {
     let Some(id) = im.selected else {
         if hover_label.current.is_some() { hover_label.hide(); }
         return;
     };
 
-    if hover_label.current.as_ref().is_some_and(|c| c.entity_id == id) { return; }
-
     let Some(entity) = entity_map.map.get(&id) else { return; };
+    // 同一实体仅在标签被渲染层更新时刷新
+    let labels_changed = labels_query.get(*entity).is_ok_and(|labels| labels.is_changed());
+    if hover_label.current.as_ref().is_some_and(|c| c.entity_id == id) && !labels_changed { return; }
+
     let Ok(transform) = transform_query.get(*entity) else { return; };
 
     let tags_text = format_tags_from_frame_manager(&frame_manager, id);
-    hover_label.show(id, tags_text, transform.translation);
+    let session = frame_manager.get_current_keyframe()
+        .and_then(|kf| kf.get_entity(id))
+        .map(|inpto| inpto.session.clone())
+        .unwrap_or_default();
+    hover_label.show(id, session, tags_text, transform.translation());
 }
 
 /// 处理 Tag 编辑结果，写入 FrameManager 并刷新 hover label
@@ -32,7 +39,7 @@ pub fn apply_tag_edit(
     mut frame_manager: ResMut<FrameManager>,
     mut hover_label: ResMut<HoverLabel>,
     entity_map: Res<EntityMap>,
-    transform_query: Query<&Transform>,
+    transform_query: Query<&GlobalTransform>,
 ) {
     let Some((entity_id, new_text)) = edit_result.pending.take() else { return };
 
@@ -44,7 +51,10 @@ pub fn apply_tag_edit(
     // 刷新 hover label 显示
     if let Some(entity) = entity_map.map.get(&entity_id) {
         if let Ok(transform) = transform_query.get(*entity) {
-            hover_label.show(entity_id, new_text, transform.translation);
+            let session = hover_label.current.as_ref()
+                .map(|c| c.session.clone())
+                .unwrap_or_default();
+            hover_label.show(entity_id, session, new_text, transform.translation());
         }
     }
 }
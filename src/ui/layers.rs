@@ -0,0 +1,64 @@
+use bevy_egui::egui;
+
+use crate::data::frame::FrameManager;
+use crate::data::namespace::NamespaceFilter;
+
+/// 默认命名空间（空字符串）的显示名称
+const DEFAULT_NAMESPACE_LABEL: &str = "（默认）";
+
+/// 侧栏中嵌入的图层（命名空间）显隐 UI 内容
+pub fn layers_content(
+    ui: &mut egui::Ui,
+    frame_manager: &FrameManager,
+    filter: &mut NamespaceFilter,
+) {
+    ui.heading("图层");
+    ui.separator();
+    ui.add_space(6.0);
+
+    let Some(keyframe) = frame_manager.get_current_keyframe() else {
+        ui.colored_label(egui::Color32::from_rgb(160, 160, 160), "当前无帧数据");
+        return;
+    };
+    let namespaces = keyframe.namespaces();
+
+    ui.horizontal(|ui| {
+        if ui.button("全部显示").clicked() {
+            filter.show_all();
+        }
+        if ui.button("全部隐藏").clicked() {
+            for (namespace, _) in &namespaces {
+                filter.set_visible(namespace, false);
+            }
+        }
+    });
+    ui.add_space(4.0);
+
+    for (namespace, count) in &namespaces {
+        let mut visible = filter.is_visible(namespace);
+        let label = if namespace.is_empty() { DEFAULT_NAMESPACE_LABEL } else { namespace.as_str() };
+        if ui.checkbox(&mut visible, format!("{} ({})", label, count)).changed() {
+            filter.set_visible(namespace, visible);
+        }
+    }
+
+    // 隐藏的命名空间在当前帧中不存在时也列出，便于恢复
+    let mut absent: Vec<&String> = filter.hidden.iter()
+        .filter(|ns| !namespaces.iter().any(|(name, _)| name == *ns))
+        .collect();
+    if !absent.is_empty() {
+        absent.sort();
+        ui.add_space(6.0);
+        ui.colored_label(egui::Color32::from_rgb(160, 160, 160), "当前帧中未出现的已隐藏图层:");
+        let mut restored = None;
+        for namespace in absent {
+            let label = if namespace.is_empty() { DEFAULT_NAMESPACE_LABEL } else { namespace.as_str() };
+            if ui.button(format!("显示 {}", label)).clicked() {
+                restored = Some(namespace.clone());
+            }
+        }
+        if let Some(namespace) = restored {
+            filter.set_visible(&namespace, true);
+        }
+    }
+}
@@ -71,6 +71,7 @@ pub fn playback_content(
         ui.label("当前没有接收到帧数据");
         ui.label("请检查网络连接或数据源");
         ui.separator();
+        sequence_content(ui, frame_manager);
         ui.collapsing("快捷键", |ui| {
             ui.label("空格 - 播放/暂停");
             ui.label("左/右箭头 - 上一帧/下一帧");
@@ -97,6 +98,18 @@ pub fn playback_content(
             format!("{}/{}", current_frame + 1, total_frames),
         );
     });
+    if let Some(time) = frame_manager.get_current_keyframe()
+        .filter(|kf| kf.timestamp > 0)
+        .and_then(|kf| chrono::DateTime::from_timestamp_millis(kf.timestamp as i64))
+    {
+        ui.horizontal(|ui| {
+            ui.label("时间:");
+            ui.colored_label(
+                egui::Color32::from_rgb(180, 180, 180),
+                time.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M:%S%.3f").to_string(),
+            );
+        });
+    }
     ui.separator();
 
     // 播放控制按钮
@@ -163,6 +176,9 @@ pub fn playback_content(
         }
     });
 
+    ui.checkbox(&mut playback_state.follow_timestamps, "按时间戳播放")
+        .on_hover_text("按关键帧时间戳的真实间隔播放，速度按钮作为倍速");
+
     ui.separator();
 
     // 跳转 — 进度条 + 输入框
@@ -189,6 +205,7 @@ pub fn playback_content(
     });
 
     ui.separator();
+    sequence_content(ui, frame_manager);
     ui.collapsing("快捷键", |ui| {
         ui.label("空格 - 播放/暂停");
         ui.label("左/右箭头 - 上一帧/下一帧");
@@ -197,21 +214,65 @@ pub fn playback_content(
     });
 }
 
+/// 会话序列号统计 — 丢包 / 重复 / 乱序，以及重排窗口设置
+fn sequence_content(ui: &mut egui::Ui, frame_manager: &mut FrameManager) {
+    ui.collapsing("传输统计", |ui| {
+        ui.horizontal(|ui| {
+            ui.label("重排窗口:");
+            ui.add(
+                egui::DragValue::new(&mut frame_manager.sequences.reorder_window)
+                    .range(0..=256)
+                    .suffix(" Unit"),
+            )
+            .on_hover_text("每个会话最多缓冲的乱序 Unit 数，0 表示只统计不重排");
+        });
+
+        let stats = frame_manager.sequences.stats();
+        if stats.is_empty() {
+            ui.label("暂无带会话的数据");
+            return;
+        }
+        egui::Grid::new("sequence_stats").striped(true).show(ui, |ui| {
+            ui.label("会话");
+            ui.label("接收");
+            ui.label("丢失");
+            ui.label("重复");
+            ui.label("乱序");
+            ui.end_row();
+            for (session, stat) in stats {
+                ui.label(session);
+                ui.label(stat.received.to_string());
+                let gap_color = if stat.gaps > 0 {
+                    egui::Color32::from_rgb(255, 120, 100)
+                } else {
+                    egui::Color32::from_rgb(180, 180, 180)
+                };
+                ui.colored_label(gap_color, stat.gaps.to_string());
+                ui.label(stat.duplicates.to_string());
+                ui.label(stat.reordered.to_string());
+                ui.end_row();
+            }
+        });
+    });
+}
+
 const DEFAULT_EYE: Vec3 = Vec3::new(-2.5, 4.5, 9.0);
 const DEFAULT_TARGET: Vec3 = Vec3::ZERO;
 const DEFAULT_UP: Vec3 = Vec3::Y;
 
+/// 视角回正，同时恢复透视投影（标准视图可能切换为正交）
 fn reset_camera_system(
     mut reset: ResMut<ResetCameraView>,
-    mut cameras: Query<&mut LookTransform>,
+    mut cameras: Query<(&mut LookTransform, &mut Projection)>,
 ) {
     if !reset.0 {
         return;
     }
     reset.0 = false;
 
-    for mut transform in cameras.iter_mut() {
+    for (mut transform, mut projection) in cameras.iter_mut() {
         *transform = LookTransform::new(DEFAULT_EYE, DEFAULT_TARGET, DEFAULT_UP);
+        *projection = Projection::Perspective(PerspectiveProjection::default());
         log::info!("视角已回正：eye={:?}, target={:?}", DEFAULT_EYE, DEFAULT_TARGET);
     }
 }
@@ -0,0 +1,80 @@
+//! 比例尺 — 视口右下角（水杯左侧）显示当前网格间距对应的屏幕长度
+
+use bevy::prelude::*;
+use bevy_egui::{EguiContexts, EguiPrimaryContextPass, egui};
+
+use crate::render::ground_grid::{GroundGrid, MAJOR_EVERY, format_length};
+
+/// 比例尺最短长度（像素），次网格过密时改用主网格间距
+const MIN_BAR_PX: f32 = 24.0;
+
+/// 比例尺最长长度（像素）
+const MAX_BAR_PX: f32 = 240.0;
+
+pub struct ScaleBarPlugin;
+
+impl Plugin for ScaleBarPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(EguiPrimaryContextPass, scale_bar_system);
+    }
+}
+
+/// 比例尺长度（世界单位）与对应像素数；无法换算时返回 `None`
+fn scale_bar_length(grid: &GroundGrid) -> Option<(f32, f32)> {
+    if !grid.pixels_per_unit.is_finite() || grid.pixels_per_unit <= 0.0 {
+        return None;
+    }
+    let mut length = grid.spacing;
+    if length * grid.pixels_per_unit < MIN_BAR_PX {
+        length *= MAJOR_EVERY;
+    }
+    let pixels = length * grid.pixels_per_unit;
+    (MIN_BAR_PX..=MAX_BAR_PX).contains(&pixels).then_some((length, pixels))
+}
+
+fn scale_bar_system(mut contexts: EguiContexts, grid: Res<GroundGrid>) {
+    if !grid.visible {
+        return;
+    }
+    let Some((length, pixels)) = scale_bar_length(&grid) else { return };
+    let Ok(ctx) = contexts.ctx_mut() else { return };
+
+    egui::Area::new(egui::Id::new("scale_bar"))
+        .anchor(egui::Align2::RIGHT_BOTTOM, egui::vec2(-72.0, -8.0))
+        .order(egui::Order::Background)
+        .interactable(false)
+        .show(ctx, |ui| {
+            let (rect, _) = ui.allocate_exact_size(egui::vec2(pixels, 28.0), egui::Sense::hover());
+            let painter = ui.painter();
+            let color = egui::Color32::from_rgb(220, 220, 220);
+            let stroke = egui::Stroke::new(2.0, color);
+            let y = rect.bottom() - 4.0;
+            painter.line_segment([egui::pos2(rect.left(), y), egui::pos2(rect.right(), y)], stroke);
+            for x in [rect.left(), rect.right()] {
+                painter.line_segment([egui::pos2(x, y - 6.0), egui::pos2(x, y)], stroke);
+            }
+            painter.text(
+                egui::pos2(rect.center().x, y - 8.0),
+                egui::Align2::CENTER_BOTTOM,
+                format_length(length),
+                egui::FontId::proportional(12.0),
+                color,
+            );
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scale_bar_length() {
+        let grid = GroundGrid { visible: true, spacing: 1.0, pixels_per_unit: 50.0 };
+        assert_eq!(scale_bar_length(&grid), Some((1.0, 50.0)));
+        // 次网格过密时改用主网格
+        let dense = GroundGrid { pixels_per_unit: 5.0, ..grid.clone() };
+        assert_eq!(scale_bar_length(&dense), Some((10.0, 50.0)));
+        let unknown = GroundGrid { pixels_per_unit: 0.0, ..grid };
+        assert_eq!(scale_bar_length(&unknown), None);
+    }
+}
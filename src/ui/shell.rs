@@ -7,10 +7,14 @@ use crate::data::frame::{FrameManager, PlaybackState, FrameStorage};
 use crate::ui::file_manager::{FileSaveState, files_content};
 use crate::ui::playback_control::{playback_content, ResetCameraView};
 use crate::ui::axis_adjust::axis_adjust_content;
+use crate::ui::layers::layers_content;
+use crate::ui::display::{DisplaySettings, display_content};
+use crate::data::namespace::NamespaceFilter;
 use crate::render::coord_system::CoordSystem;
 use crate::ui::notifications::NotificationCenter;
 use crate::assets::fonts::FontLoadStatus;
 use crate::render::init::LightMode;
+use crate::render::ground_grid::GroundGrid;
 
 #[derive(Default, PartialEq, Eq, Clone, Copy)]
 pub enum SidebarView {
@@ -18,6 +22,8 @@ pub enum SidebarView {
     Playback,
     Files,
     AxisAdjust,
+    Layers,
+    Display,
 }
 
 #[derive(Resource, Default)]
@@ -65,8 +71,11 @@ fn shell_system(
     storage: Option<Res<FrameStorage>>,
     mut notifications: ResMut<NotificationCenter>,
     mut coord: ResMut<CoordSystem>,
+    mut namespace_filter: ResMut<NamespaceFilter>,
     mut reset_camera: ResMut<ResetCameraView>,
     mut light_mode: ResMut<LightMode>,
+    mut ground_grid: ResMut<GroundGrid>,
+    mut display_settings: DisplaySettings,
 ) {
     if cursor_options.grab_mode == bevy::window::CursorGrabMode::Locked {
         return;
@@ -127,6 +136,32 @@ fn shell_system(
 
                 ui.add_space(4.0);
 
+                // 图层
+                let ly = sidebar.active_view == SidebarView::Layers;
+                if ui
+                    .add(icon_button("☰", ly, btn_size))
+                    .on_hover_text("图层")
+                    .clicked()
+                {
+                    sidebar.active_view = SidebarView::Layers;
+                    sidebar.visible = true;
+                }
+
+                ui.add_space(4.0);
+
+                // 显示
+                let dp = sidebar.active_view == SidebarView::Display;
+                if ui
+                    .add(icon_button("●", dp, btn_size))
+                    .on_hover_text("显示")
+                    .clicked()
+                {
+                    sidebar.active_view = SidebarView::Display;
+                    sidebar.visible = true;
+                }
+
+                ui.add_space(4.0);
+
                 ui.separator();
 
                 // 面向世界中心（底部）
@@ -176,6 +211,8 @@ fn shell_system(
                     SidebarView::Playback => "回放控制",
                     SidebarView::Files => "文件管理",
                     SidebarView::AxisAdjust => "坐标系",
+                    SidebarView::Layers => "图层",
+                    SidebarView::Display => "显示",
                 };
                 ui.horizontal(|ui| {
                     ui.heading(header);
@@ -203,7 +240,13 @@ fn shell_system(
                                 files_content(ui, &frame_manager, storage.as_deref(), &mut save_state, &mut notifications);
                             }
                             SidebarView::AxisAdjust => {
-                                axis_adjust_content(ui, &mut coord);
+                                axis_adjust_content(ui, &mut coord, &mut ground_grid, &mut frame_manager.tf);
+                            }
+                            SidebarView::Layers => {
+                                layers_content(ui, &frame_manager, &mut namespace_filter);
+                            }
+                            SidebarView::Display => {
+                                display_content(ui, &mut display_settings);
                             }
                         }
                     });
@@ -1,7 +1,11 @@
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
 use bevy_wheel_menu::*;
 
 use crate::assets::fonts::FontAssets;
+use crate::render::interaction::camera::{CameraMode, CannedView, CannedViewRequest};
+use crate::ui::notifications::NotificationCenter;
+use crate::ui::playback_control::ResetCameraView;
 
 pub struct WheelMenuGraphPlugin;
 
@@ -376,11 +380,58 @@ pub fn update_wheel_visuals(
     }
 }
 
+/// 菜单项对应的操作
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WheelAction {
+    Mode(CameraMode),
+    Reset,
+    View(CannedView),
+}
+
+/// 按菜单项索引（与 [`MENU_ITEMS`] 顺序一致）取得操作
+pub fn wheel_action(index: usize) -> Option<WheelAction> {
+    Some(match index {
+        0 => WheelAction::Mode(CameraMode::Select),
+        1 => WheelAction::Mode(CameraMode::Pan),
+        2 => WheelAction::Mode(CameraMode::Rotate),
+        3 => WheelAction::Mode(CameraMode::Zoom),
+        4 => WheelAction::Reset,
+        5 => WheelAction::View(CannedView::Top),
+        6 => WheelAction::View(CannedView::Side),
+        7 => WheelAction::View(CannedView::Front),
+        _ => return None,
+    })
+}
+
+/// 菜单操作写入的相机资源
+#[derive(SystemParam)]
+pub struct CameraActions<'w> {
+    camera_mode: ResMut<'w, CameraMode>,
+    reset_camera: ResMut<'w, ResetCameraView>,
+    canned_view: ResMut<'w, CannedViewRequest>,
+    notifications: ResMut<'w, NotificationCenter>,
+}
+
+impl CameraActions<'_> {
+    fn apply(&mut self, action: WheelAction) {
+        match action {
+            WheelAction::Mode(mode) if *self.camera_mode != mode => {
+                *self.camera_mode = mode;
+                self.notifications.notify(format!("视口操作: {}", mode.label()), false);
+            }
+            WheelAction::Mode(_) => {}
+            WheelAction::Reset => self.reset_camera.0 = true,
+            WheelAction::View(view) => self.canned_view.0 = Some(view),
+        }
+    }
+}
+
 pub fn handle_wheel_select(
     mut commands: Commands,
     mut reader: MessageReader<WheelMenuSelected>,
     mut manager: ResMut<WheelMenuManager>,
     wheel_query: Query<Entity, With<WheelMenuRoot>>,
+    mut actions: CameraActions,
 ) {
     for event in reader.read() {
         let item_name = MENU_ITEMS
@@ -388,6 +439,9 @@ pub fn handle_wheel_select(
             .map(|(name, _, _)| *name)
             .unwrap_or("未知");
         info!("快捷菜单选择: {} (索引 {})", item_name, event.index);
+        if let Some(action) = wheel_action(event.index) {
+            actions.apply(action);
+        }
         manager.state.hide();
         manager.active_menu = None;
         for entity in wheel_query.iter() {
@@ -415,6 +469,22 @@ mod tests {
             assert!(end > start);
         }
     }
+    #[test]
+    fn test_wheel_actions_match_items() {
+        assert_eq!(wheel_action(0), Some(WheelAction::Mode(CameraMode::Select)));
+        for (index, (name, _, _)) in MENU_ITEMS.iter().enumerate() {
+            let action = wheel_action(index).expect("每个菜单项都有操作");
+            match action {
+                WheelAction::Mode(mode) => assert_eq!(mode.label(), *name),
+                WheelAction::Reset => assert_eq!(*name, "重置"),
+                WheelAction::View(CannedView::Top) => assert_eq!(*name, "俯视"),
+                WheelAction::View(CannedView::Side) => assert_eq!(*name, "侧视"),
+                WheelAction::View(CannedView::Front) => assert_eq!(*name, "前视"),
+            }
+        }
+        assert_eq!(wheel_action(MENU_ITEMS.len()), None);
+    }
+
     #[test]
     fn test_menu_configuration() {
         let menu = WheelMenu::default();